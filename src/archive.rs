@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+
+use crate::url::{clean_url, Url};
+
+const MAX_REDIRECTS: usize = 10; // Longest chain of recorded redirects to follow
+
+pub struct ArchivedResource {
+    pub url: Url,           // Final location of the resource
+    pub media_type: String, // MIME-type, things like "text/plain", "image/png"...
+    pub charset: String,    // "UTF-8", "UTF-16"...
+    pub data: Vec<u8>,
+}
+
+// Collection of previously saved resources, used in place of the network
+#[derive(Default)]
+pub struct Archive {
+    index: HashMap<String, usize>,   // Maps URLs to resources
+    redirects: HashMap<String, Url>, // Maps URLs to other URLs within the archive
+    resources: Vec<ArchivedResource>,
+}
+
+impl Archive {
+    pub fn new() -> Archive {
+        Archive::default()
+    }
+
+    pub fn add(&mut self, resource: ArchivedResource) {
+        let key: String = clean_url(resource.url.clone()).to_string();
+
+        // Resources which appear later take precedence over earlier ones
+        self.index.insert(key, self.resources.len());
+        self.resources.push(resource);
+    }
+
+    pub fn add_redirect(&mut self, url: &Url, location: &Url) {
+        self.redirects.insert(
+            clean_url(url.clone()).to_string(),
+            clean_url(location.clone()),
+        );
+    }
+
+    pub fn get(&self, url: &Url) -> Option<&ArchivedResource> {
+        let mut key: String = clean_url(url.clone()).to_string();
+
+        // Resources take precedence over redirects recorded for the same URL
+        for _ in 0..=MAX_REDIRECTS {
+            if let Some(i) = self.index.get(&key) {
+                return Some(&self.resources[*i]);
+            }
+
+            key = self.redirects.get(&key)?.to_string();
+        }
+
+        None
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.resources.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.resources.len()
+    }
+}
@@ -0,0 +1,257 @@
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use base64::{prelude::BASE64_STANDARD, Engine};
+use md5::Md5;
+use sha2::{Digest, Sha256};
+
+use crate::url::Url;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Credentials {
+    Password(String, String), // Used to answer Basic and Digest challenges
+    Token(String),            // Sent as bearer token along with every request
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuthRule {
+    pub origin: String, // Scheme, host and port credentials are meant for
+    pub credentials: Credentials,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuthChallenge {
+    pub scheme: String, // Always lowercase
+    pub params: HashMap<String, String>,
+}
+
+impl AuthRule {
+    pub fn matches_url(&self, url: &Url) -> bool {
+        // Credentials never get sent to other origins, not even to subdomains
+        match Url::parse(&self.origin) {
+            Ok(origin) => {
+                ["http", "https"].contains(&url.scheme()) && origin.origin() == url.origin()
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+pub fn create_authorization(
+    user: &str,
+    password: &str,
+    www_authenticate: &[String],
+    url: &Url,
+) -> Option<String> {
+    let challenges: Vec<AuthChallenge> = www_authenticate
+        .iter()
+        .flat_map(|header| parse_challenges(header))
+        .collect();
+
+    // Digest is preferred over Basic, since it doesn't reveal the password
+    if let Some(challenge) = challenges.iter().find(|c| c.scheme == "digest") {
+        let mut uri: String = url.path().to_string();
+        if let Some(query) = url.query() {
+            uri.push('?');
+            uri.push_str(query);
+        }
+        let cnonce: String = hash(
+            "SHA-256",
+            &format!(
+                "{}:{}",
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_nanos(),
+                challenge.params.get("nonce")?
+            ),
+        )?[..16]
+            .to_string();
+
+        return create_digest_authorization(challenge, user, password, &uri, &cnonce);
+    }
+
+    if challenges.iter().any(|c| c.scheme == "basic") {
+        return Some(format!(
+            "Basic {}",
+            BASE64_STANDARD.encode(format!("{}:{}", user, password))
+        ));
+    }
+
+    None
+}
+
+pub fn create_digest_authorization(
+    challenge: &AuthChallenge,
+    user: &str,
+    password: &str,
+    uri: &str,
+    cnonce: &str,
+) -> Option<String> {
+    let realm: &str = challenge.params.get("realm")?;
+    let nonce: &str = challenge.params.get("nonce")?;
+    let algorithm: &str = challenge
+        .params
+        .get("algorithm")
+        .map(String::as_str)
+        .unwrap_or("MD5");
+    let session: bool = algorithm.to_uppercase().ends_with("-SESS");
+    let qop: Option<&str> = challenge.params.get("qop").map(|qop| {
+        if qop.split(',').any(|qop| qop.trim() == "auth") {
+            "auth"
+        } else {
+            ""
+        }
+    });
+    let nc: &str = "00000001";
+
+    let mut ha1: String = hash(algorithm, &format!("{}:{}:{}", user, realm, password))?;
+    if session {
+        ha1 = hash(algorithm, &format!("{}:{}:{}", ha1, nonce, cnonce))?;
+    }
+    let ha2: String = hash(algorithm, &format!("GET:{}", uri))?;
+    let response: String = match qop {
+        Some("auth") => hash(
+            algorithm,
+            &format!("{}:{}:{}:{}:auth:{}", ha1, nonce, nc, cnonce, ha2),
+        )?,
+        // Only integrity protection is asked for, which can't be done
+        Some(_) => return None,
+        None => hash(algorithm, &format!("{}:{}:{}", ha1, nonce, ha2))?,
+    };
+
+    let mut authorization: String = format!(
+        "Digest username=\"{}\", realm=\"{}\", nonce=\"{}\", uri=\"{}\", algorithm={}, response=\"{}\"",
+        user, realm, nonce, uri, algorithm, response
+    );
+    if qop.is_some() {
+        authorization.push_str(&format!(", qop=auth, nc={}, cnonce=\"{}\"", nc, cnonce));
+    }
+    if let Some(opaque) = challenge.params.get("opaque") {
+        authorization.push_str(&format!(", opaque=\"{}\"", opaque));
+    }
+
+    Some(authorization)
+}
+
+fn hash(algorithm: &str, input: &str) -> Option<String> {
+    let digest: Vec<u8> = match algorithm.to_uppercase().trim_end_matches("-SESS") {
+        "MD5" => Md5::digest(input.as_bytes()).to_vec(),
+        "SHA-256" => Sha256::digest(input.as_bytes()).to_vec(),
+        _ => return None,
+    };
+
+    Some(digest.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+pub fn parse_challenges(header: &str) -> Vec<AuthChallenge> {
+    let mut challenges: Vec<AuthChallenge> = vec![];
+    let mut chars = header.chars().peekable();
+
+    loop {
+        while chars.peek().is_some_and(|c| c.is_whitespace() || *c == ',') {
+            chars.next();
+        }
+
+        let mut token: String = "".to_string();
+        while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != ',' && *c != '=') {
+            token.push(c);
+        }
+        if token.is_empty() {
+            break;
+        }
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+
+        if chars.next_if_eq(&'=').is_none() {
+            // Token which isn't followed by a value starts a new challenge
+            challenges.push(AuthChallenge {
+                scheme: token.to_lowercase(),
+                params: HashMap::new(),
+            });
+            continue;
+        }
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+
+        let mut value: String = "".to_string();
+        if chars.next_if_eq(&'"').is_some() {
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => value.extend(chars.next()),
+                    '"' => break,
+                    _ => value.push(c),
+                }
+            }
+        } else {
+            while let Some(c) = chars.next_if(|c| *c != ',') {
+                value.push(c);
+            }
+            // Token68 (e.g. padding of base64-encoded data)
+            while let Some(c) = chars.next_if_eq(&'=') {
+                value.push(c);
+            }
+        }
+
+        if let Some(challenge) = challenges.last_mut() {
+            challenge
+                .params
+                .insert(token.to_lowercase(), value.trim().to_string());
+        }
+    }
+
+    challenges
+}
+
+pub fn parse_netrc(contents: &str) -> Vec<AuthRule> {
+    let mut auth_rules: Vec<AuthRule> = vec![];
+    let mut machine: Option<String> = None;
+    let mut login: String = "".to_string();
+    let mut password: Option<String> = None;
+    let mut in_macro: bool = false;
+
+    let mut add_machine = |machine: Option<String>, login: &str, password: Option<String>| {
+        if let (Some(machine), Some(password)) = (machine, password) {
+            // Netrc doesn't distinguish between schemes or ports
+            for scheme in ["https", "http"] {
+                auth_rules.push(AuthRule {
+                    origin: format!("{}://{}", scheme, machine),
+                    credentials: Credentials::Password(login.to_string(), password.clone()),
+                });
+            }
+        }
+    };
+
+    for line in contents.lines() {
+        // Macro definitions end with an empty line
+        if in_macro {
+            in_macro = !line.trim().is_empty();
+            continue;
+        }
+
+        let mut tokens = line.split_whitespace();
+        while let Some(token) = tokens.next() {
+            match token {
+                "machine" | "default" => {
+                    add_machine(machine.take(), &login, password.take());
+                    login.clear();
+                    // The default entry would match any host, so it's never used
+                    if token == "machine" {
+                        machine = tokens.next().map(str::to_string);
+                    }
+                }
+                "login" => login = tokens.next().unwrap_or_default().to_string(),
+                "password" => password = tokens.next().map(str::to_string),
+                "account" => {
+                    tokens.next();
+                }
+                "macdef" => {
+                    in_macro = true;
+                    break;
+                }
+                _ => {}
+            }
+        }
+    }
+    add_machine(machine, &login, password);
+
+    auth_rules
+}
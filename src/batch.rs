@@ -0,0 +1,146 @@
+use serde_json::Value;
+
+use crate::core::{create_monolithic_document, MonolithError};
+use crate::session::Session;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BatchTarget {
+    pub target: String,
+    pub output: Option<String>, // Overrides the output path template for this target
+}
+
+pub fn batch(
+    session: &mut Session,
+    targets: &[BatchTarget],
+    mut prepare_document: impl FnMut(&mut Session, &BatchTarget), // Called before each target gets captured
+    mut save_document: impl FnMut(
+        &Session,
+        &BatchTarget,
+        Result<(Vec<u8>, Option<String>), MonolithError>,
+    ),
+) {
+    // Every document gets saved using the same session, which allows assets to be retrieved once
+    for target in targets {
+        session.start_document();
+        prepare_document(session, target);
+
+        let result = create_monolithic_document(session, target.target.clone());
+
+        save_document(session, target, result);
+    }
+}
+
+pub fn parse_batch(data: &str) -> Option<Vec<BatchTarget>> {
+    let data: &str = data.trim_start_matches('\u{feff}').trim();
+
+    if data.starts_with('[') {
+        parse_batch_json(data)
+    } else if is_batch_csv_header(data.lines().next().unwrap_or_default()) {
+        parse_batch_csv(data)
+    } else {
+        // One target per line, blank lines and comments are skipped
+        Some(
+            data.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(|line| BatchTarget {
+                    target: line.to_string(),
+                    output: None,
+                })
+                .collect(),
+        )
+    }
+}
+
+fn is_batch_csv_header(line: &str) -> bool {
+    let columns: Vec<String> = split_csv_line(line)
+        .iter()
+        .map(|column| column.trim().to_lowercase())
+        .collect();
+
+    columns.len() > 1
+        && columns
+            .iter()
+            .any(|column| column == "target" || column == "url")
+}
+
+fn parse_batch_csv(data: &str) -> Option<Vec<BatchTarget>> {
+    let mut lines = data.lines();
+    let header: Vec<String> = split_csv_line(lines.next()?)
+        .iter()
+        .map(|column| column.trim().to_lowercase())
+        .collect();
+    let target_column: usize = header
+        .iter()
+        .position(|column| column == "target" || column == "url")?;
+    let output_column: Option<usize> = header.iter().position(|column| column == "output");
+
+    let mut targets: Vec<BatchTarget> = vec![];
+    for line in lines.filter(|line| !line.trim().is_empty()) {
+        let fields: Vec<String> = split_csv_line(line);
+        let target: &str = fields.get(target_column)?.trim();
+        if target.is_empty() {
+            return None;
+        }
+
+        targets.push(BatchTarget {
+            target: target.to_string(),
+            output: output_column
+                .and_then(|column| fields.get(column))
+                .map(|output| output.trim().to_string())
+                .filter(|output| !output.is_empty()),
+        });
+    }
+
+    Some(targets)
+}
+
+fn parse_batch_json(data: &str) -> Option<Vec<BatchTarget>> {
+    let list: Value = serde_json::from_str(data).ok()?;
+
+    // Targets can be given as plain strings, or as objects which also specify output paths
+    list.as_array()?
+        .iter()
+        .map(|item| match item {
+            Value::String(target) => Some(BatchTarget {
+                target: target.to_string(),
+                output: None,
+            }),
+            Value::Object(object) => Some(BatchTarget {
+                target: object
+                    .get("target")
+                    .or_else(|| object.get("url"))?
+                    .as_str()?
+                    .to_string(),
+                output: object
+                    .get("output")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields: Vec<String> = vec![];
+    let mut field: String = "".to_string();
+    let mut quoted: bool = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                // Escaped double quote
+                chars.next();
+                field.push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+
+    fields
+}
@@ -1,14 +1,31 @@
 use std::collections::HashMap;
-use std::fs::File;
+use std::fs::{self, File};
 use std::io::{BufWriter, Write};
 use std::path::Path;
+use std::time::Duration;
 
-use redb::{Database, Error, TableDefinition};
+use chrono::{DateTime, Utc};
+use redb::{Database, Error, ReadableTable, TableDefinition};
+use serde_json::{json, Value};
+
+// Response headers which determine how long cached assets stay fresh, and how to check if they changed
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CacheHeaders {
+    pub cache_control: Option<String>,
+    pub etag: Option<String>,
+    pub expires: Option<String>,
+    pub last_modified: Option<String>,
+}
 
 pub struct CacheMetadataItem {
     data: Option<Vec<u8>>, // Asset's blob; used for caching small files or if on-disk database isn't utilized
     media_type: Option<String>, // MIME-type, things like "text/plain", "image/png"...
     charset: Option<String>, // "UTF-8", "UTF-16"...
+    url: Option<String>,   // Final URL, if the asset was redirected to it
+    headers: CacheHeaders,
+    size: usize,
+    stored: DateTime<Utc>, // When the asset was last retrieved or revalidated
+    current: bool, // Retrieved or revalidated during this run, doesn't need to be checked again
 }
 
 // #[derive(Debug)]
@@ -18,9 +35,13 @@ pub struct Cache {
     db: Option<Database>, // Pointer to database instance; None if not yet initialized or if failed to initialize
     db_ok: Option<bool>, // None by default, Some(true) if was able to initialize database, Some (false) if an error occurred
     db_file_path: Option<String>, // Filesystem path to file used for storing database
+    persistent: bool,    // Everything gets stored in the database, to be reused by future runs
 }
 
+const DB_FILE_NAME: &str = "cache.redb"; // Name of the database file within persistent cache directory
 const FILE_WRITE_BUF_LEN: usize = 1024 * 100; // On-disk cache file write buffer size (in bytes)
+const HEURISTIC_FRESHNESS_FRACTION: i32 = 10; // Assets without expiration time stay fresh for 1/10 of their age
+const METADATA_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("metadata");
 const TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("_");
 
 impl Cache {
@@ -31,6 +52,7 @@ impl Cache {
             db: None,
             db_ok: None,
             db_file_path: db_file_path.clone(),
+            persistent: false,
         };
 
         if db_file_path.is_some() {
@@ -52,7 +74,63 @@ impl Cache {
         }
     }
 
+    pub fn open(directory_path: &str) -> Option<Cache> {
+        fs::create_dir_all(directory_path).ok()?;
+
+        let db_file_path: String = Path::new(directory_path)
+            .join(DB_FILE_NAME)
+            .display()
+            .to_string();
+        let db: Database = Database::create(&db_file_path).ok()?;
+
+        // Make sure both tables exist, even if nothing has been cached yet
+        let write_txn = db.begin_write().ok()?;
+        write_txn.open_table(TABLE).ok()?;
+        write_txn.open_table(METADATA_TABLE).ok()?;
+        write_txn.commit().ok()?;
+
+        // Metadata is kept in memory, asset blobs get read from the database only when needed
+        let mut metadata: HashMap<String, CacheMetadataItem> = HashMap::new();
+        {
+            let read_txn = db.begin_read().ok()?;
+            let table = read_txn.open_table(METADATA_TABLE).ok()?;
+            for (key, value) in table.iter().ok()?.flatten() {
+                if let Some(cache_metadata_item) = parse_metadata(value.value()) {
+                    metadata.insert(key.value().to_string(), cache_metadata_item);
+                }
+            }
+        }
+
+        Some(Cache {
+            min_file_size: 0,
+            metadata,
+            db: Some(db),
+            db_ok: Some(true),
+            db_file_path: Some(db_file_path),
+            persistent: true,
+        })
+    }
+
     pub fn set(&mut self, key: &str, data: &Vec<u8>, media_type: String, charset: String) {
+        self.set_response(
+            key,
+            data,
+            media_type,
+            charset,
+            None,
+            CacheHeaders::default(),
+        );
+    }
+
+    pub fn set_response(
+        &mut self,
+        key: &str,
+        data: &Vec<u8>,
+        media_type: String,
+        charset: String,
+        url: Option<String>,
+        headers: CacheHeaders,
+    ) {
         let mut cache_metadata_item: CacheMetadataItem = CacheMetadataItem {
             data: if self.db_ok.is_some() && self.db_ok.unwrap() {
                 None
@@ -61,9 +139,25 @@ impl Cache {
             },
             media_type: Some(media_type.to_owned()),
             charset: Some(charset),
+            url,
+            size: data.len(),
+            stored: Utc::now(),
+            current: true,
+            headers,
         };
 
-        if (self.db_ok.is_none() || !self.db_ok.unwrap()) || data.len() <= self.min_file_size {
+        // Assets which aren't supposed to be stored only get reused during this run
+        let persist: bool =
+            self.persistent && !has_directive(&cache_metadata_item.headers, "no-store");
+
+        let in_memory: bool = (self.db_ok.is_none() || !self.db_ok.unwrap())
+            || if self.persistent {
+                !persist
+            } else {
+                data.len() <= self.min_file_size
+            };
+
+        if in_memory {
             cache_metadata_item.data = Some(data.to_owned().to_vec());
         } else {
             match self.db.as_ref().unwrap().begin_write() {
@@ -71,6 +165,13 @@ impl Cache {
                     {
                         let mut table = write_txn.open_table(TABLE).unwrap();
                         table.insert(key, &*data.to_owned()).unwrap();
+
+                        if persist {
+                            let mut metadata_table = write_txn.open_table(METADATA_TABLE).unwrap();
+                            metadata_table
+                                .insert(key, &*serialize_metadata(&cache_metadata_item))
+                                .unwrap();
+                        }
                     }
                     write_txn.commit().unwrap();
                 }
@@ -112,10 +213,123 @@ impl Cache {
         Err(Error::TransactionInProgress) // XXX
     }
 
+    pub fn is_persistent(&self) -> bool {
+        self.persistent
+    }
+
     pub fn contains_key(&self, key: &str) -> bool {
         self.metadata.contains_key(key)
     }
 
+    pub fn get_url(&self, key: &str) -> Option<String> {
+        self.metadata.get(key)?.url.clone()
+    }
+
+    // Fresh assets can be reused without asking the server whether they've changed
+    pub fn is_fresh(&self, key: &str) -> bool {
+        match self.metadata.get(key) {
+            Some(cache_metadata_item) => {
+                cache_metadata_item.current
+                    || Utc::now().signed_duration_since(cache_metadata_item.stored)
+                        < get_freshness_lifetime(cache_metadata_item)
+            }
+            None => false,
+        }
+    }
+
+    // Request headers which make the server respond with 304 Not Modified if the asset hasn't changed
+    pub fn get_validators(&self, key: &str) -> Vec<(String, String)> {
+        let mut validators: Vec<(String, String)> = vec![];
+
+        if let Some(cache_metadata_item) = self.metadata.get(key) {
+            if let Some(etag) = &cache_metadata_item.headers.etag {
+                validators.push(("If-None-Match".to_string(), etag.clone()));
+            }
+            if let Some(last_modified) = &cache_metadata_item.headers.last_modified {
+                validators.push(("If-Modified-Since".to_string(), last_modified.clone()));
+            }
+        }
+
+        validators
+    }
+
+    pub fn revalidate(&mut self, key: &str, headers: CacheHeaders) {
+        let Some(cache_metadata_item) = self.metadata.get_mut(key) else {
+            return;
+        };
+
+        // Responses to conditional requests only carry headers which have changed
+        let old_headers: CacheHeaders = cache_metadata_item.headers.clone();
+        cache_metadata_item.headers = CacheHeaders {
+            cache_control: headers.cache_control.or(old_headers.cache_control),
+            etag: headers.etag.or(old_headers.etag),
+            expires: headers.expires.or(old_headers.expires),
+            last_modified: headers.last_modified.or(old_headers.last_modified),
+        };
+        cache_metadata_item.stored = Utc::now();
+        cache_metadata_item.current = true;
+
+        if self.persistent && cache_metadata_item.data.is_none() {
+            if let Ok(write_txn) = self.db.as_ref().unwrap().begin_write() {
+                if let Ok(mut metadata_table) = write_txn.open_table(METADATA_TABLE) {
+                    metadata_table
+                        .insert(key, &*serialize_metadata(cache_metadata_item))
+                        .ok();
+                }
+                write_txn.commit().ok();
+            }
+        }
+    }
+
+    // Removes assets which are too old, then the oldest ones until the cache fits into given size
+    pub fn prune(&mut self, max_size: Option<usize>, max_age: Option<Duration>) -> usize {
+        let mut entries: Vec<(String, DateTime<Utc>, usize)> = self
+            .metadata
+            .iter()
+            .map(|(key, item)| (key.clone(), item.stored, item.size))
+            .collect();
+        entries.sort_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(&b.0)));
+
+        let now: DateTime<Utc> = Utc::now();
+        let mut total_size: usize = entries.iter().map(|(_, _, size)| size).sum();
+        let mut keys_to_remove: Vec<String> = vec![];
+        for (key, stored, size) in entries {
+            let too_old: bool = max_age.is_some_and(|max_age| {
+                now.signed_duration_since(stored)
+                    .to_std()
+                    .unwrap_or_default()
+                    > max_age
+            });
+            let too_big: bool = max_size.is_some_and(|max_size| total_size > max_size);
+
+            if too_old || too_big {
+                total_size -= size;
+                keys_to_remove.push(key);
+            }
+        }
+
+        for key in &keys_to_remove {
+            self.metadata.remove(key);
+        }
+
+        if self.db_ok.is_some() && self.db_ok.unwrap() && !keys_to_remove.is_empty() {
+            if let Ok(write_txn) = self.db.as_ref().unwrap().begin_write() {
+                if let (Ok(mut table), Ok(mut metadata_table)) = (
+                    write_txn.open_table(TABLE),
+                    write_txn.open_table(METADATA_TABLE),
+                ) {
+                    for key in &keys_to_remove {
+                        table.remove(key.as_str()).ok();
+                        metadata_table.remove(key.as_str()).ok();
+                    }
+                }
+                write_txn.commit().ok();
+            }
+        }
+
+        keys_to_remove.len()
+    }
+
     pub fn destroy_database_file(&mut self) {
         if self.db_ok.is_none() || !self.db_ok.unwrap() {
             return;
@@ -152,3 +366,89 @@ impl Cache {
         }
     }
 }
+
+fn get_freshness_lifetime(cache_metadata_item: &CacheMetadataItem) -> chrono::Duration {
+    let headers: &CacheHeaders = &cache_metadata_item.headers;
+
+    if has_directive(headers, "no-cache") || has_directive(headers, "no-store") {
+        return chrono::Duration::zero();
+    }
+
+    // Explicit expiration time takes precedence
+    if let Some(max_age) = headers
+        .cache_control
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|directive| directive.trim().split_once('='))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("max-age"))
+        .and_then(|(_, value)| value.trim().trim_matches('"').parse::<i64>().ok())
+    {
+        return chrono::Duration::seconds(max_age);
+    }
+    if let Some(expires) = headers.expires.as_deref().and_then(parse_http_date) {
+        return expires.signed_duration_since(cache_metadata_item.stored);
+    }
+
+    // Assets which haven't changed in a while are unlikely to change soon
+    if let Some(last_modified) = headers.last_modified.as_deref().and_then(parse_http_date) {
+        return cache_metadata_item
+            .stored
+            .signed_duration_since(last_modified)
+            / HEURISTIC_FRESHNESS_FRACTION;
+    }
+
+    chrono::Duration::zero()
+}
+
+fn has_directive(headers: &CacheHeaders, directive: &str) -> bool {
+    headers
+        .cache_control
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .any(|d| d.trim().eq_ignore_ascii_case(directive))
+}
+
+pub fn parse_http_date(date: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc2822(date.trim())
+        .ok()
+        .map(|date| date.with_timezone(&Utc))
+}
+
+fn parse_metadata(data: &[u8]) -> Option<CacheMetadataItem> {
+    let metadata: Value = serde_json::from_slice(data).ok()?;
+    let get_string = |name: &str| metadata[name].as_str().map(|value| value.to_string());
+
+    Some(CacheMetadataItem {
+        data: None,
+        media_type: Some(get_string("media_type")?),
+        charset: Some(get_string("charset")?),
+        url: get_string("url"),
+        headers: CacheHeaders {
+            cache_control: get_string("cache_control"),
+            etag: get_string("etag"),
+            expires: get_string("expires"),
+            last_modified: get_string("last_modified"),
+        },
+        size: metadata["size"].as_u64()? as usize,
+        stored: DateTime::from_timestamp(metadata["stored"].as_i64()?, 0)?,
+        current: false,
+    })
+}
+
+fn serialize_metadata(cache_metadata_item: &CacheMetadataItem) -> Vec<u8> {
+    let metadata: Value = json!({
+        "media_type": cache_metadata_item.media_type,
+        "charset": cache_metadata_item.charset,
+        "url": cache_metadata_item.url,
+        "cache_control": cache_metadata_item.headers.cache_control,
+        "etag": cache_metadata_item.headers.etag,
+        "expires": cache_metadata_item.headers.expires,
+        "last_modified": cache_metadata_item.headers.last_modified,
+        "size": cache_metadata_item.size,
+        "stored": cache_metadata_item.stored.timestamp(),
+    });
+
+    serde_json::to_vec(&metadata).unwrap()
+}
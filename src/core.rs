@@ -8,59 +8,156 @@ use std::path::Path;
 use chrono::{SecondsFormat, Utc};
 use encoding_rs::Encoding;
 use markup5ever_rcdom::RcDom;
+use reqwest::StatusCode;
 use url::Url;
 
+use crate::archive::Archive;
+use crate::auth::AuthRule;
+use crate::epub::create_epub;
+use crate::events::DocumentPhase;
+use crate::har::parse_har;
+use crate::headers::HeaderRule;
 use crate::html::{
     add_favicon, create_metadata_tag, get_base_url, get_charset, get_robots, get_title,
-    has_favicon, html_to_dom, serialize_document, set_base_url, set_charset, set_robots, walk,
+    has_favicon, html_to_dom, index_headings, serialize_document, set_base_url, set_charset,
+    set_robots, walk, Heading,
 };
-use crate::session::Session;
-use crate::url::{create_data_url, resolve_url};
-
-#[derive(Debug)]
-pub struct MonolithError {
-    details: String,
+use crate::mhtml::{create_mhtml, parse_mhtml};
+use crate::report::AssetOutcome;
+use crate::session::{EmbeddedAsset, RateLimitRule, Session};
+use crate::url::resolve_url;
+use crate::warc::create_warc;
+use crate::webarchive::create_webarchive;
+use crate::zim::create_zim;
+use crate::zip::{create_zip, ZipFile};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MonolithError {
+    BlockedDomain { url: Url },                 // Excluded by domain rules
+    Cancelled,                                  // Capture got cancelled by the caller
+    DeadlineExceeded,                           // Capture took longer than it was allowed to
+    Decode { url: Url, cause: String },         // Retrieved, but couldn't be parsed
+    FileNotFound { url: Url },                  // Local file doesn't exist
+    FileUnreadable { url: Url, cause: String }, // Local file exists, but couldn't be read
+    HttpStatus { url: Url, status: u16 },       // Server responded with error
+    IntegrityMismatch { url: Url },             // Data doesn't match integrity attribute
+    InvalidProxy { proxy: String },             // Proxy given in options can't be used
+    IsDirectory { url: Url },                   // Local path points to directory
+    Network { url: Url, cause: String },        // Request couldn't be made or response was lost
+    NotInArchive { url: Url },                  // Missing from archive assets are taken from
+    Offline { url: Url },                       // Not available without going to the network
+    Pending { url: Url },                       // Not retrieved yet (during dry run)
+    SecurityError { url: Url },                 // Local file referenced by remote document
+    Other(String),                              // Anything which isn't about particular asset
 }
 
 impl MonolithError {
-    fn new(msg: &str) -> MonolithError {
-        MonolithError {
-            details: msg.to_string(),
+    pub(crate) fn new(msg: &str) -> MonolithError {
+        MonolithError::Other(msg.to_string())
+    }
+
+    pub fn url(&self) -> Option<&Url> {
+        match self {
+            MonolithError::BlockedDomain { url }
+            | MonolithError::Decode { url, .. }
+            | MonolithError::FileNotFound { url }
+            | MonolithError::FileUnreadable { url, .. }
+            | MonolithError::HttpStatus { url, .. }
+            | MonolithError::IntegrityMismatch { url }
+            | MonolithError::IsDirectory { url }
+            | MonolithError::Network { url, .. }
+            | MonolithError::NotInArchive { url }
+            | MonolithError::Offline { url }
+            | MonolithError::Pending { url }
+            | MonolithError::SecurityError { url } => Some(url),
+            MonolithError::Cancelled
+            | MonolithError::DeadlineExceeded
+            | MonolithError::InvalidProxy { .. }
+            | MonolithError::Other(_) => None,
         }
     }
 }
 
 impl fmt::Display for MonolithError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.details)
+        match self {
+            MonolithError::BlockedDomain { url } => {
+                write!(f, "\"{}\" is blocked by domain rules", url)
+            }
+            MonolithError::Cancelled => write!(f, "capture was cancelled"),
+            MonolithError::DeadlineExceeded => write!(f, "capture ran out of time"),
+            MonolithError::Decode { url, cause } => {
+                write!(f, "could not parse \"{}\": {}", url, cause)
+            }
+            MonolithError::FileNotFound { url } => write!(f, "could not find file \"{}\"", url),
+            MonolithError::FileUnreadable { url, cause } => {
+                write!(f, "could not read file \"{}\": {}", url, cause)
+            }
+            MonolithError::HttpStatus { url, status } => match StatusCode::from_u16(*status) {
+                Ok(status) => write!(f, "could not retrieve \"{}\" ({})", url, status),
+                Err(_) => write!(f, "could not retrieve \"{}\" ({})", url, status),
+            },
+            MonolithError::IntegrityMismatch { url } => {
+                write!(f, "integrity check failed for \"{}\"", url)
+            }
+            MonolithError::InvalidProxy { proxy } => write!(f, "invalid proxy \"{}\"", proxy),
+            MonolithError::IsDirectory { url } => write!(f, "\"{}\" is a directory", url),
+            MonolithError::Network { url, cause } => {
+                write!(f, "could not retrieve \"{}\": {}", url, cause)
+            }
+            MonolithError::NotInArchive { url } => {
+                write!(f, "could not find \"{}\" within archive", url)
+            }
+            MonolithError::Offline { url } => {
+                write!(f, "could not find \"{}\" while offline", url)
+            }
+            MonolithError::Pending { url } => write!(f, "\"{}\" is yet to be retrieved", url),
+            MonolithError::SecurityError { url } => {
+                write!(f, "\"{}\" can not be embedded (security error)", url)
+            }
+            MonolithError::Other(details) => write!(f, "{}", details),
+        }
     }
 }
 
-impl Error for MonolithError {
-    fn description(&self) -> &str {
-        &self.details
-    }
-}
+impl Error for MonolithError {}
 
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
 pub enum MonolithOutputFormat {
     #[default]
     HTML,
     MHTML,
-    // WARC,
-    // ZIM,
-    // HAR,
+    WARC,
+    ZIM,
+    Directory, // HTML document along with a directory of asset files
+    EPUB,
+    ZIP,
+    WebArchive, // Safari's format
+}
+
+// What to do with assets which can't be found while offline
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub enum MonolithOfflinePolicy {
+    #[default]
+    Keep, // Keep remote references to them
+    Placeholder, // Replace them with empty placeholders
+    Fail,        // Fail to save the document
 }
 
 #[derive(Default)]
 pub struct MonolithOptions {
+    pub assets_directory: Option<String>,
+    pub auth_rules: Vec<AuthRule>, // Credentials, only sent to their own origins
     pub base_url: Option<String>,
     pub blacklist_domains: bool,
+    pub document_url: Option<String>,
     pub domains: Option<Vec<String>>,
     pub encoding: Option<String>,
+    pub header_rules: Vec<HeaderRule>, // Custom headers, only sent to matching domains
     pub ignore_errors: bool,
     pub insecure: bool,
     pub isolate: bool,
+    pub keep_partial: bool, // Save what's been captured so far if the capture gets aborted
     pub no_audio: bool,
     pub no_css: bool,
     pub no_fonts: bool,
@@ -68,9 +165,24 @@ pub struct MonolithOptions {
     pub no_images: bool,
     pub no_js: bool,
     pub no_metadata: bool,
+    pub no_proxy: Vec<String>, // Domains which get accessed directly, bypassing the proxy
     pub no_video: bool,
+    pub offline: bool, // Never use the network, only what's cached or available locally
+    pub offline_policy: MonolithOfflinePolicy,
     pub output_format: MonolithOutputFormat,
+    pub parallel_requests: usize, // Assets are retrieved one by one unless this is above 1
+    pub parallel_requests_per_host: usize, // No limit if set to 0
+    pub proxy: Option<String>,    // HTTP(S) or SOCKS proxy which all requests go through
+    pub proxy_rules: Vec<(String, String)>, // Domains which go through their own proxies instead
+    pub proxy_user: Option<String>, // Credentials for proxy authentication, as user:password
+    pub rate_limit_rules: Vec<RateLimitRule>, // Limits for specific domains
+    pub record_exchanges: bool,
+    pub request_delay: u64, // Minimum delay between requests to the same host (in milliseconds)
+    pub requests_per_second: f64, // Maximum rate of requests to the same host, no limit if set to 0
+    pub retries: usize,     // How many times to retry transient failures for each asset
+    pub retry_delay: u64, // Initial delay between retries, doubled after each one (in milliseconds)
     pub silent: bool,
+    pub time_limit: u64, // Maximum time the whole capture may take (in seconds), no limit if set to 0
     pub timeout: u64,
     pub unwrap_noscript: bool,
     pub user_agent: Option<String>,
@@ -114,11 +226,16 @@ const PLAINTEXT_MEDIA_TYPES: &[&str] = &[
 ];
 
 pub fn create_monolithic_document_from_data(
-    mut session: Session,
+    session: &mut Session,
     input_data: Vec<u8>,
     input_encoding: Option<String>,
     input_target: Option<String>,
 ) -> Result<(Vec<u8>, Option<String>), MonolithError> {
+    // E-books get stripped of JavaScript
+    if session.options.output_format == MonolithOutputFormat::EPUB {
+        session.options.no_js = true;
+    }
+
     // Validate options
     {
         // Check if custom encoding value is acceptable
@@ -138,10 +255,11 @@ pub fn create_monolithic_document_from_data(
         Url::parse("data:text/html,").unwrap()
     };
     let mut document_encoding: String = input_encoding.clone().unwrap_or("utf-8".to_string());
+    let mut dom_encoding: String = document_encoding.clone();
     let mut dom: RcDom;
 
     // Initial parse
-    dom = html_to_dom(&input_data, document_encoding.clone());
+    dom = html_to_dom(&input_data, dom_encoding.clone());
 
     // Attempt to determine document's encoding
     if let Some(html_charset) = get_charset(&dom.document) {
@@ -151,7 +269,8 @@ pub fn create_monolithic_document_from_data(
                 Encoding::for_label_no_replacement(html_charset.as_bytes())
             {
                 document_encoding = html_charset;
-                dom = html_to_dom(&input_data, document_charset.name().to_string());
+                dom_encoding = document_charset.name().to_string();
+                dom = html_to_dom(&input_data, dom_encoding.clone());
             }
         }
     }
@@ -199,8 +318,47 @@ pub fn create_monolithic_document_from_data(
         }
     }
 
+    // Websites which don't link their favicons may still have one
+    let favicon_ico_url: Option<Url> = if !session.options.no_images
+        && (base_url.scheme() == "http" || base_url.scheme() == "https")
+        && (input_target.is_some()
+            && (input_target.as_ref().unwrap().starts_with("http:")
+                || input_target.as_ref().unwrap().starts_with("https:")))
+    {
+        Some(resolve_url(&base_url, "/favicon.ico"))
+    } else {
+        None
+    };
+
+    // Retrieve assets ahead of time by going through a copy of the document (if allowed to do that concurrently)
+    session.prefetch_assets(|session| {
+        let dom: RcDom = html_to_dom(&input_data, dom_encoding.clone());
+        walk(session, &base_url, &dom.document);
+
+        if let Some(favicon_ico_url) = &favicon_ico_url {
+            if !has_favicon(&dom.document) {
+                session.retrieve_asset(&base_url, favicon_ico_url).ok();
+            }
+        }
+    });
+
     // Traverse through the document and embed remote assets
-    walk(&mut session, &base_url, &dom.document);
+    session.enter_phase(DocumentPhase::Embedding);
+    walk(session, &base_url, &dom.document);
+
+    // Documents with assets missing while offline may not be allowed to be saved
+    if let Some(missing_url) = session.take_offline_misses().first() {
+        return Err(MonolithError::Offline {
+            url: missing_url.clone(),
+        });
+    }
+
+    // Aborted captures only get saved if the caller is fine with them being incomplete
+    if !session.options.keep_partial {
+        if let Some(error) = session.abort_reason() {
+            return Err(error);
+        }
+    }
 
     // Update or add new BASE element to reroute network requests and hash-links
     if let Some(new_base_url) = session.options.base_url.clone() {
@@ -208,23 +366,21 @@ pub fn create_monolithic_document_from_data(
     }
 
     // Request and embed /favicon.ico (unless it's already linked in the document)
-    if !session.options.no_images
-        && (base_url.scheme() == "http" || base_url.scheme() == "https")
-        && (input_target.is_some()
-            && (input_target.as_ref().unwrap().starts_with("http:")
-                || input_target.as_ref().unwrap().starts_with("https:")))
-        && !has_favicon(&dom.document)
-    {
-        let favicon_ico_url: Url = resolve_url(&base_url, "/favicon.ico");
-
+    if let Some(favicon_ico_url) = favicon_ico_url.filter(|_| !has_favicon(&dom.document)) {
         match session.retrieve_asset(/*&target_url, */ &base_url, &favicon_ico_url) {
-            Ok((data, final_url, media_type, charset)) => {
-                let favicon_data_url: Url =
-                    create_data_url(&media_type, &charset, &data, &final_url);
-                dom = add_favicon(&dom.document, favicon_data_url.to_string());
+            Ok((data, final_url, media_type, charset)) if !data.is_empty() => {
+                let favicon_data_url: String =
+                    session.embed_asset(&media_type, &charset, &data, &final_url, None);
+                dom = add_favicon(&dom.document, favicon_data_url);
+                session.report_asset(
+                    &base_url,
+                    &favicon_ico_url,
+                    "/favicon.ico",
+                    AssetOutcome::Embedded,
+                );
             }
-            Err(_) => {
-                // Failed to retrieve /favicon.ico
+            _ => {
+                // Failed to retrieve /favicon.ico (or got an empty placeholder)
             }
         }
     }
@@ -241,21 +397,33 @@ pub fn create_monolithic_document_from_data(
         dom = set_charset(dom, document_encoding.clone());
     }
 
+    // E-books have to be encoded using UTF-8, their tables of contents point to document's headings
+    let mut headings: Vec<Heading> = vec![];
+    if session.options.output_format == MonolithOutputFormat::EPUB {
+        document_encoding = "UTF-8".to_string();
+        dom = set_charset(dom, document_encoding.clone());
+        headings = index_headings(&dom.document);
+    }
+
     let document_title: Option<String> = get_title(&dom.document);
 
-    if session.options.output_format == MonolithOutputFormat::HTML {
-        // Serialize DOM tree
-        let mut result: Vec<u8> = serialize_document(dom, document_encoding, &session.options);
-
-        // Prepend metadata comment tag
-        if !session.options.no_metadata && !input_target.clone().unwrap_or_default().is_empty() {
-            let mut metadata_comment: String =
-                create_metadata_tag(&Url::parse(&input_target.unwrap_or_default()).unwrap());
-            // let mut metadata_comment: String = create_metadata_tag(target);
-            metadata_comment += "\n";
-            result.splice(0..0, metadata_comment.as_bytes().to_vec());
-        }
+    // Serialize DOM tree
+    session.enter_phase(DocumentPhase::Serializing);
+    let output_encoding: String = document_encoding.clone();
+    let mut result: Vec<u8> = serialize_document(dom, document_encoding, &session.options);
+
+    // Prepend metadata comment tag
+    if !session.options.no_metadata && !input_target.clone().unwrap_or_default().is_empty() {
+        let mut metadata_comment: String =
+            create_metadata_tag(&Url::parse(&input_target.clone().unwrap_or_default()).unwrap());
+        // let mut metadata_comment: String = create_metadata_tag(target);
+        metadata_comment += "\n";
+        result.splice(0..0, metadata_comment.as_bytes().to_vec());
+    }
 
+    if session.options.output_format == MonolithOutputFormat::HTML
+        || session.options.output_format == MonolithOutputFormat::Directory
+    {
         // Ensure newline at end of result
         if result.last() != Some(&b"\n"[0]) {
             result.extend_from_slice(b"\n");
@@ -263,41 +431,118 @@ pub fn create_monolithic_document_from_data(
 
         Ok((result, document_title))
     } else if session.options.output_format == MonolithOutputFormat::MHTML {
-        // Serialize DOM tree
-        let mut result: Vec<u8> = serialize_document(dom, document_encoding, &session.options);
-
-        // Prepend metadata comment tag
-        if !session.options.no_metadata && !input_target.clone().unwrap_or_default().is_empty() {
-            let mut metadata_comment: String =
-                create_metadata_tag(&Url::parse(&input_target.unwrap_or_default()).unwrap());
-            // let mut metadata_comment: String = create_metadata_tag(target);
-            metadata_comment += "\n";
-            result.splice(0..0, metadata_comment.as_bytes().to_vec());
+        // Store the document as the first part, followed by parts that contain its assets
+        let document: EmbeddedAsset = EmbeddedAsset {
+            url: match input_target {
+                Some(input_target) => Url::parse(&input_target).unwrap(),
+                None => base_url,
+            },
+            media_type: "text/html".to_string(),
+            charset: output_encoding,
+            data: result,
+        };
+
+        Ok((
+            create_mhtml(&document, session.assets(), document_title.clone()),
+            document_title,
+        ))
+    } else if session.options.output_format == MonolithOutputFormat::WARC {
+        // Ensure newline at end of result
+        if result.last() != Some(&b"\n"[0]) {
+            result.extend_from_slice(b"\n");
         }
 
-        // Extremely hacky way to convert output to MIME
-        let mime = "MIME-Version: 1.0\r\n\
-Content-Type: multipart/related; boundary=\"----=_NextPart_000_0000\"\r\n\
-\r\n\
-------=_NextPart_000_0000\r\n\
-Content-Type: text/html; charset=\"utf-8\"\r\n\
-Content-Location: http://example.com/\r\n\
-\r\n";
-
-        result.splice(0..0, mime.as_bytes().to_vec());
-
-        let mime = "\r\n------=_NextPart_000_0000--\r\n";
+        // Wrap the document along with all HTTP transactions which were made to create it
+        let document_url: Url = match input_target {
+            Some(input_target) => Url::parse(&input_target).unwrap(),
+            None => base_url,
+        };
+
+        Ok((
+            create_warc(&document_url, &result, session.document_exchanges()),
+            document_title,
+        ))
+    } else if session.options.output_format == MonolithOutputFormat::ZIM {
+        // Store the document as the main page, with its assets as separate entries next to it
+        let document: EmbeddedAsset = EmbeddedAsset {
+            url: match input_target {
+                Some(input_target) => Url::parse(&input_target).unwrap(),
+                None => base_url,
+            },
+            media_type: "text/html".to_string(),
+            charset: output_encoding,
+            data: result,
+        };
+
+        Ok((
+            create_zim(&document, session.assets(), document_title.clone()),
+            document_title,
+        ))
+    } else if session.options.output_format == MonolithOutputFormat::EPUB {
+        // Package the document as the only chapter of the book, along with its assets
+        let document: EmbeddedAsset = EmbeddedAsset {
+            url: match input_target {
+                Some(input_target) => Url::parse(&input_target).unwrap(),
+                None => base_url,
+            },
+            media_type: "application/xhtml+xml".to_string(),
+            charset: output_encoding,
+            data: result,
+        };
+
+        Ok((
+            create_epub(
+                &document,
+                session.assets(),
+                document_title.clone(),
+                &headings,
+            ),
+            document_title,
+        ))
+    } else if session.options.output_format == MonolithOutputFormat::ZIP {
+        // Ensure newline at end of result
+        if result.last() != Some(&b"\n"[0]) {
+            result.extend_from_slice(b"\n");
+        }
 
-        result.extend_from_slice(mime.as_bytes());
+        // Put the document into the root of the archive, next to its assets
+        let mut files: Vec<ZipFile> = vec![ZipFile {
+            path: "index.html".to_string(),
+            data: result,
+            stored: false,
+        }];
+        for asset in session.assets() {
+            files.push(ZipFile {
+                path: asset.file_name(),
+                data: asset.data.clone(),
+                stored: false,
+            });
+        }
 
-        Ok((result, document_title))
+        Ok((create_zip(&files), document_title))
+    } else if session.options.output_format == MonolithOutputFormat::WebArchive {
+        // Web archives are pretty much the same thing as MHTML, just in a binary form
+        let document: EmbeddedAsset = EmbeddedAsset {
+            url: match input_target {
+                Some(input_target) => Url::parse(&input_target).unwrap(),
+                None => base_url,
+            },
+            media_type: "text/html".to_string(),
+            charset: output_encoding,
+            data: result,
+        };
+
+        Ok((
+            create_webarchive(&document, session.assets()),
+            document_title,
+        ))
     } else {
         Ok((vec![], document_title))
     }
 }
 
 pub fn create_monolithic_document(
-    mut session: Session,
+    session: &mut Session,
     target: String,
 ) -> Result<(Vec<u8>, Option<String>), MonolithError> {
     // Check if target was provided
@@ -374,8 +619,73 @@ pub fn create_monolithic_document(
         || target_url.scheme() == "https"
         || target_url.scheme() == "data"
     {
+        session.enter_phase(DocumentPhase::Retrieving);
         match session.retrieve_asset(&target_url, &target_url) {
-            Ok((retrieved_data, final_url, media_type, charset)) => {
+            Ok((mut retrieved_data, final_url, mut media_type, mut charset)) => {
+                // Unpack archives, and use the document stored within them
+                let archive: Option<(Url, Archive)> = if media_type
+                    .eq_ignore_ascii_case("multipart/related")
+                    || media_type.eq_ignore_ascii_case("message/rfc822")
+                {
+                    match parse_mhtml(&retrieved_data) {
+                        Some(parsed_archive) => Some(parsed_archive),
+                        None => {
+                            return Err(MonolithError::Decode {
+                                url: final_url,
+                                cause: "not a valid MHTML archive".to_string(),
+                            });
+                        }
+                    }
+                } else if media_type.eq_ignore_ascii_case("application/har+json") {
+                    match parse_har(&retrieved_data) {
+                        Some(parsed_archive) => Some(parsed_archive),
+                        None => {
+                            return Err(MonolithError::Decode {
+                                url: final_url,
+                                cause: "not a valid HAR file".to_string(),
+                            });
+                        }
+                    }
+                } else {
+                    None
+                };
+
+                if let Some((root_url, archive)) = archive {
+                    // Documents other than the main one can be picked by their URL
+                    let document_url: Url = match &session.options.document_url {
+                        Some(document_url) => match Url::parse(document_url) {
+                            Ok(parsed_url) => parsed_url,
+                            Err(_) => {
+                                return Err(MonolithError::new(&format!(
+                                    "could not parse document URL \"{}\"",
+                                    document_url
+                                )));
+                            }
+                        },
+                        None => root_url,
+                    };
+
+                    match archive.get(&document_url) {
+                        Some(document) => {
+                            retrieved_data = document.data.clone();
+                            media_type = document.media_type.clone();
+                            charset = document.charset.clone();
+                            target_url = document.url.clone();
+                        }
+                        None => {
+                            return Err(MonolithError::new(&format!(
+                                "could not find document \"{}\" within archive",
+                                document_url
+                            )));
+                        }
+                    }
+
+                    session.set_document_archive(archive);
+                } else if final_url != target_url {
+                    // If got redirected, set target_url to that
+                    target_url = final_url.clone();
+                }
+
                 if !media_type.eq_ignore_ascii_case("text/html")
                     && !media_type.eq_ignore_ascii_case("application/xhtml+xml")
                 {
@@ -383,16 +693,11 @@ pub fn create_monolithic_document(
                     return Ok((retrieved_data, None));
                 }
 
-                // If got redirected, set target_url to that
-                if final_url != target_url {
-                    target_url = final_url.clone();
-                }
-
                 data = retrieved_data;
                 document_encoding = Some(charset);
             }
-            Err(_) => {
-                return Err(MonolithError::new("could not retrieve target document"));
+            Err(error) => {
+                return Err(error);
             }
         }
     } else {
@@ -432,12 +737,14 @@ pub fn detect_media_type_by_file_name(filename: &str) -> String {
             "css" => "text/css",
             "flac" => "audio/flac",
             "gif" => "image/gif",
+            "har" => "application/har+json",
             "htm" | "html" => "text/html",
             "ico" => "image/x-icon",
             "jpeg" | "jpg" => "image/jpeg",
             "js" => "text/javascript",
             "json" => "application/json",
             "jsonld" => "application/ld+json",
+            "mht" | "mhtml" => "multipart/related",
             "mp3" => "audio/mpeg",
             "mp4" | "m4v" => "video/mp4",
             "ogg" => "audio/ogg",
@@ -461,6 +768,46 @@ pub fn detect_media_type_by_file_name(filename: &str) -> String {
     mime.to_string()
 }
 
+pub fn detect_file_extension_by_media_type(media_type: &str) -> String {
+    let extension: &str = match media_type.to_lowercase().as_str() {
+        "application/javascript" | "text/javascript" => "js",
+        "application/json" => "json",
+        "application/ld+json" => "jsonld",
+        "application/pdf" => "pdf",
+        "application/x-shockwave-flash" => "swf",
+        "application/xhtml+xml" => "xhtml",
+        "application/xml" | "text/xml" => "xml",
+        "audio/flac" | "audio/x-flac" => "flac",
+        "audio/mpeg" => "mp3",
+        "audio/ogg" => "ogg",
+        "audio/wav" => "wav",
+        "font/otf" => "otf",
+        "font/ttf" => "ttf",
+        "font/woff" => "woff",
+        "font/woff2" => "woff2",
+        "image/avif" => "avif",
+        "image/bmp" => "bmp",
+        "image/gif" => "gif",
+        "image/jpeg" => "jpg",
+        "image/png" => "png",
+        "image/svg+xml" => "svg",
+        "image/tiff" => "tiff",
+        "image/vnd.microsoft.icon" | "image/x-icon" => "ico",
+        "image/webp" => "webp",
+        "text/css" => "css",
+        "text/html" => "html",
+        "text/plain" => "txt",
+        "video/avi" => "avi",
+        "video/mp4" => "mp4",
+        "video/mpeg" => "mpeg",
+        "video/ogg" => "ogv",
+        "video/quicktime" => "mov",
+        "video/webm" => "webm",
+        &_ => "",
+    };
+    extension.to_string()
+}
+
 pub fn format_output_path(
     path: &str,
     document_title: &str,
@@ -484,20 +831,44 @@ pub fn format_output_path(
         )
         .replace(
             "%ext%",
-            if output_format == MonolithOutputFormat::HTML {
+            if output_format == MonolithOutputFormat::HTML
+                || output_format == MonolithOutputFormat::Directory
+            {
                 "htm"
             } else if output_format == MonolithOutputFormat::MHTML {
                 "mht"
+            } else if output_format == MonolithOutputFormat::WARC {
+                "warc"
+            } else if output_format == MonolithOutputFormat::ZIM {
+                "zim"
+            } else if output_format == MonolithOutputFormat::EPUB {
+                "epub"
+            } else if output_format == MonolithOutputFormat::ZIP {
+                "zip"
+            } else if output_format == MonolithOutputFormat::WebArchive {
+                "webarchive"
             } else {
                 ""
             },
         )
         .replace(
             "%extension%",
-            if output_format == MonolithOutputFormat::HTML {
+            if output_format == MonolithOutputFormat::HTML
+                || output_format == MonolithOutputFormat::Directory
+            {
                 "html"
             } else if output_format == MonolithOutputFormat::MHTML {
                 "mhtml"
+            } else if output_format == MonolithOutputFormat::WARC {
+                "warc"
+            } else if output_format == MonolithOutputFormat::ZIM {
+                "zim"
+            } else if output_format == MonolithOutputFormat::EPUB {
+                "epub"
+            } else if output_format == MonolithOutputFormat::ZIP {
+                "zip"
+            } else if output_format == MonolithOutputFormat::WebArchive {
+                "webarchive"
             } else {
                 ""
             },
@@ -505,6 +876,14 @@ pub fn format_output_path(
         .to_string()
 }
 
+// Header names are case-insensitive, the first one found wins
+pub fn get_header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(header_name, _)| header_name.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
 pub fn is_plaintext_media_type(media_type: &str) -> bool {
     media_type.to_lowercase().as_str().starts_with("text/")
         || PLAINTEXT_MEDIA_TYPES.contains(&media_type.to_lowercase().as_str())
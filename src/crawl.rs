@@ -0,0 +1,98 @@
+use std::collections::VecDeque;
+
+use sha2::{Digest, Sha256};
+
+use crate::core::{
+    create_monolithic_document, format_output_path, MonolithError, MonolithOutputFormat,
+};
+use crate::session::Session;
+use crate::url::{clean_url, Url};
+
+pub fn crawl(
+    session: &mut Session,
+    target: &str,
+    depth: usize,
+    mut save_page: impl FnMut(&Session, &Url, &str, Result<(Vec<u8>, Option<String>), MonolithError>),
+) -> Result<(), MonolithError> {
+    let target_url: Url =
+        match Url::parse(target).or_else(|_| Url::parse(&format!("http://{}", target))) {
+            Ok(url) if url.scheme() == "http" || url.scheme() == "https" => clean_url(url),
+            _ => {
+                return Err(MonolithError::new(&format!(
+                    "only websites can be crawled, \"{}\" is not one",
+                    target
+                )));
+            }
+        };
+
+    // Links are followed within the domain of the first page (unless domains are given explicitly)
+    let domain: Option<String> = target_url.host_str().map(|host| host.to_string());
+
+    // Pages get saved in breadth-first order, closest ones to the first page go first
+    session.add_page(&target_url);
+    let mut queue: VecDeque<(Url, usize)> = VecDeque::from([(target_url, 0)]);
+
+    while let Some((page_url, page_depth)) = queue.pop_front() {
+        session.start_document();
+        session.set_link_scope(domain.clone(), page_depth < depth);
+
+        let result = create_monolithic_document(session, page_url.to_string());
+
+        for link in session.take_links() {
+            queue.push_back((link, page_depth + 1));
+        }
+
+        let page_file_name: String =
+            create_page_file_name(&page_url, session.options.output_format.clone());
+        save_page(session, &page_url, &page_file_name, result);
+    }
+
+    session.set_link_scope(None, false);
+
+    Ok(())
+}
+
+pub fn create_page_file_name(url: &Url, output_format: MonolithOutputFormat) -> String {
+    // Pages are stored next to each other, named after their hosts and paths
+    let mut name: String = url.host_str().unwrap_or_default().to_string();
+    if let Some(port) = url.port() {
+        name.push_str(&format!("_{}", port));
+    }
+
+    let path: &str = url.path().trim_matches('/');
+    let path: &str = path
+        .strip_suffix(".html")
+        .or_else(|| path.strip_suffix(".htm"))
+        .unwrap_or(path);
+    if !path.is_empty() {
+        name.push('_');
+        name.push_str(path);
+    }
+
+    // Different queries lead to different pages
+    if let Some(query) = url.query() {
+        let mut hasher = Sha256::new();
+        hasher.update(query.as_bytes());
+        let digest: String = hasher
+            .finalize()
+            .iter()
+            .take(4)
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        name.push('_');
+        name.push_str(&digest);
+    }
+
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    format_output_path(&format!("{}.%extension%", name), "", output_format)
+}
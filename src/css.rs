@@ -2,8 +2,9 @@ use cssparser::{
     serialize_identifier, serialize_string, ParseError, Parser, ParserInput, SourcePosition, Token,
 };
 
+use crate::report::AssetOutcome;
 use crate::session::Session;
-use crate::url::{create_data_url, resolve_url, Url, EMPTY_IMAGE_DATA_URL};
+use crate::url::{resolve_url, Url, EMPTY_IMAGE_DATA_URL};
 
 const CSS_PROPS_WITH_IMAGE_URLS: &[&str] = &[
     // Universal
@@ -46,6 +47,15 @@ pub fn format_quoted_string(string: &str) -> String {
     res
 }
 
+// Describes where URL was found, for the report
+fn format_url_context(prop_name: &str) -> String {
+    if prop_name.is_empty() {
+        "url()".to_string()
+    } else {
+        format!("{}: url()", prop_name)
+    }
+}
+
 pub fn is_image_url_prop(prop_name: &str) -> bool {
     CSS_PROPS_WITH_IMAGE_URLS
         .iter()
@@ -168,20 +178,27 @@ pub fn process_css<'a>(
                             import_media_type,
                             import_charset,
                         )) => {
-                            let mut import_data_url = create_data_url(
-                                &import_media_type,
-                                &import_charset,
+                            session.report_asset(
+                                document_url,
+                                &import_full_url,
+                                "@import",
+                                AssetOutcome::Embedded,
+                            );
+                            let import_css: String = session.within_asset(|session| {
                                 embed_css(
                                     session,
                                     &import_final_url,
                                     &String::from_utf8_lossy(&import_contents),
                                 )
-                                .as_bytes(),
+                            });
+                            let import_data_url: String = session.embed_asset(
+                                &import_media_type,
+                                &import_charset,
+                                import_css.as_bytes(),
                                 &import_final_url,
+                                import_full_url.fragment(),
                             );
-                            import_data_url.set_fragment(import_full_url.fragment());
-                            result
-                                .push_str(format_quoted_string(import_data_url.as_ref()).as_str());
+                            result.push_str(format_quoted_string(&import_data_url).as_str());
                         }
                         Err(_) => {
                             // Keep remote reference if unable to retrieve the asset
@@ -191,6 +208,19 @@ pub fn process_css<'a>(
                                 result.push_str(
                                     format_quoted_string(import_full_url.as_ref()).as_str(),
                                 );
+                                session.report_asset(
+                                    document_url,
+                                    &import_full_url,
+                                    "@import",
+                                    AssetOutcome::Remote,
+                                );
+                            } else {
+                                session.report_asset(
+                                    document_url,
+                                    &import_full_url,
+                                    "@import",
+                                    AssetOutcome::Dropped,
+                                );
                             }
                         }
                     }
@@ -200,18 +230,35 @@ pub fn process_css<'a>(
                         continue;
                     }
 
+                    let resolved_url: Url = resolve_url(document_url, value);
+                    let context: String = format_url_context(&curr_prop);
+
                     if session.options.no_images && is_image_url_prop(curr_prop.as_str()) {
                         result.push_str(format_quoted_string(EMPTY_IMAGE_DATA_URL).as_str());
+                        session.report_asset(
+                            document_url,
+                            &resolved_url,
+                            &context,
+                            AssetOutcome::Excluded,
+                        );
                     } else {
-                        let resolved_url: Url = resolve_url(document_url, value);
-
                         match session.retrieve_asset(document_url, &resolved_url) {
                             Ok((data, final_url, media_type, charset)) => {
                                 // TODO: if it's @font-face, exclude definitions of non-woff/woff-2 fonts (if woff/woff-2 are present)
-                                let mut data_url =
-                                    create_data_url(&media_type, &charset, &data, &final_url);
-                                data_url.set_fragment(resolved_url.fragment());
-                                result.push_str(format_quoted_string(data_url.as_ref()).as_str());
+                                let data_url: String = session.embed_asset(
+                                    &media_type,
+                                    &charset,
+                                    &data,
+                                    &final_url,
+                                    resolved_url.fragment(),
+                                );
+                                result.push_str(format_quoted_string(&data_url).as_str());
+                                session.report_asset(
+                                    document_url,
+                                    &resolved_url,
+                                    &context,
+                                    AssetOutcome::Embedded,
+                                );
                             }
                             Err(_) => {
                                 // Keep remote reference if unable to retrieve the asset
@@ -221,6 +268,19 @@ pub fn process_css<'a>(
                                     result.push_str(
                                         format_quoted_string(resolved_url.as_ref()).as_str(),
                                     );
+                                    session.report_asset(
+                                        document_url,
+                                        &resolved_url,
+                                        &context,
+                                        AssetOutcome::Remote,
+                                    );
+                                } else {
+                                    session.report_asset(
+                                        document_url,
+                                        &resolved_url,
+                                        &context,
+                                        AssetOutcome::Dropped,
+                                    );
                                 }
                             }
                         }
@@ -293,38 +353,89 @@ pub fn process_css<'a>(
                     let full_url: Url = resolve_url(document_url, value);
                     match session.retrieve_asset(document_url, &full_url) {
                         Ok((css, final_url, media_type, charset)) => {
-                            let mut data_url = create_data_url(
+                            session.report_asset(
+                                document_url,
+                                &full_url,
+                                "@import",
+                                AssetOutcome::Embedded,
+                            );
+                            let import_css: String = session.within_asset(|session| {
+                                embed_css(session, &final_url, &String::from_utf8_lossy(&css))
+                            });
+                            let data_url: String = session.embed_asset(
                                 &media_type,
                                 &charset,
-                                embed_css(session, &final_url, &String::from_utf8_lossy(&css))
-                                    .as_bytes(),
+                                import_css.as_bytes(),
                                 &final_url,
+                                full_url.fragment(),
                             );
-                            data_url.set_fragment(full_url.fragment());
-                            result.push_str(format_quoted_string(data_url.as_ref()).as_str());
+                            result.push_str(format_quoted_string(&data_url).as_str());
                         }
                         Err(_) => {
                             // Keep remote reference if unable to retrieve the asset
                             if full_url.scheme() == "http" || full_url.scheme() == "https" {
                                 result.push_str(format_quoted_string(full_url.as_ref()).as_str());
+                                session.report_asset(
+                                    document_url,
+                                    &full_url,
+                                    "@import",
+                                    AssetOutcome::Remote,
+                                );
+                            } else {
+                                session.report_asset(
+                                    document_url,
+                                    &full_url,
+                                    "@import",
+                                    AssetOutcome::Dropped,
+                                );
                             }
                         }
                     }
                 } else if is_image_url_prop(curr_prop.as_str()) && session.options.no_images {
                     result.push_str(format_quoted_string(EMPTY_IMAGE_DATA_URL).as_str());
+                    session.report_asset(
+                        document_url,
+                        &resolve_url(document_url, value),
+                        &format_url_context(&curr_prop),
+                        AssetOutcome::Excluded,
+                    );
                 } else {
                     let full_url: Url = resolve_url(document_url, value);
+                    let context: String = format_url_context(&curr_prop);
                     match session.retrieve_asset(document_url, &full_url) {
                         Ok((data, final_url, media_type, charset)) => {
-                            let mut data_url =
-                                create_data_url(&media_type, &charset, &data, &final_url);
-                            data_url.set_fragment(full_url.fragment());
-                            result.push_str(format_quoted_string(data_url.as_ref()).as_str());
+                            let data_url: String = session.embed_asset(
+                                &media_type,
+                                &charset,
+                                &data,
+                                &final_url,
+                                full_url.fragment(),
+                            );
+                            result.push_str(format_quoted_string(&data_url).as_str());
+                            session.report_asset(
+                                document_url,
+                                &full_url,
+                                &context,
+                                AssetOutcome::Embedded,
+                            );
                         }
                         Err(_) => {
                             // Keep remote reference if unable to retrieve the asset
                             if full_url.scheme() == "http" || full_url.scheme() == "https" {
                                 result.push_str(format_quoted_string(full_url.as_ref()).as_str());
+                                session.report_asset(
+                                    document_url,
+                                    &full_url,
+                                    &context,
+                                    AssetOutcome::Remote,
+                                );
+                            } else {
+                                session.report_asset(
+                                    document_url,
+                                    &full_url,
+                                    &context,
+                                    AssetOutcome::Dropped,
+                                );
                             }
                         }
                     }
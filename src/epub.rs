@@ -0,0 +1,239 @@
+use chrono::{SecondsFormat, Utc};
+use sha2::{Digest, Sha256};
+
+use crate::html::{create_source_url, Heading};
+use crate::session::EmbeddedAsset;
+use crate::zip::{create_zip, ZipFile};
+
+const EPUB_DIRECTORY: &str = "EPUB";
+const EPUB_DOCUMENT_PATH: &str = "index.xhtml";
+const EPUB_LANGUAGE: &str = "und"; // Undetermined
+const EPUB_DEFAULT_TITLE: &str = "Untitled";
+
+pub fn create_epub(
+    document: &EmbeddedAsset,
+    assets: &[EmbeddedAsset],
+    document_title: Option<String>,
+    headings: &[Heading],
+) -> Vec<u8> {
+    let title: String = document_title
+        .filter(|title| !title.trim().is_empty())
+        .unwrap_or(EPUB_DEFAULT_TITLE.to_string());
+    let identifier: String = create_epub_identifier(&document.data);
+
+    // Link to every heading, or at least to the document itself
+    let mut toc: Vec<(String, String)> = headings
+        .iter()
+        .map(|heading| {
+            (
+                format!("{}#{}", EPUB_DOCUMENT_PATH, heading.id),
+                heading.title.clone(),
+            )
+        })
+        .collect();
+    if toc.is_empty() {
+        toc.push((EPUB_DOCUMENT_PATH.to_string(), title.clone()));
+    }
+
+    let mut files: Vec<ZipFile> = vec![
+        // Has to be the first file in the archive, and can't be compressed
+        ZipFile {
+            path: "mimetype".to_string(),
+            data: b"application/epub+zip".to_vec(),
+            stored: true,
+        },
+        ZipFile {
+            path: "META-INF/container.xml".to_string(),
+            data: create_epub_container().into_bytes(),
+            stored: false,
+        },
+        ZipFile {
+            path: format!("{}/package.opf", EPUB_DIRECTORY),
+            data: create_epub_package(document, assets, &title, &identifier).into_bytes(),
+            stored: false,
+        },
+        ZipFile {
+            path: format!("{}/nav.xhtml", EPUB_DIRECTORY),
+            data: create_epub_nav(&title, &toc).into_bytes(),
+            stored: false,
+        },
+        ZipFile {
+            path: format!("{}/toc.ncx", EPUB_DIRECTORY),
+            data: create_epub_ncx(&title, &identifier, &toc).into_bytes(),
+            stored: false,
+        },
+        ZipFile {
+            path: format!("{}/{}", EPUB_DIRECTORY, EPUB_DOCUMENT_PATH),
+            data: document.data.clone(),
+            stored: false,
+        },
+    ];
+    for asset in assets {
+        files.push(ZipFile {
+            path: format!("{}/{}", EPUB_DIRECTORY, asset.file_name()),
+            data: asset.data.clone(),
+            stored: false,
+        });
+    }
+
+    create_zip(&files)
+}
+
+pub fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn create_epub_container() -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<container version="1.0" xmlns="urn:oasis:names:tc:opendocument:xmlns:container">
+  <rootfiles>
+    <rootfile full-path="{}/package.opf" media-type="application/oebps-package+xml"/>
+  </rootfiles>
+</container>
+"#,
+        EPUB_DIRECTORY
+    )
+}
+
+fn create_epub_identifier(data: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    let digest: String = hasher
+        .finalize()
+        .iter()
+        .take(16)
+        .map(|b| format!("{:02x}", b))
+        .collect();
+
+    format!(
+        "urn:uuid:{}-{}-{}-{}-{}",
+        &digest[0..8],
+        &digest[8..12],
+        &digest[12..16],
+        &digest[16..20],
+        &digest[20..32]
+    )
+}
+
+fn create_epub_nav(title: &str, toc: &[(String, String)]) -> String {
+    let mut items: String = "".to_string();
+    for (href, label) in toc {
+        items.push_str(&format!(
+            "        <li><a href=\"{}\">{}</a></li>\n",
+            escape_xml(href),
+            escape_xml(label)
+        ));
+    }
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE html>
+<html xmlns="http://www.w3.org/1999/xhtml" xmlns:epub="http://www.idpf.org/2007/ops">
+  <head>
+    <title>{title}</title>
+  </head>
+  <body>
+    <nav epub:type="toc" id="toc">
+      <h1>{title}</h1>
+      <ol>
+{items}      </ol>
+    </nav>
+  </body>
+</html>
+"#,
+        title = escape_xml(title),
+        items = items
+    )
+}
+
+fn create_epub_ncx(title: &str, identifier: &str, toc: &[(String, String)]) -> String {
+    let mut nav_points: String = "".to_string();
+    for (i, (href, label)) in toc.iter().enumerate() {
+        nav_points.push_str(&format!(
+            "    <navPoint id=\"navpoint-{n}\" playOrder=\"{n}\">\n      <navLabel><text>{}</text></navLabel>\n      <content src=\"{}\"/>\n    </navPoint>\n",
+            escape_xml(label),
+            escape_xml(href),
+            n = i + 1
+        ));
+    }
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<ncx xmlns="http://www.daisy.org/z3986/2005/ncx/" version="2005-1">
+  <head>
+    <meta name="dtb:uid" content="{}"/>
+    <meta name="dtb:depth" content="1"/>
+    <meta name="dtb:totalPageCount" content="0"/>
+    <meta name="dtb:maxPageNumber" content="0"/>
+  </head>
+  <docTitle><text>{}</text></docTitle>
+  <navMap>
+{}  </navMap>
+</ncx>
+"#,
+        escape_xml(identifier),
+        escape_xml(title),
+        nav_points
+    )
+}
+
+fn create_epub_package(
+    document: &EmbeddedAsset,
+    assets: &[EmbeddedAsset],
+    title: &str,
+    identifier: &str,
+) -> String {
+    let mut metadata: String = format!(
+        "    <dc:identifier id=\"uid\">{}</dc:identifier>\n    <dc:title>{}</dc:title>\n    <dc:language>{}</dc:language>\n",
+        escape_xml(identifier),
+        escape_xml(title),
+        EPUB_LANGUAGE
+    );
+    if let Some(source_url) = create_source_url(&document.url) {
+        metadata.push_str(&format!(
+            "    <dc:source>{}</dc:source>\n",
+            escape_xml(source_url.as_str())
+        ));
+    }
+    metadata.push_str(&format!(
+        "    <meta property=\"dcterms:modified\">{}</meta>\n",
+        Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+    ));
+
+    let mut manifest: String = format!(
+        "    <item id=\"nav\" href=\"nav.xhtml\" media-type=\"application/xhtml+xml\" properties=\"nav\"/>\n    <item id=\"ncx\" href=\"toc.ncx\" media-type=\"application/x-dtbncx+xml\"/>\n    <item id=\"document\" href=\"{}\" media-type=\"application/xhtml+xml\"/>\n",
+        EPUB_DOCUMENT_PATH
+    );
+    for (i, asset) in assets.iter().enumerate() {
+        manifest.push_str(&format!(
+            "    <item id=\"asset-{}\" href=\"{}\" media-type=\"{}\"/>\n",
+            i + 1,
+            escape_xml(&asset.file_name()),
+            escape_xml(if asset.media_type.is_empty() {
+                "application/octet-stream"
+            } else {
+                &asset.media_type
+            })
+        ));
+    }
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<package xmlns="http://www.idpf.org/2007/opf" version="3.0" unique-identifier="uid">
+  <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+{}  </metadata>
+  <manifest>
+{}  </manifest>
+  <spine toc="ncx">
+    <itemref idref="document"/>
+  </spine>
+</package>
+"#,
+        metadata, manifest
+    )
+}
@@ -0,0 +1,76 @@
+use crate::core::MonolithError;
+use crate::url::Url;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DocumentPhase {
+    Retrieving,  // Getting the document itself
+    Prefetching, // Retrieving assets ahead of time, concurrently
+    Embedding,   // Going through the document and embedding assets into it
+    Serializing, // Turning the document into output
+}
+
+impl DocumentPhase {
+    pub fn as_str(&self) -> &str {
+        match self {
+            DocumentPhase::Retrieving => "retrieving",
+            DocumentPhase::Prefetching => "prefetching",
+            DocumentPhase::Embedding => "embedding",
+            DocumentPhase::Serializing => "serializing",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SessionEvent {
+    PhaseChanged(DocumentPhase),
+    AssetStarted {
+        url: Url,
+    },
+    // Received part of the asset's body, assets retrieved ahead of time report it while prefetching
+    BytesReceived {
+        url: Url,
+        received: usize,
+        total: Option<usize>, // Size the server said the body would be of, if any
+    },
+    AssetRetried {
+        url: Url,
+        retries: usize,
+    },
+    AssetFinished {
+        url: Url,
+        final_url: Url,
+        size: usize,
+    },
+    AssetFromCache {
+        url: Url,
+        final_url: Url,
+        size: usize,
+        revalidated: bool, // Server had to confirm that the cached copy is still good to use
+    },
+    AssetFromSource {
+        url: Url,
+        final_url: Url,
+        size: usize,
+        source: String, // Name of the source (or "archive")
+    },
+    // Asset got retrieved, but its body could only be read partially (or not at all)
+    AssetIncomplete {
+        url: Url,
+        cause: String,
+    },
+    AssetFailed {
+        url: Url,
+        error: MonolithError,
+    },
+}
+
+// Gets told about everything the session does, events may come from multiple threads
+pub trait EventListener: Send {
+    fn on_event(&mut self, event: &SessionEvent);
+}
+
+impl<F: FnMut(&SessionEvent) + Send> EventListener for F {
+    fn on_event(&mut self, event: &SessionEvent) {
+        self(event)
+    }
+}
@@ -16,7 +16,8 @@ use monolith::core::{
     create_monolithic_document, format_output_path, MonolithError, MonolithOptions,
     MonolithOutputFormat,
 };
-use monolith::session::Session;
+use monolith::events::SessionEvent;
+use monolith::session::{CancellationToken, Session};
 
 const CACHE_ASSET_FILE_SIZE_THRESHOLD: usize = 1024 * 20; // Minimum file size for on-disk caching (in bytes)
 const FILESPEC_HTML: FileSpec = FileSpec::new("HTML files", &["html"]);
@@ -24,6 +25,7 @@ const MONOLITH_GUI_WRITE_OUTPUT: druid::Selector<(Vec<u8>, Option<String>)> =
     druid::Selector::new("monolith-gui.write-output");
 const MONOLITH_GUI_ERROR: druid::Selector<MonolithError> =
     druid::Selector::new("monolith-gui.error");
+const MONOLITH_GUI_STATUS: druid::Selector<String> = druid::Selector::new("monolith-gui.status");
 const TEXT_BOX_WIDTH: f64 = 512_f64;
 
 struct Delegate;
@@ -40,6 +42,9 @@ struct AppState {
     isolate: bool,
     unwrap_noscript: bool,
     busy: bool,
+    status: String,
+    #[data(ignore)]
+    cancellation_token: Option<CancellationToken>, // Stops the capture which is in progress
 }
 
 fn main() -> Result<(), PlatformError> {
@@ -67,6 +72,8 @@ fn main() -> Result<(), PlatformError> {
         isolate: true,
         unwrap_noscript: false,
         busy: false,
+        status: "".to_string(),
+        cancellation_token: None,
     };
 
     AppLauncher::with_window(main_window)
@@ -151,9 +158,19 @@ fn ui_builder() -> impl Widget<AppState> {
         .lens(AppState::unwrap_noscript)
         .disabled_if(|state: &AppState, _env| state.busy)
         .padding(5.0);
-    let start_stop_button = Button::new(LocalizedString::new("Start"))
+    let start_stop_label = Label::dynamic(|state: &AppState, _env| {
+        if state.busy {
+            "Stop".to_string()
+        } else {
+            "Start".to_string()
+        }
+    });
+    let start_stop_button = Button::from_label(start_stop_label)
         .on_click(|ctx, state: &mut AppState, _env| {
             if state.busy {
+                if let Some(cancellation_token) = &state.cancellation_token {
+                    cancellation_token.cancel();
+                }
                 return;
             }
 
@@ -196,10 +213,25 @@ fn ui_builder() -> impl Widget<AppState> {
                 },
             ));
 
-            let session: Session = Session::new(cache, None, options);
+            let mut session: Session =
+                Session::new(cache, None, options).expect("Failed to initialize session");
+            state.cancellation_token = Some(session.cancellation_token());
 
-            thread::spawn(
-                move || match create_monolithic_document(session, thread_state.target) {
+            // Show what's currently being done next to the spinner
+            let status_handle = handle.clone();
+            session.add_listener(move |event: &SessionEvent| {
+                let status: String = match event {
+                    SessionEvent::PhaseChanged(phase) => phase.as_str().to_string(),
+                    SessionEvent::AssetStarted { url } => url.to_string(),
+                    _ => return,
+                };
+                status_handle
+                    .submit_command(MONOLITH_GUI_STATUS, status, Target::Auto)
+                    .ok();
+            });
+
+            thread::spawn(move || {
+                match create_monolithic_document(&mut session, thread_state.target) {
                     Ok(result) => {
                         handle
                             .submit_command(MONOLITH_GUI_WRITE_OUTPUT, result, Target::Auto)
@@ -216,11 +248,11 @@ fn ui_builder() -> impl Widget<AppState> {
                         // TODO: make it work again
                         //cache.unwrap().destroy_database_file();
                     }
-                },
-            );
+                }
+            });
         })
         .disabled_if(|state: &AppState, _env| {
-            state.busy || state.target.is_empty() || state.output_path.is_empty()
+            !state.busy && (state.target.is_empty() || state.output_path.is_empty())
         })
         .padding(5.0);
     let spinner = Either::new(
@@ -229,6 +261,7 @@ fn ui_builder() -> impl Widget<AppState> {
         Label::new(""),
     )
     .padding(5.0);
+    let status_label = Label::dynamic(|state: &AppState, _env| state.status.clone()).padding(5.0);
 
     Flex::column()
         .with_spacer(5_f64)
@@ -258,6 +291,7 @@ fn ui_builder() -> impl Widget<AppState> {
         )
         .with_child(start_stop_button)
         .with_child(spinner)
+        .with_child(status_label)
         .with_spacer(5_f64)
 }
 
@@ -304,11 +338,20 @@ impl AppDelegate<AppState> for Delegate {
             }
 
             state.busy = false;
+            state.status.clear();
             return Handled::Yes;
         }
         // Handle errors
-        else if let Some(_error) = cmd.get(MONOLITH_GUI_ERROR) {
+        else if let Some(error) = cmd.get(MONOLITH_GUI_ERROR) {
             state.busy = false;
+            state.status = format!("Error: {}", error);
+            return Handled::Yes;
+        }
+        // Show progress
+        else if let Some(status) = cmd.get(MONOLITH_GUI_STATUS) {
+            if state.busy {
+                state.status = status.clone();
+            }
             return Handled::Yes;
         }
 
@@ -0,0 +1,262 @@
+use std::time::Duration;
+
+use base64::{prelude::BASE64_STANDARD, Engine};
+use chrono::{DateTime, SecondsFormat, Utc};
+use encoding_rs::Encoding;
+use serde_json::{json, Value};
+
+use crate::archive::{Archive, ArchivedResource};
+use crate::core::{get_header, is_plaintext_media_type, parse_content_type};
+use crate::session::HttpExchange;
+use crate::url::{resolve_url, Url};
+
+const HAR_VERSION: &str = "1.2";
+const HAR_PAGE_ID: &str = "page_1";
+
+pub fn create_har(page_title: &str, exchanges: &[HttpExchange]) -> Vec<u8> {
+    let started_date: DateTime<Utc> = exchanges
+        .first()
+        .map(|exchange| exchange.date)
+        .unwrap_or_else(Utc::now);
+
+    let har: Value = json!({
+        "log": {
+            "version": HAR_VERSION,
+            "creator": {
+                "name": env!("CARGO_PKG_NAME"),
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+            "pages": [{
+                "startedDateTime": format_har_date(&started_date),
+                "id": HAR_PAGE_ID,
+                "title": page_title,
+                "pageTimings": {
+                    "onContentLoad": -1,
+                    "onLoad": -1,
+                },
+            }],
+            "entries": exchanges.iter().map(create_har_entry).collect::<Vec<Value>>(),
+        }
+    });
+
+    let mut result: Vec<u8> = serde_json::to_vec_pretty(&har).unwrap();
+    result.push(b'\n');
+
+    result
+}
+
+pub fn parse_har(data: &[u8]) -> Option<(Url, Archive)> {
+    let har: Value = serde_json::from_slice(data).ok()?;
+    let entries: &Vec<Value> = har["log"]["entries"].as_array()?;
+    // Only consider entries which belong to the first page when looking for the document
+    let page_id: Option<&str> = har["log"]["pages"][0]["id"].as_str();
+
+    let mut archive: Archive = Archive::new();
+    let mut document_url: Option<Url> = None;
+    let mut fallback_document_url: Option<Url> = None;
+
+    for entry in entries {
+        let url: Url = match entry["request"]["url"]
+            .as_str()
+            .and_then(|url| Url::parse(url).ok())
+        {
+            Some(url) => url,
+            None => continue,
+        };
+        let response: &Value = &entry["response"];
+        let status: u64 = response["status"].as_u64().unwrap_or(0);
+
+        if (300..400).contains(&status) {
+            let location: Option<&str> = response["redirectURL"]
+                .as_str()
+                .filter(|location| !location.is_empty())
+                .or_else(|| {
+                    response["headers"].as_array()?.iter().find_map(|header| {
+                        if header["name"].as_str()?.eq_ignore_ascii_case("Location") {
+                            header["value"].as_str()
+                        } else {
+                            None
+                        }
+                    })
+                });
+            if let Some(location) = location {
+                archive.add_redirect(&url, &resolve_url(&url, location));
+            }
+        } else if (200..300).contains(&status) {
+            let content: &Value = &response["content"];
+            // Browsers don't always store response bodies
+            let text: &str = match content["text"].as_str() {
+                Some(text) => text,
+                None => continue,
+            };
+            let content_type: &str = content["mimeType"].as_str().unwrap_or("");
+            let (media_type, charset) = if content_type.trim().is_empty() {
+                ("".to_string(), "".to_string())
+            } else {
+                let (media_type, charset, _is_base64) = parse_content_type(content_type);
+                if content_type.to_lowercase().contains("charset=") {
+                    (media_type, charset)
+                } else {
+                    (media_type, "".to_string())
+                }
+            };
+
+            let data: Vec<u8> = if content["encoding"].as_str() == Some("base64") {
+                match BASE64_STANDARD.decode(text) {
+                    Ok(decoded) => decoded,
+                    Err(_) => continue,
+                }
+            } else {
+                // Text content has already been decoded from its original charset
+                match Encoding::for_label_no_replacement(charset.as_bytes()) {
+                    Some(encoding) => encoding.encode(text).0.to_vec(),
+                    None => text.as_bytes().to_vec(),
+                }
+            };
+
+            if entry["pageref"].as_str() == page_id || page_id.is_none() {
+                if document_url.is_none()
+                    && (media_type.eq_ignore_ascii_case("text/html")
+                        || media_type.eq_ignore_ascii_case("application/xhtml+xml"))
+                {
+                    document_url = Some(url.clone());
+                }
+                if fallback_document_url.is_none() {
+                    fallback_document_url = Some(url.clone());
+                }
+            }
+
+            archive.add(ArchivedResource {
+                url,
+                media_type,
+                charset,
+                data,
+            });
+        }
+    }
+
+    document_url
+        .or(fallback_document_url)
+        .map(|document_url| (document_url, archive))
+}
+
+fn create_har_entry(exchange: &HttpExchange) -> Value {
+    let content_type: &str = get_header(&exchange.response_headers, "Content-Type").unwrap_or("");
+    let redirect_url: String = get_header(&exchange.response_headers, "Location")
+        .map(|location| resolve_url(&exchange.url, location).to_string())
+        .unwrap_or_default();
+
+    let mut content: Value = json!({
+        "size": exchange.data.len(),
+        "mimeType": content_type,
+    });
+    if !exchange.data.is_empty() {
+        let (media_type, _charset, _is_base64) = parse_content_type(content_type);
+
+        if is_plaintext_media_type(&media_type) {
+            content["text"] = json!(String::from_utf8_lossy(&exchange.data));
+        } else {
+            content["text"] = json!(BASE64_STANDARD.encode(&exchange.data));
+            content["encoding"] = json!("base64");
+        }
+    }
+
+    let mut entry: Value = json!({
+        "pageref": HAR_PAGE_ID,
+        "startedDateTime": format_har_date(&exchange.date),
+        "time": format_har_duration(&(exchange.wait + exchange.receive)),
+        "request": {
+            "method": "GET",
+            "url": exchange.url.as_str(),
+            "httpVersion": if exchange.http_version.is_empty() {
+                "HTTP/1.1"
+            } else {
+                &exchange.http_version
+            },
+            "cookies": get_header(&exchange.request_headers, "Cookie")
+                .map(parse_cookie_header)
+                .unwrap_or_default(),
+            "headers": format_har_headers(&exchange.request_headers),
+            "queryString": exchange
+                .url
+                .query_pairs()
+                .map(|(name, value)| json!({ "name": name, "value": value }))
+                .collect::<Vec<Value>>(),
+            "headersSize": -1,
+            "bodySize": 0,
+        },
+        "response": {
+            "status": exchange.status,
+            "statusText": reqwest::StatusCode::from_u16(exchange.status)
+                .ok()
+                .and_then(|status| status.canonical_reason())
+                .unwrap_or(""),
+            "httpVersion": exchange.http_version,
+            "cookies": [],
+            "headers": format_har_headers(&exchange.response_headers),
+            "content": content,
+            "redirectURL": redirect_url,
+            "headersSize": -1,
+            // Responses served from cache don't transfer any data
+            "bodySize": if exchange.from_cache || exchange.error.is_some() {
+                0
+            } else {
+                exchange.data.len()
+            },
+        },
+        "cache": {},
+        "timings": {
+            "blocked": -1,
+            "dns": -1,
+            "connect": -1,
+            "send": 0,
+            "wait": format_har_duration(&exchange.wait),
+            "receive": format_har_duration(&exchange.receive),
+            "ssl": -1,
+        },
+    });
+
+    if let Some(remote_address) = &exchange.remote_address {
+        entry["serverIPAddress"] = json!(remote_address);
+    }
+    if exchange.from_cache {
+        entry["cache"] = json!({
+            "beforeRequest": {
+                "lastAccess": format_har_date(&exchange.date),
+                "eTag": "",
+                "hitCount": 1,
+            }
+        });
+        entry["_fromCache"] = json!("disk");
+    }
+    if let Some(error) = &exchange.error {
+        // Custom fields are prefixed with an underscore
+        entry["_error"] = json!(error);
+    }
+
+    entry
+}
+
+fn format_har_date(date: &DateTime<Utc>) -> String {
+    date.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+fn format_har_duration(duration: &Duration) -> f64 {
+    // Milliseconds
+    (duration.as_secs_f64() * 1000000.0).round() / 1000.0
+}
+
+fn format_har_headers(headers: &[(String, String)]) -> Vec<Value> {
+    headers
+        .iter()
+        .map(|(name, value)| json!({ "name": name, "value": value }))
+        .collect()
+}
+
+fn parse_cookie_header(value: &str) -> Vec<Value> {
+    value
+        .split(';')
+        .filter_map(|cookie| cookie.split_once('='))
+        .map(|(name, value)| json!({ "name": name.trim(), "value": value.trim() }))
+        .collect()
+}
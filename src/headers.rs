@@ -0,0 +1,58 @@
+use reqwest::header::{HeaderName, HeaderValue};
+
+use crate::url::{domain_is_within_domain, Url};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct HeaderRule {
+    pub domain: String, // Leading dot makes the rule apply to subdomains as well
+    pub name: String,
+    pub value: String,
+}
+
+impl HeaderRule {
+    pub fn matches_url(&self, url: &Url) -> bool {
+        // Should never match URLs of protocols other than HTTP(S)
+        if !["http", "https"].contains(&url.scheme()) {
+            return false;
+        }
+
+        match url.host_str() {
+            Some(host) => domain_is_within_domain(host, &self.domain),
+            None => false,
+        }
+    }
+}
+
+pub fn parse_header(input: &str) -> Option<(String, String)> {
+    let (name, value) = input.split_once(':')?;
+    let name: &str = name.trim();
+    let value: &str = value.trim();
+
+    // Make sure the header can actually be sent
+    HeaderName::from_bytes(name.as_bytes()).ok()?;
+    HeaderValue::from_str(value).ok()?;
+
+    Some((name.to_string(), value.to_string()))
+}
+
+pub fn parse_header_rules_file_contents(contents: &str) -> Option<Vec<HeaderRule>> {
+    let mut header_rules: Vec<HeaderRule> = vec![];
+
+    // One rule per line, domain followed by header, blank lines and comments are skipped
+    for line in contents.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (domain, header) = line.split_once(char::is_whitespace)?;
+        let (name, value) = parse_header(header)?;
+
+        header_rules.push(HeaderRule {
+            domain: domain.to_string(),
+            name,
+            value,
+        });
+    }
+
+    Some(header_rules)
+}
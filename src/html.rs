@@ -11,14 +11,14 @@ use markup5ever_rcdom::{Handle, NodeData, RcDom, SerializableHandle};
 use regex::Regex;
 use sha2::{Digest, Sha256, Sha384, Sha512};
 use std::default::Default;
+use xml5ever::serialize::{serialize as xml_serialize, SerializeOpts as XmlSerializeOpts};
 
-use crate::core::{parse_content_type, MonolithOptions};
+use crate::core::{parse_content_type, MonolithError, MonolithOptions, MonolithOutputFormat};
 use crate::css::embed_css;
 use crate::js::attr_is_event_handler;
+use crate::report::AssetOutcome;
 use crate::session::Session;
-use crate::url::{
-    clean_url, create_data_url, is_url_and_has_protocol, resolve_url, Url, EMPTY_IMAGE_DATA_URL,
-};
+use crate::url::{clean_url, is_url_and_has_protocol, resolve_url, Url, EMPTY_IMAGE_DATA_URL};
 
 const FAVICON_VALUES: &[&str] = &["icon", "shortcut icon"];
 const WHITESPACES: &[char] = &[' ', '\t', '\n', '\x0c', '\r']; // ASCII whitespaces
@@ -33,6 +33,11 @@ pub enum LinkType {
     Stylesheet,
 }
 
+pub struct Heading {
+    pub id: String,
+    pub title: String,
+}
+
 pub struct SrcSetItem<'a> {
     pub path: &'a str,
     pub descriptor: &'a str, // Width or pixel density descriptor
@@ -92,7 +97,24 @@ pub fn check_integrity(data: &[u8], integrity: &str) -> bool {
 pub fn compose_csp(options: &MonolithOptions) -> String {
     let mut string_list = vec![];
 
-    if options.isolate {
+    // MHTML documents and web archives refer to their parts by original URLs (which get served from within the archive)
+    if options.isolate
+        && matches!(
+            options.output_format,
+            MonolithOutputFormat::ZIM
+                | MonolithOutputFormat::Directory
+                | MonolithOutputFormat::EPUB
+                | MonolithOutputFormat::ZIP
+        )
+    {
+        // Assets saved as separate files are referred to using relative paths
+        string_list.push("default-src 'self' 'unsafe-eval' 'unsafe-inline' data:;");
+    } else if options.isolate
+        && !matches!(
+            options.output_format,
+            MonolithOutputFormat::MHTML | MonolithOutputFormat::WebArchive
+        )
+    {
         string_list.push("default-src 'unsafe-eval' 'unsafe-inline' data:;");
     }
 
@@ -123,21 +145,12 @@ pub fn compose_csp(options: &MonolithOptions) -> String {
 
 pub fn create_metadata_tag(url: &Url) -> String {
     let datetime: &str = &Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
-    let mut clean_url: Url = clean_url(url.clone());
-
-    // Prevent credentials from getting into metadata
-    if clean_url.scheme() == "http" || clean_url.scheme() == "https" {
-        // Only HTTP(S) URLs can contain credentials
-        clean_url.set_username("").unwrap();
-        clean_url.set_password(None).unwrap();
-    }
 
     format!(
         "<!-- Saved from {} at {} using {} v{} -->",
-        if clean_url.scheme() == "http" || clean_url.scheme() == "https" {
-            clean_url.as_str()
-        } else {
-            "local source"
+        match create_source_url(url) {
+            Some(source_url) => source_url.to_string(),
+            None => "local source".to_string(),
         },
         datetime,
         env!("CARGO_PKG_NAME"),
@@ -145,6 +158,21 @@ pub fn create_metadata_tag(url: &Url) -> String {
     )
 }
 
+pub fn create_source_url(url: &Url) -> Option<Url> {
+    let mut clean_url: Url = clean_url(url.clone());
+
+    // Only HTTP(S) URLs are worth mentioning (and can contain credentials)
+    if clean_url.scheme() == "http" || clean_url.scheme() == "https" {
+        // Prevent credentials from getting into metadata
+        clean_url.set_username("").unwrap();
+        clean_url.set_password(None).unwrap();
+
+        Some(clean_url)
+    } else {
+        None
+    }
+}
+
 pub fn embed_srcset(session: &mut Session, document_url: &Url, srcset: &str) -> String {
     let srcset_items: Vec<SrcSetItem> = parse_srcset(srcset);
 
@@ -152,29 +180,54 @@ pub fn embed_srcset(session: &mut Session, document_url: &Url, srcset: &str) ->
     let mut result: String = "".to_string();
     let mut i: usize = srcset_items.len();
     for srcset_item in srcset_items {
+        let image_full_url: Url = resolve_url(document_url, srcset_item.path);
+
         if session.options.no_images {
             result.push_str(EMPTY_IMAGE_DATA_URL);
+            session.report_asset(
+                document_url,
+                &image_full_url,
+                "srcset",
+                AssetOutcome::Excluded,
+            );
         } else {
-            let image_full_url: Url = resolve_url(document_url, srcset_item.path);
             match session.retrieve_asset(document_url, &image_full_url) {
                 Ok((image_data, image_final_url, image_media_type, image_charset)) => {
-                    let mut image_data_url = create_data_url(
+                    // Append retrieved asset as a data URL
+                    let image_url: String = session.embed_asset(
                         &image_media_type,
                         &image_charset,
                         &image_data,
                         &image_final_url,
+                        image_full_url.fragment(),
+                    );
+                    result.push_str(&image_url);
+                    session.report_asset(
+                        document_url,
+                        &image_full_url,
+                        "srcset",
+                        AssetOutcome::Embedded,
                     );
-                    // Append retrieved asset as a data URL
-                    image_data_url.set_fragment(image_full_url.fragment());
-                    result.push_str(image_data_url.as_ref());
                 }
                 Err(_) => {
                     // Keep remote reference if unable to retrieve the asset
                     if image_full_url.scheme() == "http" || image_full_url.scheme() == "https" {
                         result.push_str(image_full_url.as_ref());
+                        session.report_asset(
+                            document_url,
+                            &image_full_url,
+                            "srcset",
+                            AssetOutcome::Remote,
+                        );
                     } else {
                         // Avoid breaking the structure in case if not an HTTP(S) URL
                         result.push_str(EMPTY_IMAGE_DATA_URL);
+                        session.report_asset(
+                            document_url,
+                            &image_full_url,
+                            "srcset",
+                            AssetOutcome::Dropped,
+                        );
                     }
                 }
             }
@@ -349,6 +402,54 @@ pub fn html_to_dom(data: &Vec<u8>, document_encoding: String) -> RcDom {
         .unwrap()
 }
 
+// Collects headings in order of appearance, giving IDs to those which don't have any (to make them linkable)
+pub fn index_headings(node: &Handle) -> Vec<Heading> {
+    fn get_text(node: &Handle, text: &mut String) {
+        match node.data {
+            NodeData::Text { ref contents } => text.push_str(&contents.borrow()),
+            _ => {
+                for child_node in node.children.borrow().iter() {
+                    get_text(child_node, text);
+                }
+            }
+        }
+    }
+
+    fn find_headings(node: &Handle, headings: &mut Vec<Heading>) {
+        if let Some(node_name) = get_node_name(node) {
+            if matches!(node_name, "h1" | "h2" | "h3" | "h4" | "h5" | "h6") {
+                let mut text: String = "".to_string();
+                get_text(node, &mut text);
+                let title: String = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+
+                if !title.is_empty() {
+                    let id: String = match get_node_attr(node, "id") {
+                        Some(id) if !id.trim().is_empty() => id,
+                        _ => {
+                            let id: String = format!("heading-{}", headings.len() + 1);
+                            set_node_attr(node, "id", Some(id.clone()));
+                            id
+                        }
+                    };
+
+                    headings.push(Heading { id, title });
+                }
+
+                return;
+            }
+        }
+
+        for child_node in node.children.borrow().iter() {
+            find_headings(child_node, headings);
+        }
+    }
+
+    let mut headings: Vec<Heading> = vec![];
+    find_headings(node, &mut headings);
+
+    headings
+}
+
 pub fn is_favicon(attr_value: &str) -> bool {
     FAVICON_VALUES.contains(&attr_value.to_lowercase().as_str())
 }
@@ -641,8 +742,14 @@ pub fn serialize_document(
     }
 
     let serializable: SerializableHandle = dom.document.into();
-    serialize(&mut buf, &serializable, SerializeOpts::default())
-        .expect("Unable to serialize DOM into buffer");
+    if options.output_format == MonolithOutputFormat::EPUB {
+        // E-book readers expect documents to be well-formed XML
+        xml_serialize(&mut buf, &serializable, XmlSerializeOpts::default())
+            .expect("Unable to serialize DOM into buffer");
+    } else {
+        serialize(&mut buf, &serializable, SerializeOpts::default())
+            .expect("Unable to serialize DOM into buffer");
+    }
 
     // Unwrap NOSCRIPT elements
     if options.unwrap_noscript {
@@ -662,6 +769,27 @@ pub fn serialize_document(
     buf
 }
 
+// Reports asset which doesn't get retrieved due to options (e.g. --no-images)
+fn exclude_asset(
+    session: &mut Session,
+    document_url: &Url,
+    node: &Handle,
+    attr_name: &str,
+    attr_value: &str,
+) {
+    if attr_value.trim().is_empty() {
+        return;
+    }
+
+    let context: String = format!("{}[{}]", get_node_name(node).unwrap_or_default(), attr_name);
+    session.report_asset(
+        document_url,
+        &resolve_url(document_url, attr_value),
+        &context,
+        AssetOutcome::Excluded,
+    );
+}
+
 pub fn retrieve_and_embed_asset(
     session: &mut Session,
     document_url: &Url,
@@ -670,11 +798,11 @@ pub fn retrieve_and_embed_asset(
     attr_value: &str,
 ) {
     let resolved_url: Url = resolve_url(document_url, attr_value);
+    let node_name: &str = get_node_name(node).unwrap();
+    let context: String = format!("{}[{}]", node_name, attr_name);
 
     match session.retrieve_asset(&document_url.clone(), &resolved_url) {
         Ok((data, final_url, media_type, charset)) => {
-            let node_name: &str = get_node_name(node).unwrap();
-
             // Check integrity if it's a LINK or SCRIPT element
             let mut ok_to_include: bool = true;
             if node_name == "link" || node_name == "script" {
@@ -690,6 +818,14 @@ pub fn retrieve_and_embed_asset(
             }
 
             if ok_to_include {
+                // Reported ahead of assets found within it
+                session.report_asset(
+                    document_url,
+                    &resolved_url,
+                    &context,
+                    AssetOutcome::Embedded,
+                );
+
                 if node_name == "link"
                     && parse_link_type(&get_node_attr(node, "rel").unwrap_or(String::from("")))
                         .contains(&LinkType::Stylesheet)
@@ -703,26 +839,51 @@ pub fn retrieve_and_embed_asset(
                     }
 
                     // Stylesheet LINK elements require special treatment
-                    let css: String = embed_css(session, &final_url, &stylesheet);
+                    let css: String =
+                        session.within_asset(|session| embed_css(session, &final_url, &stylesheet));
 
                     // Create and embed data URL
-                    let css_data_url =
-                        create_data_url(&media_type, &charset, css.as_bytes(), &final_url);
-                    set_node_attr(node, attr_name, Some(css_data_url.to_string()));
+                    let css_data_url: String = session.embed_asset(
+                        &media_type,
+                        &charset,
+                        css.as_bytes(),
+                        &final_url,
+                        None,
+                    );
+                    set_node_attr(node, attr_name, Some(css_data_url));
                 } else if node_name == "frame" || node_name == "iframe" {
                     // (I)FRAMEs are also quite different from conventional resources
-                    let frame_dom = html_to_dom(&data, charset.clone());
-                    walk(session, &final_url, &frame_dom.document);
+                    let mut frame_dom = html_to_dom(&data, charset.clone());
+                    session.within_asset(|session| walk(session, &final_url, &frame_dom.document));
 
                     let mut frame_data: Vec<u8> = Vec::new();
-                    let serializable: SerializableHandle = frame_dom.document.into();
-                    serialize(&mut frame_data, &serializable, SerializeOpts::default()).unwrap();
+                    let frame_media_type: String;
+                    let frame_charset: String;
+                    if session.options.output_format == MonolithOutputFormat::EPUB {
+                        // E-books can only contain XHTML documents, just like the main one
+                        frame_dom = set_charset(frame_dom, "UTF-8".to_string());
+                        let serializable: SerializableHandle = frame_dom.document.into();
+                        xml_serialize(&mut frame_data, &serializable, XmlSerializeOpts::default())
+                            .unwrap();
+                        frame_media_type = "application/xhtml+xml".to_string();
+                        frame_charset = "UTF-8".to_string();
+                    } else {
+                        let serializable: SerializableHandle = frame_dom.document.into();
+                        serialize(&mut frame_data, &serializable, SerializeOpts::default())
+                            .unwrap();
+                        frame_media_type = media_type;
+                        frame_charset = charset;
+                    }
 
                     // Create and embed data URL
-                    let mut frame_data_url =
-                        create_data_url(&media_type, &charset, &frame_data, &final_url);
-                    frame_data_url.set_fragment(resolved_url.fragment());
-                    set_node_attr(node, attr_name, Some(frame_data_url.to_string()));
+                    let frame_data_url: String = session.embed_asset(
+                        &frame_media_type,
+                        &frame_charset,
+                        &frame_data,
+                        &final_url,
+                        resolved_url.fragment(),
+                    );
+                    set_node_attr(node, attr_name, Some(frame_data_url));
                 } else {
                     // Every other type of element gets processed here
 
@@ -758,28 +919,46 @@ pub fn retrieve_and_embed_asset(
                             }
                         } else {
                             // Create and embed data URL
-                            let mut data_url =
-                                create_data_url(&script_media_type, &charset, &data, &final_url);
-                            data_url.set_fragment(resolved_url.fragment());
-                            set_node_attr(node, attr_name, Some(data_url.to_string()));
+                            let data_url: String = session.embed_asset(
+                                &script_media_type,
+                                &charset,
+                                &data,
+                                &final_url,
+                                resolved_url.fragment(),
+                            );
+                            set_node_attr(node, attr_name, Some(data_url));
                         }
                     } else {
                         // Create and embed data URL
-                        let mut data_url =
-                            create_data_url(&media_type, &charset, &data, &final_url);
-                        data_url.set_fragment(resolved_url.fragment());
-                        set_node_attr(node, attr_name, Some(data_url.to_string()));
+                        let data_url: String = session.embed_asset(
+                            &media_type,
+                            &charset,
+                            &data,
+                            &final_url,
+                            resolved_url.fragment(),
+                        );
+                        set_node_attr(node, attr_name, Some(data_url));
                     }
                 }
+            } else {
+                session.add_asset_error(MonolithError::IntegrityMismatch { url: final_url });
+                session.report_asset(
+                    document_url,
+                    &resolved_url,
+                    &context,
+                    AssetOutcome::IntegrityMismatch,
+                );
             }
         }
         Err(_) => {
             if resolved_url.scheme() == "http" || resolved_url.scheme() == "https" {
                 // Keep remote references if unable to retrieve the asset
                 set_node_attr(node, attr_name, Some(resolved_url.to_string()));
+                session.report_asset(document_url, &resolved_url, &context, AssetOutcome::Remote);
             } else {
                 // Remove local references if they can't be successfully embedded as data URLs
                 set_node_attr(node, attr_name, None);
+                session.report_asset(document_url, &resolved_url, &context, AssetOutcome::Dropped);
             }
         }
     }
@@ -828,6 +1007,13 @@ pub fn walk(session: &mut Session, document_url: &Url, node: &Handle) {
                                     &link_attr_href_value,
                                 );
                             } else {
+                                exclude_asset(
+                                    session,
+                                    document_url,
+                                    node,
+                                    "href",
+                                    &link_attr_href_value,
+                                );
                                 set_node_attr(node, "href", None);
                             }
                         }
@@ -835,6 +1021,13 @@ pub fn walk(session: &mut Session, document_url: &Url, node: &Handle) {
                         // Resolve LINK's href attribute
                         if let Some(link_attr_href_value) = get_node_attr(node, "href") {
                             if session.options.no_css {
+                                exclude_asset(
+                                    session,
+                                    document_url,
+                                    node,
+                                    "href",
+                                    &link_attr_href_value,
+                                );
                                 set_node_attr(node, "href", None);
                                 // Wipe integrity attribute
                                 set_node_attr(node, "integrity", None);
@@ -863,7 +1056,16 @@ pub fn walk(session: &mut Session, document_url: &Url, node: &Handle) {
                     }
                 }
                 "base" => {
-                    if document_url.scheme() == "http" || document_url.scheme() == "https" {
+                    if matches!(
+                        session.options.output_format,
+                        MonolithOutputFormat::ZIM
+                            | MonolithOutputFormat::Directory
+                            | MonolithOutputFormat::EPUB
+                            | MonolithOutputFormat::ZIP
+                    ) {
+                        // Assets saved as separate files have to be resolved relative to the document
+                        set_node_attr(node, "href", None);
+                    } else if document_url.scheme() == "http" || document_url.scheme() == "https" {
                         // Ensure the BASE node doesn't have a relative URL
                         if let Some(base_attr_href_value) = get_node_attr(node, "href") {
                             let href_full_url: Url =
@@ -878,7 +1080,15 @@ pub fn walk(session: &mut Session, document_url: &Url, node: &Handle) {
                         // Remove background BODY node attribute by default
                         set_node_attr(node, "background", None);
 
-                        if !session.options.no_images && !body_attr_background_value.is_empty() {
+                        if session.options.no_images {
+                            exclude_asset(
+                                session,
+                                document_url,
+                                node,
+                                "background",
+                                &body_attr_background_value,
+                            );
+                        } else if !body_attr_background_value.is_empty() {
                             retrieve_and_embed_asset(
                                 session,
                                 document_url,
@@ -896,10 +1106,18 @@ pub fn walk(session: &mut Session, document_url: &Url, node: &Handle) {
 
                     if session.options.no_images {
                         // Put empty images into src and data-src attributes
-                        if img_attr_src_value.is_some() {
+                        if let Some(img_attr_src_value) = &img_attr_src_value {
+                            exclude_asset(session, document_url, node, "src", img_attr_src_value);
                             set_node_attr(node, "src", Some(EMPTY_IMAGE_DATA_URL.to_string()));
                         }
-                        if img_attr_data_src_value.is_some() {
+                        if let Some(img_attr_data_src_value) = &img_attr_data_src_value {
+                            exclude_asset(
+                                session,
+                                document_url,
+                                node,
+                                "data-src",
+                                img_attr_data_src_value,
+                            );
                             set_node_attr(node, "data-src", Some(EMPTY_IMAGE_DATA_URL.to_string()));
                         }
                     } else if img_attr_src_value.clone().unwrap_or_default().is_empty()
@@ -938,6 +1156,13 @@ pub fn walk(session: &mut Session, document_url: &Url, node: &Handle) {
                         if input_attr_type_value.eq_ignore_ascii_case("image") {
                             if let Some(input_attr_src_value) = get_node_attr(node, "src") {
                                 if session.options.no_images || input_attr_src_value.is_empty() {
+                                    exclude_asset(
+                                        session,
+                                        document_url,
+                                        node,
+                                        "src",
+                                        &input_attr_src_value,
+                                    );
                                     let value = if input_attr_src_value.is_empty() {
                                         ""
                                     } else {
@@ -969,6 +1194,13 @@ pub fn walk(session: &mut Session, document_url: &Url, node: &Handle) {
                     for attr_name in attr_names.into_iter() {
                         if let Some(image_attr_href_value) = get_node_attr(node, attr_name) {
                             if session.options.no_images {
+                                exclude_asset(
+                                    session,
+                                    document_url,
+                                    node,
+                                    attr_name,
+                                    &image_attr_href_value,
+                                );
                                 set_node_attr(node, attr_name, None);
                             } else {
                                 retrieve_and_embed_asset(
@@ -988,13 +1220,28 @@ pub fn walk(session: &mut Session, document_url: &Url, node: &Handle) {
                     for attr_name in attr_names.into_iter() {
                         if let Some(use_attr_href_value) = get_node_attr(node, attr_name) {
                             if session.options.no_images {
+                                exclude_asset(
+                                    session,
+                                    document_url,
+                                    node,
+                                    attr_name,
+                                    &use_attr_href_value,
+                                );
                                 set_node_attr(node, attr_name, None);
                             } else {
                                 let image_asset_url: Url =
                                     resolve_url(document_url, &use_attr_href_value);
+                                let context: String = format!("use[{}]", attr_name);
 
                                 match session.retrieve_asset(document_url, &image_asset_url) {
                                     Ok((data, final_url, media_type, charset)) => {
+                                        session.report_asset(
+                                            document_url,
+                                            &image_asset_url,
+                                            &context,
+                                            AssetOutcome::Embedded,
+                                        );
+
                                         if media_type == "image/svg+xml" {
                                             // Parse SVG
                                             let svg_dom: RcDom = parse_document(
@@ -1072,17 +1319,14 @@ pub fn walk(session: &mut Session, document_url: &Url, node: &Handle) {
                                             }
                                         } else {
                                             // It's likely a raster image; embed it as data URL
-                                            let image_asset_data: Url = create_data_url(
+                                            let image_asset_data: String = session.embed_asset(
                                                 &media_type,
                                                 &charset,
                                                 &data,
                                                 &final_url,
+                                                None,
                                             );
-                                            set_node_attr(
-                                                node,
-                                                attr_name,
-                                                Some(image_asset_data.to_string()),
-                                            );
+                                            set_node_attr(node, attr_name, Some(image_asset_data));
                                         }
                                     }
                                     Err(_) => {
@@ -1091,6 +1335,12 @@ pub fn walk(session: &mut Session, document_url: &Url, node: &Handle) {
                                             attr_name,
                                             Some(image_asset_url.to_string()),
                                         );
+                                        session.report_asset(
+                                            document_url,
+                                            &image_asset_url,
+                                            &context,
+                                            AssetOutcome::Remote,
+                                        );
                                     }
                                 }
                             }
@@ -1104,6 +1354,13 @@ pub fn walk(session: &mut Session, document_url: &Url, node: &Handle) {
                     if let Some(source_attr_src_value) = get_node_attr(node, "src") {
                         if parent_node_name == "audio" {
                             if session.options.no_audio {
+                                exclude_asset(
+                                    session,
+                                    document_url,
+                                    node,
+                                    "src",
+                                    &source_attr_src_value,
+                                );
                                 set_node_attr(node, "src", None);
                             } else {
                                 retrieve_and_embed_asset(
@@ -1116,6 +1373,13 @@ pub fn walk(session: &mut Session, document_url: &Url, node: &Handle) {
                             }
                         } else if parent_node_name == "video" {
                             if session.options.no_video {
+                                exclude_asset(
+                                    session,
+                                    document_url,
+                                    node,
+                                    "src",
+                                    &source_attr_src_value,
+                                );
                                 set_node_attr(node, "src", None);
                             } else {
                                 retrieve_and_embed_asset(
@@ -1132,6 +1396,14 @@ pub fn walk(session: &mut Session, document_url: &Url, node: &Handle) {
                     if let Some(source_attr_srcset_value) = get_node_attr(node, "srcset") {
                         if parent_node_name == "picture" && !source_attr_srcset_value.is_empty() {
                             if session.options.no_images {
+                                for srcset_item in parse_srcset(&source_attr_srcset_value) {
+                                    session.report_asset(
+                                        document_url,
+                                        &resolve_url(document_url, srcset_item.path),
+                                        "srcset",
+                                        AssetOutcome::Excluded,
+                                    );
+                                }
                                 set_node_attr(
                                     node,
                                     "srcset",
@@ -1156,13 +1428,15 @@ pub fn walk(session: &mut Session, document_url: &Url, node: &Handle) {
                                 // Replace with empty JS call to preserve original behavior
                                 set_node_attr(node, "href", Some("javascript:;".to_string()));
                             }
-                        } else {
-                            // Don't touch mailto: links or hrefs which begin with a hash sign
-                            if !anchor_attr_href_value.clone().starts_with('#')
-                                && !is_url_and_has_protocol(&anchor_attr_href_value.clone())
-                            {
-                                let href_full_url: Url =
-                                    resolve_url(document_url, &anchor_attr_href_value);
+                        } else if !anchor_attr_href_value.clone().starts_with('#') {
+                            let href_full_url: Url =
+                                resolve_url(document_url, &anchor_attr_href_value);
+
+                            // Links to pages which get saved along with this one lead to their local copies
+                            if let Some(page_file_name) = session.follow_link(&href_full_url) {
+                                set_node_attr(node, "href", Some(page_file_name));
+                            } else if !is_url_and_has_protocol(&anchor_attr_href_value.clone()) {
+                                // Don't touch mailto: links or hrefs which begin with a hash sign
                                 set_node_attr(node, "href", Some(href_full_url.to_string()));
                             }
                         }
@@ -1177,6 +1451,7 @@ pub fn walk(session: &mut Session, document_url: &Url, node: &Handle) {
                         node.children.borrow_mut().clear();
                         // Remove src attribute
                         if !script_attr_src.is_empty() {
+                            exclude_asset(session, document_url, node, "src", script_attr_src);
                             set_node_attr(node, "src", None);
                             // Wipe integrity attribute
                             set_node_attr(node, "integrity", None);
@@ -1218,6 +1493,13 @@ pub fn walk(session: &mut Session, document_url: &Url, node: &Handle) {
                 "frame" | "iframe" => {
                     if let Some(frame_attr_src_value) = get_node_attr(node, "src") {
                         if session.options.no_frames {
+                            exclude_asset(
+                                session,
+                                document_url,
+                                node,
+                                "src",
+                                &frame_attr_src_value,
+                            );
                             // Empty the src attribute
                             set_node_attr(node, "src", Some("".to_string()));
                         } else {
@@ -1238,6 +1520,13 @@ pub fn walk(session: &mut Session, document_url: &Url, node: &Handle) {
                     // Embed audio source
                     if let Some(audio_attr_src_value) = get_node_attr(node, "src") {
                         if session.options.no_audio {
+                            exclude_asset(
+                                session,
+                                document_url,
+                                node,
+                                "src",
+                                &audio_attr_src_value,
+                            );
                             set_node_attr(node, "src", None);
                         } else {
                             retrieve_and_embed_asset(
@@ -1254,6 +1543,13 @@ pub fn walk(session: &mut Session, document_url: &Url, node: &Handle) {
                     // Embed video source
                     if let Some(video_attr_src_value) = get_node_attr(node, "src") {
                         if session.options.no_video {
+                            exclude_asset(
+                                session,
+                                document_url,
+                                node,
+                                "src",
+                                &video_attr_src_value,
+                            );
                             set_node_attr(node, "src", None);
                         } else {
                             retrieve_and_embed_asset(
@@ -1271,6 +1567,13 @@ pub fn walk(session: &mut Session, document_url: &Url, node: &Handle) {
                         // Skip posters with empty source
                         if !video_attr_poster_value.is_empty() {
                             if session.options.no_images {
+                                exclude_asset(
+                                    session,
+                                    document_url,
+                                    node,
+                                    "poster",
+                                    &video_attr_poster_value,
+                                );
                                 set_node_attr(
                                     node,
                                     "poster",
@@ -1351,6 +1654,13 @@ pub fn walk(session: &mut Session, document_url: &Url, node: &Handle) {
             for child_node in node.children.borrow().iter() {
                 walk(session, document_url, child_node);
             }
+
+            // E-books which contain SCRIPT elements would have to be declared as scripted
+            if session.options.output_format == MonolithOutputFormat::EPUB {
+                node.children
+                    .borrow_mut()
+                    .retain(|child_node| get_node_name(child_node) != Some("script"));
+            }
         }
         _ => {
             // Note: in case of options.no_js being set to true, there's no need to worry about
@@ -1,8 +1,23 @@
+pub mod archive;
+pub mod auth;
+pub mod batch;
 pub mod cache;
 pub mod cookies;
 pub mod core;
+pub mod crawl;
 pub mod css;
+pub mod epub;
+pub mod events;
+pub mod har;
+pub mod headers;
 pub mod html;
 pub mod js;
+pub mod mhtml;
+pub mod report;
 pub mod session;
+pub mod source;
 pub mod url;
+pub mod warc;
+pub mod webarchive;
+pub mod zim;
+pub mod zip;
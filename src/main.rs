@@ -1,17 +1,32 @@
 use std::fs;
 use std::io::{self, Error as IoError, Read, Write};
+use std::path::{Path, PathBuf};
 use std::process;
+use std::time::Duration;
 
 use clap::Parser;
+use reqwest::StatusCode;
 use tempfile::{Builder, NamedTempFile};
 
+use monolith::archive::Archive;
+use monolith::auth::{parse_netrc, AuthRule, Credentials};
+use monolith::batch::{batch, parse_batch, BatchTarget};
 use monolith::cache::Cache;
 use monolith::cookies::{parse_cookie_file_contents, Cookie};
 use monolith::core::{
     create_monolithic_document, create_monolithic_document_from_data, format_output_path,
-    print_error_message, MonolithOptions, MonolithOutputFormat,
+    print_error_message, print_info_message, MonolithError, MonolithOfflinePolicy, MonolithOptions,
+    MonolithOutputFormat,
 };
-use monolith::session::Session;
+use monolith::crawl::{crawl, create_page_file_name};
+use monolith::events::SessionEvent;
+use monolith::har::create_har;
+use monolith::headers::{parse_header, parse_header_rules_file_contents, HeaderRule};
+use monolith::report::create_report;
+use monolith::session::{is_valid_rate, parse_rate_limit_rule, Session};
+use monolith::source::DirectorySource;
+use monolith::url::{clean_url, Url};
+use monolith::warc::parse_warc;
 
 const ASCII: &str = " \
  _____    _____________   __________     ___________________    ___
@@ -24,6 +39,10 @@ const ASCII: &str = " \
 ";
 const CACHE_ASSET_FILE_SIZE_THRESHOLD: usize = 1024 * 10; // Minimum file size for on-disk caching (in bytes)
 const DEFAULT_NETWORK_TIMEOUT: u64 = 120; // Maximum time to retrieve each remote asset (in seconds)
+const DEFAULT_PARALLEL_REQUESTS: usize = 8; // Maximum number of assets retrieved at once
+const DEFAULT_PARALLEL_REQUESTS_PER_HOST: usize = 4; // Maximum number of assets retrieved at once from one host
+const DEFAULT_RETRY_DELAY: u64 = 1000; // Initial delay between retries (in milliseconds)
+const SECONDS_IN_DAY: u64 = 60 * 60 * 24;
 const DEFAULT_USER_AGENT: &str =
     "Mozilla/5.0 (X11; Ubuntu; Linux x86_64; rv:135.0) Gecko/20100101 Firefox/135.0";
 
@@ -48,6 +67,18 @@ struct Cli {
     #[arg(short = 'c', long)]
     no_css: bool,
 
+    /// Keep retrieved assets in directory, to be reused by future runs
+    #[arg(long, value_name = "cache")]
+    cache_dir: Option<String>,
+
+    /// Remove assets retrieved more than this many days ago from cache directory
+    #[arg(long, value_name = "30", requires = "cache_dir")]
+    cache_max_age: Option<u64>,
+
+    /// Remove oldest assets from cache directory until it fits into this many megabytes
+    #[arg(long, value_name = "500", requires = "cache_dir")]
+    cache_max_size: Option<usize>,
+
     /// Specify cookie file
     #[arg(short = 'C', long, value_name = "cookies.txt")]
     cookie_file: Option<String>,
@@ -120,8 +151,144 @@ struct Cli {
     #[arg(short = 'v', long)]
     no_video: bool,
 
+    /// Use WARC as output format
+    #[arg(long, conflicts_with = "mhtml")]
+    warc: bool,
+
+    /// Use ZIM as output format
+    #[arg(long, conflicts_with_all = ["mhtml", "warc"])]
+    zim: bool,
+
+    /// Use EPUB as output format
+    #[arg(long, conflicts_with_all = ["mhtml", "warc", "zim", "assets_dir"])]
+    epub: bool,
+
+    /// Use ZIP as output format
+    #[arg(long, conflicts_with_all = ["mhtml", "warc", "zim", "assets_dir", "epub"])]
+    zip: bool,
+
+    /// Use Safari's web archive as output format
+    #[arg(long, conflicts_with_all = ["mhtml", "warc", "zim", "assets_dir", "epub", "zip"])]
+    webarchive: bool,
+
+    /// Save assets as separate files into directory next to the document
+    #[arg(long, value_name = "page_files", conflicts_with_all = ["mhtml", "warc", "zim"])]
+    assets_dir: Option<String>,
+
+    /// Retrieve assets from WARC file instead of the network
+    #[arg(long, value_name = "archive.warc")]
+    from_warc: Option<String>,
+
+    /// Retrieve assets from local mirror directory (host/path/to/file) before trying the network
+    #[arg(long, value_name = "mirror")]
+    mirror: Option<String>,
+
+    /// Record all network requests into HAR file
+    #[arg(long, value_name = "requests.har")]
+    har: Option<String>,
+
+    /// Write report of every asset found within documents and its outcome into JSON file
+    #[arg(long, value_name = "report.json")]
+    report: Option<String>,
+
+    /// Follow links to other pages within the same domain, saving them into output directory
+    #[arg(long, value_name = "1")]
+    depth: Option<usize>,
+
+    /// Save every target listed in file (plain list, CSV or JSON), use - for STDIN
+    #[arg(long, value_name = "targets.txt", conflicts_with_all = ["depth", "target"])]
+    batch: Option<String>,
+
+    /// Retrieve up to this many assets at once, 1 disables concurrency
+    #[arg(long, value_name = "8")]
+    parallel: Option<usize>,
+
+    /// Retrieve up to this many assets at once from the same host
+    #[arg(long, value_name = "4")]
+    parallel_per_host: Option<usize>,
+
+    /// Retry transient network failures up to this many times for each asset
+    #[arg(long, value_name = "0")]
+    retries: Option<usize>,
+
+    /// Wait this long before the first retry, doubling the delay after each one (in milliseconds)
+    #[arg(long, value_name = "1000")]
+    retry_delay: Option<u64>,
+
+    /// Make at most this many requests per second to the same host
+    #[arg(long, value_name = "2")]
+    rate_limit: Option<f64>,
+
+    /// Wait at least this long between requests to the same host (in milliseconds)
+    #[arg(long, value_name = "500")]
+    request_delay: Option<u64>,
+
+    /// Limit rate of requests to hosts of given domain separately
+    #[arg(
+        long = "rate-limit-rule",
+        value_name = "example.com rate=1 delay=500 connections=2"
+    )]
+    rate_limit_rules: Vec<String>,
+
+    /// Never use the network, only cached or local data
+    #[arg(long)]
+    offline: bool,
+
+    /// What to do with assets missing while offline
+    #[arg(long, value_name = "keep", value_parser = ["keep", "placeholder", "fail"], requires = "offline")]
+    offline_policy: Option<String>,
+
+    /// Abort capture if it takes longer than this in total (in seconds)
+    #[arg(long, value_name = "300")]
+    time_limit: Option<u64>,
+
+    /// Save what's been captured so far if capture gets aborted, instead of failing
+    #[arg(long, requires = "time_limit")]
+    keep_partial: bool,
+
+    /// Send all requests through HTTP(S) or SOCKS proxy
+    #[arg(long, value_name = "socks5h://localhost:9050")]
+    proxy: Option<String>,
+
+    /// Authenticate with proxy using given credentials
+    #[arg(long, value_name = "user:password")]
+    proxy_user: Option<String>,
+
+    /// Send requests for given domain through its own proxy
+    #[arg(long = "proxy-rule", value_name = "example.com=http://localhost:3128")]
+    proxy_rules: Vec<String>,
+
+    /// Access given domains directly, bypassing the proxy
+    #[arg(long, value_name = "example.com", value_delimiter = ',')]
+    no_proxy: Vec<String>,
+
+    /// Authenticate with target's origin using Basic or Digest authentication
+    #[arg(long, value_name = "user:password")]
+    user: Option<String>,
+
+    /// Authenticate with target's origin using bearer token
+    #[arg(long, value_name = "token")]
+    bearer: Option<String>,
+
+    /// Authenticate with hosts using credentials listed in netrc file
+    #[arg(long, value_name = ".netrc")]
+    netrc: Option<String>,
+
+    /// Send custom header along with requests made to target's host
+    #[arg(short = 'H', long = "header", value_name = "Name: value")]
+    headers: Vec<String>,
+
+    /// Send custom headers to domains listed in file
+    #[arg(long, value_name = "headers.txt")]
+    header_rules: Option<String>,
+
+    /// Pick document to save out of HAR or MHTML target
+    #[arg(long = "document", value_name = "http://localhost/")]
+    document_url: Option<String>,
+
     /// URL or file path, use - for STDIN
-    target: String,
+    #[arg(required_unless_present = "batch")]
+    target: Option<String>,
 }
 
 pub enum Output {
@@ -157,6 +324,163 @@ impl Output {
     }
 }
 
+pub fn get_document_directory(
+    destination: &str,
+    document_title: &str,
+    output_format: MonolithOutputFormat,
+) -> PathBuf {
+    // Documents written into STDOUT are considered to be within current directory
+    if destination.is_empty() || destination.eq("-") {
+        PathBuf::new()
+    } else {
+        let final_destination = format_output_path(destination, document_title, output_format);
+        Path::new(&final_destination)
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default()
+    }
+}
+
+pub fn write_assets(
+    destination: &str,
+    document_title: &str,
+    session: &Session,
+) -> Result<(), IoError> {
+    // Assets directory is located next to the document (or within current directory)
+    let mut path: PathBuf = get_document_directory(
+        destination,
+        document_title,
+        session.options.output_format.clone(),
+    );
+    path.push(session.options.assets_directory.as_deref().unwrap_or(""));
+
+    fs::create_dir_all(&path)?;
+    for asset in session.assets() {
+        fs::write(path.join(asset.file_name()), &asset.data)?;
+    }
+
+    Ok(())
+}
+
+pub fn create_batch_output_path(target: &str, output_format: MonolithOutputFormat) -> String {
+    // Documents are named after their URLs, local files after their full paths
+    let url: Option<Url> = match Url::parse(target) {
+        Ok(url) => Some(url),
+        Err(_) => match fs::canonicalize(target) {
+            Ok(path) => Url::from_file_path(path).ok(),
+            Err(_) => Url::parse(&format!("http://{}", target)).ok(),
+        },
+    };
+
+    match url {
+        Some(url) => create_page_file_name(&url, output_format),
+        None => format_output_path("%title%.%extension%", "", output_format),
+    }
+}
+
+pub fn create_target_auth_rules(target: &str, credentials: &[Credentials]) -> Vec<AuthRule> {
+    // Credentials given without origin are scoped to the origin of the target
+    match Url::parse(target) {
+        Ok(url) if url.scheme() == "http" || url.scheme() == "https" => credentials
+            .iter()
+            .map(|credentials| AuthRule {
+                origin: url.origin().ascii_serialization(),
+                credentials: credentials.clone(),
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+pub fn create_target_header_rules(target: &str, headers: &[(String, String)]) -> Vec<HeaderRule> {
+    // Headers given without domain are scoped to the exact host of the target
+    match Url::parse(target)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+    {
+        Some(host) => headers
+            .iter()
+            .map(|(name, value)| HeaderRule {
+                domain: host.clone(),
+                name: name.clone(),
+                value: value.clone(),
+            })
+            .collect(),
+        None => vec![],
+    }
+}
+
+pub fn print_session_event(event: &SessionEvent) {
+    match event {
+        SessionEvent::AssetFinished { url, final_url, .. } => {
+            if url == final_url {
+                print_info_message(clean_url(url.clone()).as_str());
+            } else {
+                print_info_message(&format!("{} -> {}", clean_url(url.clone()), final_url));
+            }
+        }
+        SessionEvent::AssetFromCache {
+            url, revalidated, ..
+        } => {
+            print_info_message(&format!(
+                "{} ({})",
+                clean_url(url.clone()),
+                if *revalidated {
+                    "not modified"
+                } else {
+                    "from cache"
+                }
+            ));
+        }
+        SessionEvent::AssetFromSource {
+            url,
+            final_url,
+            source,
+            ..
+        } => {
+            let url: Url = clean_url(url.clone());
+            if url == *final_url {
+                print_info_message(&format!("{} (from {})", url, source));
+            } else {
+                print_info_message(&format!("{} -> {} (from {})", url, final_url, source));
+            }
+        }
+        SessionEvent::AssetRetried { url, retries } => {
+            print_info_message(&format!(
+                "{} (retries: {})",
+                clean_url(url.clone()),
+                retries
+            ));
+        }
+        SessionEvent::AssetIncomplete { cause, .. } => {
+            print_error_message(cause);
+        }
+        SessionEvent::AssetFailed { url, error } => {
+            let reason: String = match error {
+                // Assets which aren't meant to be retrieved aren't worth mentioning
+                MonolithError::BlockedDomain { .. } => return,
+                MonolithError::FileNotFound { .. } => {
+                    print_error_message(&format!("{} (file not found)", url));
+                    return;
+                }
+                MonolithError::FileUnreadable { cause, .. }
+                | MonolithError::Network { cause, .. } => cause.clone(),
+                MonolithError::HttpStatus { status, .. } => match StatusCode::from_u16(*status) {
+                    Ok(status) => status.to_string(),
+                    Err(_) => status.to_string(),
+                },
+                MonolithError::IsDirectory { .. } => "is a directory".to_string(),
+                MonolithError::NotInArchive { .. } => "not found in archive".to_string(),
+                MonolithError::Offline { .. } => "not available offline".to_string(),
+                MonolithError::SecurityError { .. } => "security error".to_string(),
+                _ => error.to_string(),
+            };
+            print_error_message(&format!("{} ({})", clean_url(url.clone()), reason));
+        }
+        _ => {}
+    }
+}
+
 pub fn read_stdin() -> Vec<u8> {
     let mut buffer: Vec<u8> = vec![];
 
@@ -169,21 +493,52 @@ pub fn read_stdin() -> Vec<u8> {
 fn main() {
     let cli = Cli::parse();
     let cookie_file_path;
+    let mut credentials: Vec<Credentials> = vec![];
+    let mut headers: Vec<(String, String)> = vec![];
     let mut exit_code = 0;
     let mut options: MonolithOptions = MonolithOptions::default();
+    let batch_file_path;
+    let cache_directory_path;
     let destination;
+    let har_file_path;
+    let mirror_directory_path;
+    let report_file_path;
+    let warc_file_path;
 
     // Process the command
     {
         options.base_url = cli.base_url;
         options.blacklist_domains = cli.blacklist_domains;
+        options.document_url = cli.document_url;
         options.encoding = cli.encoding;
         if !cli.domains.is_empty() {
             options.domains = Some(cli.domains);
         }
+        if let Some(user) = &cli.user {
+            let (user, password) = user.split_once(':').unwrap_or((user, ""));
+            credentials.push(Credentials::Password(
+                user.to_string(),
+                password.to_string(),
+            ));
+        }
+        if let Some(token) = &cli.bearer {
+            credentials.push(Credentials::Token(token.to_string()));
+        }
+        for header in &cli.headers {
+            match parse_header(header) {
+                Some(header) => headers.push(header),
+                None => {
+                    if !cli.quiet {
+                        print_error_message(&format!("invalid header \"{}\"", header));
+                    }
+                    process::exit(1);
+                }
+            }
+        }
         options.ignore_errors = cli.ignore_errors;
         options.insecure = cli.insecure;
         options.isolate = cli.isolate;
+        options.keep_partial = cli.keep_partial;
         options.no_audio = cli.no_audio;
         options.no_css = cli.no_css;
         options.no_fonts = cli.no_fonts;
@@ -195,9 +550,83 @@ fn main() {
             // The MHTML format doesn't allow JavaScript
             options.no_js = true;
         }
+        if cli.warc {
+            options.output_format = MonolithOutputFormat::WARC;
+        }
+        if cli.zim {
+            options.output_format = MonolithOutputFormat::ZIM;
+        }
+        if let Some(assets_dir) = cli.assets_dir {
+            options.output_format = MonolithOutputFormat::Directory;
+            options.assets_directory = Some(assets_dir);
+        }
+        if cli.epub {
+            options.output_format = MonolithOutputFormat::EPUB;
+        }
+        if cli.zip {
+            options.output_format = MonolithOutputFormat::ZIP;
+        }
+        if cli.webarchive {
+            options.output_format = MonolithOutputFormat::WebArchive;
+        }
         options.no_metadata = cli.no_metadata;
+        options.parallel_requests = cli.parallel.unwrap_or(DEFAULT_PARALLEL_REQUESTS);
+        options.parallel_requests_per_host = cli
+            .parallel_per_host
+            .unwrap_or(DEFAULT_PARALLEL_REQUESTS_PER_HOST);
         options.no_video = cli.no_video;
+        options.no_proxy = cli.no_proxy;
+        options.proxy_user = cli.proxy_user;
+        for proxy_rule in &cli.proxy_rules {
+            match proxy_rule.split_once('=') {
+                Some((domain, proxy)) if !domain.is_empty() => options
+                    .proxy_rules
+                    .push((domain.to_string(), proxy.to_string())),
+                _ => {
+                    if !cli.quiet {
+                        print_error_message(&format!("invalid proxy rule \"{}\"", proxy_rule));
+                    }
+                    process::exit(1);
+                }
+            }
+        }
+        options.proxy = cli.proxy;
+        options.offline = cli.offline;
+        options.offline_policy = match cli.offline_policy.as_deref() {
+            Some("placeholder") => MonolithOfflinePolicy::Placeholder,
+            Some("fail") => MonolithOfflinePolicy::Fail,
+            _ => MonolithOfflinePolicy::Keep,
+        };
+        for rate_limit_rule in &cli.rate_limit_rules {
+            match parse_rate_limit_rule(rate_limit_rule) {
+                Some(rate_limit_rule) => options.rate_limit_rules.push(rate_limit_rule),
+                None => {
+                    if !cli.quiet {
+                        print_error_message(&format!(
+                            "invalid rate limit rule \"{}\"",
+                            rate_limit_rule
+                        ));
+                    }
+                    process::exit(1);
+                }
+            }
+        }
+        options.record_exchanges = cli.har.is_some();
+        options.request_delay = cli.request_delay.unwrap_or(0);
+        options.requests_per_second = cli.rate_limit.unwrap_or(0.0);
+        if !is_valid_rate(&options.requests_per_second) {
+            if !cli.quiet {
+                print_error_message(&format!(
+                    "invalid rate limit \"{}\"",
+                    options.requests_per_second
+                ));
+            }
+            process::exit(1);
+        }
+        options.retries = cli.retries.unwrap_or(0);
+        options.retry_delay = cli.retry_delay.unwrap_or(DEFAULT_RETRY_DELAY);
         options.silent = cli.quiet;
+        options.time_limit = cli.time_limit.unwrap_or(0);
         options.timeout = cli.timeout.unwrap_or(DEFAULT_NETWORK_TIMEOUT);
         options.unwrap_noscript = cli.unwrap_noscript;
         if cli.user_agent.is_none() {
@@ -206,31 +635,52 @@ fn main() {
             options.user_agent = cli.user_agent;
         }
 
+        batch_file_path = cli.batch;
+        cache_directory_path = cli.cache_dir;
         cookie_file_path = cli.cookie_file;
         destination = cli.output.clone();
+        har_file_path = cli.har;
+        mirror_directory_path = cli.mirror;
+        report_file_path = cli.report;
+        warc_file_path = cli.from_warc;
     }
 
-    // Set up cache (attempt to create temporary file)
-    let temp_cache_file: Option<NamedTempFile> = match Builder::new().prefix("monolith-").tempfile()
-    {
-        Ok(tempfile) => Some(tempfile),
-        Err(_) => None,
-    };
-    let cache = Some(Cache::new(
-        CACHE_ASSET_FILE_SIZE_THRESHOLD,
-        if temp_cache_file.is_some() {
-            Some(
+    // Set up cache (use given directory, or attempt to create temporary file)
+    // The temporary file has to be kept around, it gets removed once it goes out of scope
+    let (cache, _temp_cache_file): (Option<Cache>, Option<NamedTempFile>) =
+        if let Some(cache_directory_path) = cache_directory_path {
+            match Cache::open(&cache_directory_path) {
+                Some(mut cache) => {
+                    cache.prune(
+                        cli.cache_max_size.map(|megabytes| megabytes * 1024 * 1024),
+                        cli.cache_max_age
+                            .map(|days| Duration::from_secs(days * SECONDS_IN_DAY)),
+                    );
+
+                    (Some(cache), None)
+                }
+                None => {
+                    if !options.silent {
+                        print_error_message(&format!(
+                            "could not open specified cache directory \"{}\"",
+                            cache_directory_path
+                        ));
+                    }
+                    process::exit(1);
+                }
+            }
+        } else {
+            let temp_cache_file: Option<NamedTempFile> =
+                Builder::new().prefix("monolith-").tempfile().ok();
+            let cache: Cache = Cache::new(
+                CACHE_ASSET_FILE_SIZE_THRESHOLD,
                 temp_cache_file
                     .as_ref()
-                    .unwrap()
-                    .path()
-                    .display()
-                    .to_string(),
-            )
-        } else {
-            None
-        },
-    ));
+                    .map(|temp_cache_file| temp_cache_file.path().display().to_string()),
+            );
+
+            (Some(cache), temp_cache_file)
+        };
 
     // Read and parse cookie file
     let mut cookies: Option<Vec<Cookie>> = None;
@@ -262,28 +712,354 @@ fn main() {
         }
     }
 
+    // Read and parse netrc file
+    if let Some(opt_netrc_file) = cli.netrc.clone() {
+        match fs::read_to_string(&opt_netrc_file) {
+            Ok(str) => {
+                options.auth_rules = parse_netrc(&str);
+            }
+            Err(_) => {
+                if !options.silent {
+                    print_error_message(&format!(
+                        "could not read specified netrc file \"{}\"",
+                        opt_netrc_file
+                    ));
+                }
+                process::exit(1);
+            }
+        }
+    }
+
+    // Read and parse header rules file
+    if let Some(opt_header_rules_file) = cli.header_rules.clone() {
+        match fs::read_to_string(&opt_header_rules_file) {
+            Ok(str) => match parse_header_rules_file_contents(&str) {
+                Some(header_rules) => {
+                    options.header_rules = header_rules;
+                }
+                None => {
+                    if !options.silent {
+                        print_error_message(&format!(
+                            "could not parse specified header rules file \"{}\"",
+                            opt_header_rules_file
+                        ));
+                    }
+                    process::exit(1);
+                }
+            },
+            Err(_) => {
+                if !options.silent {
+                    print_error_message(&format!(
+                        "could not read specified header rules file \"{}\"",
+                        opt_header_rules_file
+                    ));
+                }
+                process::exit(1);
+            }
+        }
+    }
+
+    // Read and parse WARC file
+    let mut archive: Option<Archive> = None;
+    if let Some(opt_warc_file) = warc_file_path {
+        match fs::read(&opt_warc_file) {
+            Ok(data) => match parse_warc(&data) {
+                Some(parsed_archive) => {
+                    archive = Some(parsed_archive);
+                }
+                None => {
+                    if !options.silent {
+                        print_error_message(&format!(
+                            "could not parse specified WARC file \"{}\"",
+                            opt_warc_file
+                        ));
+                    }
+                    process::exit(1);
+                }
+            },
+            Err(_) => {
+                if !options.silent {
+                    print_error_message(&format!(
+                        "could not read specified WARC file \"{}\"",
+                        opt_warc_file
+                    ));
+                }
+                process::exit(1);
+            }
+        }
+    }
+
+    // Make sure the local mirror exists
+    if let Some(mirror_directory) = &mirror_directory_path {
+        if !Path::new(mirror_directory).is_dir() {
+            if !options.silent {
+                print_error_message(&format!(
+                    "could not find mirror directory \"{}\"",
+                    mirror_directory
+                ));
+            }
+            process::exit(1);
+        }
+    }
+
+    if let Some(target) = &cli.target {
+        options
+            .auth_rules
+            .extend(create_target_auth_rules(target, &credentials));
+        options
+            .header_rules
+            .extend(create_target_header_rules(target, &headers));
+    }
+
     // Initiate session
     let output_format = options.output_format.clone();
     let silent = options.silent;
-    let session: Session = Session::new(cache, cookies, options);
+    let mut session: Session = match Session::new(cache, cookies, options) {
+        Ok(session) => session,
+        Err(error) => {
+            if !silent {
+                print_error_message(&error.to_string());
+            }
+            process::exit(1);
+        }
+    };
+    if !silent {
+        session.add_listener(print_session_event);
+    }
+    if let Some(archive) = archive {
+        session.set_archive(archive);
+    }
+    if let Some(mirror_directory) = &mirror_directory_path {
+        session.add_source(DirectorySource::new(mirror_directory));
+    }
+    // Assets get referenced from where the document is going to be saved (before its title is known)
+    if let Some(destination) = &destination {
+        session.set_document_directory(get_document_directory(
+            destination,
+            "",
+            output_format.clone(),
+        ));
+    }
 
     // Retrieve target from source and output result
-    if cli.target == "-" {
+    let mut document_title: Option<String> = None;
+    let target: String = cli
+        .target
+        .clone()
+        .or(batch_file_path.clone())
+        .unwrap_or_default();
+    if let Some(batch_file_path) = batch_file_path {
+        // Read and parse list of targets
+        let data: Vec<u8> = if batch_file_path == "-" {
+            read_stdin()
+        } else {
+            match fs::read(&batch_file_path) {
+                Ok(data) => data,
+                Err(_) => {
+                    if !silent {
+                        print_error_message(&format!(
+                            "could not read specified batch file \"{}\"",
+                            batch_file_path
+                        ));
+                    }
+                    process::exit(1);
+                }
+            }
+        };
+        let targets: Vec<BatchTarget> = match parse_batch(&String::from_utf8_lossy(&data)) {
+            Some(targets) => targets,
+            None => {
+                if !silent {
+                    print_error_message(&format!(
+                        "could not parse specified batch file \"{}\"",
+                        batch_file_path
+                    ));
+                }
+                process::exit(1);
+            }
+        };
+
+        // Targets which don't have outputs of their own would all get written into the same file
+        if let Some(destination) = &destination {
+            if !destination.contains("%title%")
+                && targets
+                    .iter()
+                    .filter(|target| target.output.is_none())
+                    .count()
+                    > 1
+            {
+                if !silent {
+                    print_error_message(&format!(
+                        "output path \"{}\" has to contain %title% to save multiple targets",
+                        destination
+                    ));
+                }
+                process::exit(1);
+            }
+        }
+
+        for target in &targets {
+            let auth_rules: Vec<AuthRule> = create_target_auth_rules(&target.target, &credentials);
+            session.options.auth_rules.extend(auth_rules);
+            let header_rules: Vec<HeaderRule> =
+                create_target_header_rules(&target.target, &headers);
+            session.options.header_rules.extend(header_rules);
+        }
+
+        let mut saved: usize = 0;
+        let mut failures: Vec<String> = vec![];
+        // Targets without output paths of their own get saved within current directory
+        let prepare_document = |session: &mut Session, target: &BatchTarget| {
+            let destination: String = target
+                .output
+                .clone()
+                .or(destination.clone())
+                .unwrap_or_default();
+            session.set_document_directory(get_document_directory(
+                &destination,
+                "",
+                output_format.clone(),
+            ));
+        };
+        batch(
+            &mut session,
+            &targets,
+            prepare_document,
+            |session, target, result| {
+                let error: String = match result {
+                    Ok((data, title)) => {
+                        // Each target gets written using its own path, the output template, or its URL
+                        let destination: String = target
+                            .output
+                            .clone()
+                            .or(destination.clone())
+                            .unwrap_or_else(|| {
+                                create_batch_output_path(&target.target, output_format.clone())
+                            });
+                        let title: String = title.unwrap_or_default();
+                        let final_destination: String =
+                            format_output_path(&destination, &title, output_format.clone());
+                        if let Some(parent) = Path::new(&final_destination).parent() {
+                            fs::create_dir_all(parent).ok();
+                        }
+
+                        let written: bool =
+                            match Output::new(&destination, &title, output_format.clone()) {
+                                Ok(mut output) => output.write(&data).is_ok(),
+                                Err(_) => false,
+                            } && (output_format != MonolithOutputFormat::Directory
+                                || write_assets(&destination, &title, session).is_ok());
+                        if written {
+                            saved += 1;
+                            return;
+                        }
+
+                        format!("could not write output \"{}\"", final_destination)
+                    }
+                    Err(error) => error.to_string(),
+                };
+
+                if !silent {
+                    print_error_message(&format!("Error: {} \"{}\"", error, target.target));
+                }
+                failures.push(target.target.clone());
+            },
+        );
+
+        // Summarize the whole batch
+        if !silent {
+            print_info_message(&format!("Saved {} of {} targets", saved, targets.len()));
+            for failure in &failures {
+                print_info_message(&format!("Failed: {}", failure));
+            }
+        }
+        if !failures.is_empty() {
+            exit_code = 1;
+        }
+    } else if let Some(depth) = cli.depth {
+        // Every page gets saved into its own file, all within the output directory
+        let output_directory: PathBuf = match &destination {
+            Some(destination) if !destination.is_empty() && destination != "-" => {
+                PathBuf::from(destination)
+            }
+            _ => PathBuf::from("."),
+        };
+        if fs::create_dir_all(&output_directory).is_err() {
+            if !silent {
+                print_error_message(&format!(
+                    "could not create output directory \"{}\"",
+                    output_directory.display()
+                ));
+            }
+            process::exit(1);
+        }
+
+        session.set_document_directory(output_directory.clone());
+        let crawl_result = crawl(
+            &mut session,
+            &target,
+            depth,
+            |session, page_url, page_file_name, result| match result {
+                Ok((data, _title)) => {
+                    let page_path: PathBuf = output_directory.join(page_file_name);
+
+                    if fs::write(&page_path, data).is_err()
+                        || (output_format == MonolithOutputFormat::Directory
+                            && write_assets(&page_path.to_string_lossy(), "", session).is_err())
+                    {
+                        if !silent {
+                            print_error_message(&format!(
+                                "could not write page \"{}\"",
+                                page_path.display()
+                            ));
+                        }
+
+                        exit_code = 1;
+                    }
+                }
+                Err(error) => {
+                    if !silent {
+                        print_error_message(&format!("Error: {} \"{}\"", error, page_url));
+                    }
+
+                    exit_code = 1;
+                }
+            },
+        );
+        if let Err(error) = crawl_result {
+            if !silent {
+                print_error_message(&format!("Error: {}", error));
+            }
+
+            exit_code = 1;
+        }
+    } else if target == "-" {
         // Read input from pipe (STDIN)
         let data: Vec<u8> = read_stdin();
 
-        match create_monolithic_document_from_data(session, data, None, None) {
+        match create_monolithic_document_from_data(&mut session, data, None, None) {
             Ok((result, title)) => {
+                document_title = title.clone();
+
                 // Define output
-                let mut output = Output::new(
-                    &destination.unwrap_or(String::new()),
-                    &title.unwrap_or_default(),
-                    output_format,
-                )
-                .expect("could not prepare output");
+                let destination: String = destination.unwrap_or_default();
+                let title: String = title.unwrap_or_default();
+                let mut output = Output::new(&destination, &title, output_format.clone())
+                    .expect("could not prepare output");
 
                 // Write result into STDOUT or file
                 output.write(&result).expect("could not write output");
+
+                // Write assets into their own directory
+                if output_format == MonolithOutputFormat::Directory
+                    && write_assets(&destination, &title, &session).is_err()
+                {
+                    if !silent {
+                        print_error_message("could not write assets directory");
+                    }
+
+                    exit_code = 1;
+                }
             }
             Err(error) => {
                 if !silent {
@@ -294,18 +1070,29 @@ fn main() {
             }
         }
     } else {
-        match create_monolithic_document(session, cli.target) {
+        match create_monolithic_document(&mut session, target.clone()) {
             Ok((result, title)) => {
+                document_title = title.clone();
+
                 // Define output
-                let mut output = Output::new(
-                    &destination.unwrap_or(String::new()),
-                    &title.unwrap_or_default(),
-                    output_format,
-                )
-                .expect("could not prepare output");
+                let destination: String = destination.unwrap_or_default();
+                let title: String = title.unwrap_or_default();
+                let mut output = Output::new(&destination, &title, output_format.clone())
+                    .expect("could not prepare output");
 
                 // Write result into STDOUT or file
                 output.write(&result).expect("could not write output");
+
+                // Write assets into their own directory
+                if output_format == MonolithOutputFormat::Directory
+                    && write_assets(&destination, &title, &session).is_err()
+                {
+                    if !silent {
+                        print_error_message("could not write assets directory");
+                    }
+
+                    exit_code = 1;
+                }
             }
             Err(error) => {
                 if !silent {
@@ -317,6 +1104,33 @@ fn main() {
         }
     }
 
+    // Write HAR file (even if the document couldn't be saved, it shows what went wrong)
+    if let Some(har_file_path) = har_file_path {
+        let har: Vec<u8> = create_har(&document_title.unwrap_or(target), session.exchanges());
+
+        if fs::write(&har_file_path, har).is_err() {
+            if !silent {
+                print_error_message(&format!("could not write HAR file \"{}\"", har_file_path));
+            }
+
+            exit_code = 1;
+        }
+    }
+
+    // Write report (which is just as useful when the document couldn't be saved)
+    if let Some(report_file_path) = report_file_path {
+        if fs::write(&report_file_path, create_report(session.report())).is_err() {
+            if !silent {
+                print_error_message(&format!(
+                    "could not write report file \"{}\"",
+                    report_file_path
+                ));
+            }
+
+            exit_code = 1;
+        }
+    }
+
     // TODO: bring this back
     // Clean up (shred database file)
     //cache.unwrap().destroy_database_file();
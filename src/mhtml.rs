@@ -0,0 +1,441 @@
+use base64::{prelude::BASE64_STANDARD, Engine};
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+
+use crate::archive::{Archive, ArchivedResource};
+use crate::core::{is_plaintext_media_type, parse_content_type};
+use crate::session::EmbeddedAsset;
+use crate::url::{resolve_url, Url};
+
+const MAX_LINE_LENGTH: usize = 76; // RFC 2045 limit for encoded lines (not counting CRLF)
+
+pub fn create_mhtml(
+    document: &EmbeddedAsset,
+    assets: &[EmbeddedAsset],
+    document_title: Option<String>,
+) -> Vec<u8> {
+    let parts: Vec<String> = [document]
+        .into_iter()
+        .chain(assets.iter())
+        .map(create_mhtml_part)
+        .collect();
+    let boundary: String = create_mhtml_boundary(&parts);
+
+    let mut mhtml: String = String::new();
+    mhtml.push_str(&format!("From: <Saved by {}>\r\n", env!("CARGO_PKG_NAME")));
+    mhtml.push_str(&format!("Snapshot-Content-Location: {}\r\n", document.url));
+    if let Some(title) = document_title {
+        mhtml.push_str(&format!(
+            "Subject: {}\r\n",
+            encode_mime_header_value(&title)
+        ));
+    }
+    mhtml.push_str(&format!("Date: {}\r\n", Utc::now().to_rfc2822()));
+    mhtml.push_str("MIME-Version: 1.0\r\n");
+    mhtml.push_str(&format!(
+        "Content-Type: multipart/related;\r\n\ttype=\"text/html\";\r\n\tboundary=\"{}\"\r\n",
+        boundary
+    ));
+    mhtml.push_str("\r\n");
+
+    for part in parts {
+        mhtml.push_str(&format!("--{}\r\n", boundary));
+        mhtml.push_str(&part);
+        mhtml.push_str("\r\n");
+    }
+    mhtml.push_str(&format!("--{}--\r\n", boundary));
+
+    mhtml.into_bytes()
+}
+
+pub fn decode_quoted_printable(data: &[u8]) -> Vec<u8> {
+    let mut result: Vec<u8> = Vec::with_capacity(data.len());
+    let mut i: usize = 0;
+
+    while i < data.len() {
+        if data[i] == b'=' {
+            if data[i + 1..].starts_with(b"\r\n") {
+                // Soft line break
+                i += 3;
+                continue;
+            } else if data[i + 1..].starts_with(b"\n") {
+                i += 2;
+                continue;
+            } else if i + 2 < data.len() {
+                if let Ok(byte) =
+                    u8::from_str_radix(&String::from_utf8_lossy(&data[i + 1..i + 3]), 16)
+                {
+                    result.push(byte);
+                    i += 3;
+                    continue;
+                }
+            }
+        }
+
+        // Malformed escape sequences are kept as they are
+        result.push(data[i]);
+        i += 1;
+    }
+
+    result
+}
+
+pub fn encode_quoted_printable(data: &[u8]) -> String {
+    let mut result: String = String::new();
+    let lines: Vec<&[u8]> = data.split(|byte| *byte == b'\n').collect();
+    let last_line_index: usize = lines.len() - 1;
+
+    for (i, mut line) in lines.into_iter().enumerate() {
+        // Hard line breaks become CRLF
+        if i < last_line_index {
+            line = line.strip_suffix(b"\r").unwrap_or(line);
+        }
+
+        let mut line_length: usize = 0;
+        for (j, byte) in line.iter().enumerate() {
+            let encoded: String = match byte {
+                // Trailing whitespace has to be encoded, otherwise it may get stripped in transit
+                b' ' | b'\t' if j < line.len() - 1 => (*byte as char).to_string(),
+                33..=60 | 62..=126 => (*byte as char).to_string(),
+                _ => format!("={:02X}", byte),
+            };
+
+            // Leave room for the equal sign of a soft line break
+            if line_length + encoded.len() > MAX_LINE_LENGTH - 1 {
+                result.push_str("=\r\n");
+                line_length = 0;
+            }
+
+            result.push_str(&encoded);
+            line_length += encoded.len();
+        }
+
+        if i < last_line_index {
+            result.push_str("\r\n");
+        }
+    }
+
+    result
+}
+
+fn create_cid_url(content_id: &str) -> Option<Url> {
+    let content_id: &str = content_id
+        .trim()
+        .trim_start_matches('<')
+        .trim_end_matches('>');
+
+    if content_id.is_empty() {
+        None
+    } else {
+        Url::parse(&format!("cid:{}", content_id)).ok()
+    }
+}
+
+fn create_mhtml_boundary(parts: &[String]) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part.as_bytes());
+    }
+    let digest: String = hasher
+        .finalize()
+        .iter()
+        .take(16)
+        .map(|b| format!("{:02x}", b))
+        .collect();
+
+    // Make sure the boundary doesn't occur anywhere within the encoded parts
+    let mut i: usize = 0;
+    loop {
+        let boundary: String = if i == 0 {
+            format!("----MultipartBoundary--{}----", digest)
+        } else {
+            format!("----MultipartBoundary--{}-{}----", digest, i)
+        };
+
+        if !parts.iter().any(|part| part.contains(&boundary)) {
+            return boundary;
+        }
+
+        i += 1;
+    }
+}
+
+fn create_mhtml_part(asset: &EmbeddedAsset) -> String {
+    let mut part: String = String::new();
+
+    let charset: &str = asset.charset.trim();
+    if !charset.is_empty()
+        && !charset.eq_ignore_ascii_case("US-ASCII")
+        && is_plaintext_media_type(&asset.media_type)
+    {
+        part.push_str(&format!(
+            "Content-Type: {}; charset={}\r\n",
+            asset.media_type, charset
+        ));
+    } else {
+        part.push_str(&format!("Content-Type: {}\r\n", asset.media_type));
+    }
+
+    let body: String = if is_plaintext_media_type(&asset.media_type) {
+        part.push_str("Content-Transfer-Encoding: quoted-printable\r\n");
+        encode_quoted_printable(&asset.data)
+    } else {
+        part.push_str("Content-Transfer-Encoding: base64\r\n");
+        BASE64_STANDARD
+            .encode(&asset.data)
+            .as_bytes()
+            .chunks(MAX_LINE_LENGTH)
+            .map(|line| String::from_utf8_lossy(line).to_string())
+            .collect::<Vec<String>>()
+            .join("\r\n")
+    };
+
+    part.push_str(&format!("Content-Location: {}\r\n", asset.url));
+    part.push_str("\r\n");
+    part.push_str(&body);
+
+    part
+}
+
+fn encode_mime_header_value(value: &str) -> String {
+    if value.chars().all(|c| c.is_ascii() && !c.is_ascii_control()) {
+        value.to_string()
+    } else {
+        // RFC 2047 encoded-word
+        format!("=?utf-8?B?{}?=", BASE64_STANDARD.encode(value.as_bytes()))
+    }
+}
+
+pub fn parse_mhtml(data: &[u8]) -> Option<(Url, Archive)> {
+    let (headers, body) = split_mime_entity(data);
+    let content_type: String = get_mime_header(&headers, "Content-Type").unwrap_or_default();
+    if !content_type
+        .trim()
+        .to_lowercase()
+        .starts_with("multipart/related")
+    {
+        return None;
+    }
+
+    // Relative part locations get resolved against the location of the saved document
+    let base_url: Option<Url> = get_mime_header(&headers, "Snapshot-Content-Location")
+        .or_else(|| get_mime_header(&headers, "Content-Location"))
+        .and_then(|location| Url::parse(location.trim()).ok());
+
+    let mut archive: Archive = Archive::new();
+    let mut root_url: Option<Url> = None;
+    read_multipart_body(
+        &content_type,
+        body,
+        base_url.as_ref(),
+        &mut archive,
+        &mut root_url,
+    );
+
+    // RFC 2387: the root part is either referred to by the "start" parameter, or is the first one
+    if let Some(start) = get_mime_header_parameter(&content_type, "start") {
+        if let Some(resource) = create_cid_url(&start).and_then(|url| archive.get(&url)) {
+            root_url = Some(resource.url.clone());
+        }
+    }
+
+    root_url.map(|root_url| (root_url, archive))
+}
+
+fn find_bytes(haystack: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    if from > haystack.len() || needle.is_empty() {
+        return None;
+    }
+
+    haystack[from..]
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|i| from + i)
+}
+
+fn get_mime_header(headers: &[(String, String)], name: &str) -> Option<String> {
+    headers
+        .iter()
+        .find(|(header_name, _)| header_name.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.clone())
+}
+
+fn get_mime_header_parameter(header_value: &str, name: &str) -> Option<String> {
+    header_value.split(';').skip(1).find_map(|parameter| {
+        let (parameter_name, parameter_value) = parameter.split_once('=')?;
+
+        if parameter_name.trim().eq_ignore_ascii_case(name) {
+            Some(parameter_value.trim().trim_matches('"').to_string())
+        } else {
+            None
+        }
+    })
+}
+
+fn parse_mime_headers(data: &[u8]) -> Vec<(String, String)> {
+    let mut headers: Vec<(String, String)> = vec![];
+
+    for line in String::from_utf8_lossy(data).lines() {
+        if line.starts_with([' ', '\t']) {
+            // Folded header line
+            if let Some((_, value)) = headers.last_mut() {
+                value.push(' ');
+                value.push_str(line.trim());
+            }
+        } else if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+
+    headers
+}
+
+fn read_multipart_body(
+    content_type: &str,
+    body: &[u8],
+    base_url: Option<&Url>,
+    archive: &mut Archive,
+    root_url: &mut Option<Url>,
+) {
+    let boundary: String = match get_mime_header_parameter(content_type, "boundary") {
+        Some(boundary) if !boundary.is_empty() => boundary,
+        _ => return,
+    };
+    let delimiter: Vec<u8> = format!("--{}", boundary).into_bytes();
+
+    // Locate all delimiter lines
+    let mut delimiters: Vec<usize> = vec![];
+    let mut position: usize = 0;
+    while let Some(i) = find_bytes(body, &delimiter, position) {
+        if i == 0 || body[i - 1] == b'\n' {
+            delimiters.push(i);
+        }
+        position = i + delimiter.len();
+    }
+
+    for (n, start) in delimiters.iter().enumerate() {
+        let part_start: usize = start + delimiter.len();
+
+        // Closing delimiter
+        if body[part_start..].starts_with(b"--") {
+            break;
+        }
+
+        // Skip the remainder of the delimiter line
+        let part_start: usize = match find_bytes(body, b"\n", part_start) {
+            Some(i) => i + 1,
+            None => break,
+        };
+        let mut part_end: usize = delimiters.get(n + 1).copied().unwrap_or(body.len());
+        // The line break preceding a delimiter belongs to the delimiter
+        if part_end < body.len() || body.ends_with(b"\n") {
+            part_end = part_end.saturating_sub(1);
+            if part_end > part_start && body[part_end - 1] == b'\r' {
+                part_end -= 1;
+            }
+        }
+        if part_end < part_start {
+            continue;
+        }
+
+        read_part(&body[part_start..part_end], base_url, archive, root_url);
+    }
+}
+
+fn read_part(
+    data: &[u8],
+    base_url: Option<&Url>,
+    archive: &mut Archive,
+    root_url: &mut Option<Url>,
+) {
+    let (headers, body) = split_mime_entity(data);
+    let content_type: String = get_mime_header(&headers, "Content-Type").unwrap_or_default();
+
+    // Some archivers group alternative versions of the document into nested multipart entities
+    if content_type.trim().to_lowercase().starts_with("multipart/") {
+        read_multipart_body(&content_type, body, base_url, archive, root_url);
+        return;
+    }
+
+    let data: Vec<u8> = match get_mime_header(&headers, "Content-Transfer-Encoding")
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase()
+        .as_str()
+    {
+        "base64" => {
+            let encoded: Vec<u8> = body
+                .iter()
+                .filter(|byte| !byte.is_ascii_whitespace())
+                .copied()
+                .collect();
+            match BASE64_STANDARD.decode(encoded) {
+                Ok(decoded) => decoded,
+                Err(_) => return,
+            }
+        }
+        "quoted-printable" => decode_quoted_printable(body),
+        _ => body.to_vec(),
+    };
+
+    let content_location: Option<Url> =
+        get_mime_header(&headers, "Content-Location").and_then(|location| {
+            let location: &str = location.trim();
+
+            match base_url {
+                Some(base_url) => Some(resolve_url(base_url, location)),
+                None => Url::parse(location).ok(),
+            }
+        });
+    let content_id: Option<Url> =
+        get_mime_header(&headers, "Content-ID").and_then(|content_id| create_cid_url(&content_id));
+
+    let url: Url = match content_location.or(content_id.clone()) {
+        Some(url) => url,
+        None => return, // Parts which can't be referenced are of no use
+    };
+
+    let (media_type, charset) = if content_type.trim().is_empty() {
+        ("".to_string(), "".to_string())
+    } else {
+        let (media_type, charset, _is_base64) = parse_content_type(&content_type);
+        // Charset parameter is optional, and parse_content_type() falls back to US-ASCII
+        if get_mime_header_parameter(&content_type, "charset").is_some() {
+            (media_type, charset.trim_matches('"').to_string())
+        } else {
+            (media_type, "".to_string())
+        }
+    };
+
+    archive.add(ArchivedResource {
+        url: url.clone(),
+        media_type,
+        charset,
+        data,
+    });
+    if let Some(content_id) = content_id {
+        archive.add_redirect(&content_id, &url);
+    }
+
+    if root_url.is_none() {
+        *root_url = Some(url);
+    }
+}
+
+fn split_mime_entity(data: &[u8]) -> (Vec<(String, String)>, &[u8]) {
+    // Headers are separated from the body by an empty line
+    let crlf: Option<usize> = find_bytes(data, b"\r\n\r\n", 0);
+    let lf: Option<usize> = find_bytes(data, b"\n\n", 0);
+
+    let (headers_end, body_start) = match (crlf, lf) {
+        (Some(crlf), Some(lf)) if lf < crlf => (lf, lf + 2),
+        (Some(crlf), _) => (crlf, crlf + 4),
+        (None, Some(lf)) => (lf, lf + 2),
+        (None, None) => (data.len(), data.len()),
+    };
+
+    (
+        parse_mime_headers(&data[..headers_end]),
+        &data[body_start..],
+    )
+}
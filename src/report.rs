@@ -0,0 +1,67 @@
+use serde_json::{json, Value};
+
+use crate::core::MonolithError;
+use crate::url::Url;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AssetOutcome {
+    Embedded,          // Made part of the document
+    Remote,            // Couldn't be retrieved, reference to it was kept as it is
+    Dropped,           // Couldn't be retrieved, reference to it was removed
+    IntegrityMismatch, // Retrieved, but left out for not matching its integrity hash
+    Excluded,          // Left out due to options (e.g. --no-images)
+}
+
+impl AssetOutcome {
+    pub fn as_str(&self) -> &str {
+        match self {
+            AssetOutcome::Embedded => "embedded",
+            AssetOutcome::Remote => "remote",
+            AssetOutcome::Dropped => "dropped",
+            AssetOutcome::IntegrityMismatch => "integrity-mismatch",
+            AssetOutcome::Excluded => "excluded",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReportedAsset {
+    pub url: Url,
+    pub parent_url: Url,        // Document or stylesheet the URL was found in
+    pub context: String,        // Where exactly it was found, e.g. "img[src]" or "@import"
+    pub final_url: Option<Url>, // Location it was retrieved from, after redirects
+    pub media_type: String,
+    pub size: usize,
+    pub from_cache: bool,
+    pub outcome: AssetOutcome,
+    pub error: Option<MonolithError>,
+}
+
+pub fn create_report(reported_assets: &[ReportedAsset]) -> Vec<u8> {
+    let report: Value = json!({
+        "creator": {
+            "name": env!("CARGO_PKG_NAME"),
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "assets": reported_assets.iter().map(create_report_entry).collect::<Vec<Value>>(),
+    });
+
+    let mut result: Vec<u8> = serde_json::to_vec_pretty(&report).unwrap();
+    result.push(b'\n');
+
+    result
+}
+
+fn create_report_entry(reported_asset: &ReportedAsset) -> Value {
+    json!({
+        "url": reported_asset.url.as_str(),
+        "parent_url": reported_asset.parent_url.as_str(),
+        "context": reported_asset.context,
+        "final_url": reported_asset.final_url.as_ref().map(Url::as_str),
+        "media_type": reported_asset.media_type,
+        "size": reported_asset.size,
+        "from_cache": reported_asset.from_cache,
+        "outcome": reported_asset.outcome.as_str(),
+        "error": reported_asset.error.as_ref().map(MonolithError::to_string),
+    })
+}
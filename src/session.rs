@@ -1,23 +1,197 @@
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::time::Duration;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 
-use reqwest::blocking::Client;
-use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE, COOKIE, REFERER, USER_AGENT};
+use chrono::{DateTime, Utc};
+use reqwest::header::{
+    HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, CACHE_CONTROL, CONTENT_TYPE, COOKIE, ETAG,
+    EXPIRES, HOST, LAST_MODIFIED, LOCATION, REFERER, RETRY_AFTER, USER_AGENT, WWW_AUTHENTICATE,
+};
+use reqwest::StatusCode;
+use sha2::{Digest, Sha256};
 
-use crate::cache::Cache;
+use crate::archive::{Archive, ArchivedResource};
+use crate::auth::{create_authorization, Credentials};
+use crate::cache::{parse_http_date, Cache, CacheHeaders};
 use crate::cookies::Cookie;
 use crate::core::{
-    detect_media_type, parse_content_type, print_error_message, print_info_message, MonolithOptions,
+    detect_file_extension_by_media_type, detect_media_type, detect_media_type_by_file_name,
+    parse_content_type, MonolithError, MonolithOfflinePolicy, MonolithOptions,
+    MonolithOutputFormat,
+};
+use crate::crawl::create_page_file_name;
+use crate::events::{DocumentPhase, EventListener, SessionEvent};
+use crate::report::{AssetOutcome, ReportedAsset};
+use crate::source::{AssetSource, ClientFetcher, FetchError, FetchedResponse, Fetcher};
+use crate::url::{
+    clean_url, create_data_url, create_relative_url, domain_is_within_domain, get_referer_url,
+    parse_data_url, resolve_url, Url,
 };
-use crate::url::{clean_url, domain_is_within_domain, get_referer_url, parse_data_url, Url};
+
+const ABORT_CHECK_INTERVAL: Duration = Duration::from_millis(100); // How often waiting gets interrupted to see if the capture got aborted
+const MAX_FILE_EXTENSION_LENGTH: usize = 5;
+const MAX_REDIRECTS: usize = 10; // Same limit as the one reqwest uses by default
+const MAX_REQUEST_INTERVAL: Duration = Duration::from_secs(60 * 60 * 24); // Slower rates are treated as one request per day
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(120); // Servers asking to wait longer don't get retried
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+// Single HTTP request-response pair, as it was seen on the wire (or an attempt to make one)
+#[derive(Clone, Debug)]
+pub struct HttpExchange {
+    pub date: DateTime<Utc>,
+    pub url: Url,
+    pub request_headers: Vec<(String, String)>,
+    pub remote_address: Option<String>,
+    pub http_version: String,
+    pub status: u16,
+    pub response_headers: Vec<(String, String)>,
+    pub data: Vec<u8>,
+    pub wait: Duration,        // Time spent waiting for the response to arrive
+    pub receive: Duration,     // Time spent reading the response body
+    pub from_cache: bool,      // Served from cache, no request was actually made
+    pub error: Option<String>, // Reason why no response was received
+}
+
+// Prefetched assets are looked up by their URL (same as the cache does), no matter where they're referenced from
+type RequestKey = String;
+
+// Outcome of retrieving an asset over the network, kept around until the asset is needed
+#[derive(Clone)]
+struct RequestedAsset {
+    exchanges: Vec<HttpExchange>, // Every hop, including redirects
+    response: Result<RequestedResponse, String>,
+    retries: usize,
+}
+
+#[derive(Clone)]
+struct RequestedResponse {
+    status: StatusCode,
+    url: Url,
+    content_type: String,
+    cache_headers: CacheHeaders,
+    data: Vec<u8>,
+    error: Option<String>, // Reason why the body couldn't be read
+    authorized: bool,      // Credentials were sent along with the request (or one of its redirects)
+}
+
+// Outcome of the latest attempt to retrieve an asset, which gets reported wherever the asset is used
+struct Retrieval {
+    final_url: Option<Url>,
+    media_type: String,
+    size: usize,
+    from_cache: bool,
+    error: Option<MonolithError>,
+}
+
+// Asset stored alongside the document as a separate entity (instead of being turned into a data URL)
+#[derive(Clone, Debug)]
+pub struct EmbeddedAsset {
+    pub url: Url,
+    pub media_type: String,
+    pub charset: String,
+    pub data: Vec<u8>,
+}
+
+impl EmbeddedAsset {
+    pub fn file_name(&self) -> String {
+        // Named after its contents, which makes identical assets share one file
+        let mut hasher = Sha256::new();
+        hasher.update(&self.data);
+        let digest: String = hasher
+            .finalize()
+            .iter()
+            .take(16)
+            .map(|b| format!("{:02x}", b))
+            .collect();
+
+        // Prefer extensions which match the media type, fall back to the original one (if it looks like one)
+        let extension: Option<String> = Some(detect_file_extension_by_media_type(&self.media_type))
+            .filter(|extension| !extension.is_empty())
+            .or_else(|| {
+                self.url
+                    .path_segments()
+                    .and_then(|mut segments| segments.next_back())
+                    .and_then(|file_name| file_name.rsplit_once('.'))
+                    .map(|(_, extension)| extension.to_lowercase())
+                    .filter(|extension| {
+                        !extension.is_empty()
+                            && extension.len() <= MAX_FILE_EXTENSION_LENGTH
+                            && extension.chars().all(|c| c.is_ascii_alphanumeric())
+                    })
+            });
+
+        match extension {
+            Some(extension) => format!("{}.{}", digest, extension),
+            None => digest,
+        }
+    }
+}
+
+// Lets captures be stopped from elsewhere (e.g. from another thread)
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> CancellationToken {
+        CancellationToken::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+// Limits which apply to hosts of the given domain instead of the global ones
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RateLimitRule {
+    pub domain: String, // Leading dot makes the rule apply to subdomains as well
+    pub requests_per_second: Option<f64>,
+    pub delay: Option<u64>, // Minimum delay between requests (in milliseconds)
+    pub max_connections: Option<usize>,
+}
 
 pub struct Session {
+    archive: Option<Archive>,
+    asset_depth: usize, // How many assets deep the content that's being embedded is
+    asset_errors: Vec<MonolithError>, // Assets which couldn't be retrieved or embedded
+    asset_file_names: HashSet<String>, // Names of files assets are stored as, which are costly to compute
+    assets: Vec<EmbeddedAsset>,
     cache: Option<Cache>,
-    client: Client,
+    cancellation: CancellationToken,
     cookies: Option<Vec<Cookie>>,
+    deadline: Option<Instant>, // When the whole capture runs out of time
+    document_archive: Option<Archive>, // Archive the current document was taken from (e.g. MHTML target)
+    document_directory: PathBuf, // Where the document gets saved, assets get referenced from there
+    document_start: usize,       // Index of the first exchange made for the current document
+    dry_run: bool, // Going through the document only to find out which assets it needs
+    exchanges: Vec<HttpExchange>,
+    fetcher: Box<dyn Fetcher>,  // Makes requests over the network
+    from_cache: bool,           // Whether the asset which was retrieved last came from cache
+    follow_new_links: bool,     // Discover pages which aren't known yet
+    link_scope: Option<String>, // Domain of pages which get saved along with the current document
+    links: Vec<Url>,            // Newly discovered pages linked from the current document
+    listeners: Mutex<Vec<Box<dyn EventListener>>>, // Told about progress of retrievals
+    next_requests: Mutex<HashMap<String, Instant>>, // When each host can be sent another request
+    offline_misses: Vec<Url>,   // Assets which couldn't be found while offline
     pub options: MonolithOptions,
-    urls: Vec<String>,
+    pages: HashSet<Url>, // Pages which get saved along with the current document
+    pending_requests: Vec<(Url, Url)>, // Assets which the dry run needed, but didn't have
+    prefetched_assets: HashMap<RequestKey, RequestedAsset>, // Retrieved ahead of time
+    report: Vec<ReportedAsset>, // Every asset found within documents, along with its fate
+    report_start: usize, // Index of the first report entry of the current document
+    retried_assets: Vec<(Url, usize)>, // Assets which needed retries, along with their number
+    retrievals: HashMap<String, Retrieval>, // Latest retrieval of every asset, by its URL
+    sources: Vec<Box<dyn AssetSource>>, // Asked for assets before going to the network
 }
 
 impl Session {
@@ -25,77 +199,942 @@ impl Session {
         cache: Option<Cache>,
         cookies: Option<Vec<Cookie>>,
         options: MonolithOptions,
-    ) -> Self {
-        let mut header_map = HeaderMap::new();
-        if let Some(user_agent) = &options.user_agent {
-            header_map.insert(
-                USER_AGENT,
-                HeaderValue::from_str(user_agent).expect("Invalid User-Agent header specified"),
-            );
-        }
-        let client = Client::builder()
-            .timeout(Duration::from_secs(if options.timeout > 0 {
-                options.timeout
-            } else {
-                // We have to specify something that eventually makes the program fail
-                // (prevent it from hanging forever)
-                600 // 10 minutes in seconds
-            }))
-            .danger_accept_invalid_certs(options.insecure)
-            .default_headers(header_map)
-            .build()
-            .expect("Failed to initialize HTTP client");
-
-        Session {
+    ) -> Result<Self, MonolithError> {
+        // Options which the network can't be accessed with are rejected right away
+        let fetcher: ClientFetcher = ClientFetcher::new(&options)?;
+        let deadline: Option<Instant> = get_deadline(options.time_limit);
+
+        Ok(Session {
+            archive: None,
+            asset_depth: 0,
+            asset_errors: Vec::new(),
+            asset_file_names: HashSet::new(),
+            assets: Vec::new(),
             cache,
+            cancellation: CancellationToken::new(),
             cookies,
-            client,
+            deadline,
+            document_archive: None,
+            document_directory: PathBuf::new(),
+            document_start: 0,
+            dry_run: false,
+            exchanges: Vec::new(),
+            fetcher: Box::new(fetcher),
+            from_cache: false,
+            follow_new_links: false,
+            link_scope: None,
+            links: Vec::new(),
+            listeners: Mutex::new(Vec::new()),
+            next_requests: Mutex::new(HashMap::new()),
+            offline_misses: Vec::new(),
             options,
-            urls: Vec::new(),
+            pages: HashSet::new(),
+            pending_requests: Vec::new(),
+            prefetched_assets: HashMap::new(),
+            report: Vec::new(),
+            report_start: 0,
+            retried_assets: Vec::new(),
+            retrievals: HashMap::new(),
+            sources: Vec::new(),
+        })
+    }
+
+    // Reason why the capture should stop, if it should
+    pub fn abort_reason(&self) -> Option<MonolithError> {
+        if self.cancellation.is_cancelled() {
+            Some(MonolithError::Cancelled)
+        } else if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            Some(MonolithError::DeadlineExceeded)
+        } else {
+            None
+        }
+    }
+
+    pub fn add_listener(&mut self, listener: impl EventListener + 'static) {
+        self.listeners.get_mut().unwrap().push(Box::new(listener));
+    }
+
+    pub fn add_page(&mut self, url: &Url) -> bool {
+        self.pages.insert(url.clone())
+    }
+
+    pub fn add_source(&mut self, source: impl AssetSource + 'static) {
+        self.sources.push(Box::new(source));
+    }
+
+    pub fn add_asset_error(&mut self, error: MonolithError) {
+        self.asset_errors.push(error);
+    }
+
+    // Everything that went wrong with assets of the current document, embedding carries on past those
+    pub fn asset_errors(&self) -> &[MonolithError] {
+        &self.asset_errors
+    }
+
+    pub fn assets(&self) -> &[EmbeddedAsset] {
+        &self.assets
+    }
+
+    pub fn document_report(&self) -> &[ReportedAsset] {
+        &self.report[self.report_start..]
+    }
+
+    pub fn report(&self) -> &[ReportedAsset] {
+        &self.report
+    }
+
+    pub fn report_asset(
+        &mut self,
+        parent_url: &Url,
+        url: &Url,
+        context: &str,
+        outcome: AssetOutcome,
+    ) {
+        // Data URLs are already part of the document
+        if self.dry_run || url.scheme() == "data" {
+            return;
+        }
+
+        let retrieval: Option<&Retrieval> = self
+            .retrievals
+            .get(url.as_str())
+            .filter(|_| outcome != AssetOutcome::Excluded);
+        self.report.push(ReportedAsset {
+            url: url.clone(),
+            parent_url: parent_url.clone(),
+            context: context.to_string(),
+            final_url: retrieval.and_then(|r| r.final_url.clone()),
+            media_type: retrieval.map(|r| r.media_type.clone()).unwrap_or_default(),
+            size: retrieval.map(|r| r.size).unwrap_or_default(),
+            from_cache: retrieval.is_some_and(|r| r.from_cache),
+            outcome,
+            error: retrieval.and_then(|r| r.error.clone()),
+        });
+    }
+
+    pub fn retried_assets(&self) -> &[(Url, usize)] {
+        &self.retried_assets
+    }
+
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.cancellation.clone()
+    }
+
+    fn emit(&self, event: SessionEvent) {
+        for listener in self.listeners.lock().unwrap().iter_mut() {
+            listener.on_event(&event);
+        }
+    }
+
+    pub fn enter_phase(&self, phase: DocumentPhase) {
+        self.emit(SessionEvent::PhaseChanged(phase));
+    }
+
+    pub fn embed_asset(
+        &mut self,
+        media_type: &str,
+        charset: &str,
+        data: &[u8],
+        final_url: &Url,
+        fragment: Option<&str>,
+    ) -> String {
+        let stores_assets_separately: bool = matches!(
+            self.options.output_format,
+            MonolithOutputFormat::MHTML
+                | MonolithOutputFormat::WebArchive
+                | MonolithOutputFormat::ZIM
+                | MonolithOutputFormat::Directory
+                | MonolithOutputFormat::EPUB
+                | MonolithOutputFormat::ZIP
+        );
+
+        if !stores_assets_separately || final_url.scheme() == "data" {
+            let mut data_url: Url = create_data_url(media_type, charset, data, final_url);
+            data_url.set_fragment(fragment);
+            return data_url.to_string();
+        }
+
+        let asset: EmbeddedAsset = EmbeddedAsset {
+            url: clean_url(final_url.clone()),
+            media_type: if media_type.is_empty() {
+                detect_media_type(data, final_url)
+            } else {
+                media_type.to_string()
+            },
+            charset: charset.to_string(),
+            data: data.to_vec(),
+        };
+
+        let asset_reference: String = if matches!(
+            self.options.output_format,
+            MonolithOutputFormat::MHTML | MonolithOutputFormat::WebArchive
+        ) {
+            // MHTML documents and web archives reference their parts by URL
+            if !self.assets.iter().any(|a| a.url == asset.url) {
+                self.assets.push(asset.clone());
+            }
+
+            asset.url.to_string()
+        } else {
+            // Other formats store assets as files next to the document
+            let file_name: String = asset.file_name();
+            if self.asset_file_names.insert(file_name.clone()) {
+                self.assets.push(asset);
+            }
+
+            match &self.options.assets_directory {
+                // Only the document itself is located outside of the assets directory
+                Some(assets_directory)
+                    if self.options.output_format == MonolithOutputFormat::Directory
+                        && self.asset_depth == 0 =>
+                {
+                    let assets_directory_url: String =
+                        create_relative_url(Path::new(assets_directory), &self.document_directory);
+                    if assets_directory_url.is_empty() {
+                        file_name
+                    } else {
+                        format!("{}/{}", assets_directory_url, file_name)
+                    }
+                }
+                _ => file_name,
+            }
+        };
+
+        match fragment {
+            Some(fragment) => format!("{}#{}", asset_reference, fragment),
+            None => asset_reference,
         }
     }
 
+    pub fn document_exchanges(&self) -> &[HttpExchange] {
+        &self.exchanges[self.document_start..]
+    }
+
+    pub fn exchanges(&self) -> &[HttpExchange] {
+        &self.exchanges
+    }
+
+    pub fn follow_link(&mut self, url: &Url) -> Option<String> {
+        let link_scope: &str = self.link_scope.as_deref()?;
+
+        // Pages found during the dry run would get discovered twice
+        if self.dry_run {
+            return None;
+        }
+
+        // Only links found within the document itself lead to other pages
+        if self.asset_depth > 0 || (url.scheme() != "http" && url.scheme() != "https") {
+            return None;
+        }
+
+        // Links to files which aren't web pages are left alone
+        let file_name: &str = url.path().rsplit('/').next().unwrap_or_default();
+        let media_type: String = detect_media_type_by_file_name(file_name);
+        if !media_type.is_empty()
+            && media_type != "text/html"
+            && media_type != "application/xhtml+xml"
+        {
+            return None;
+        }
+
+        // Stay within given domains, or within the domain of the first page
+        let host: &str = url.host_str()?;
+        let within_scope: bool = match &self.options.domains {
+            Some(domains) if !self.options.blacklist_domains => domains
+                .iter()
+                .any(|domain| domain_is_within_domain(host, domain.trim())),
+            Some(domains) => {
+                domain_is_within_domain(host, link_scope)
+                    && !domains
+                        .iter()
+                        .any(|domain| domain_is_within_domain(host, domain.trim()))
+            }
+            None => domain_is_within_domain(host, link_scope),
+        };
+        if !within_scope {
+            return None;
+        }
+
+        let mut page_url: Url = clean_url(url.clone());
+        page_url.set_fragment(None);
+        let page_file_name: String =
+            create_page_file_name(&page_url, self.options.output_format.clone());
+        if !self.pages.contains(&page_url) {
+            if !self.follow_new_links {
+                return None;
+            }
+
+            self.pages.insert(page_url.clone());
+            self.links.push(page_url);
+        }
+
+        match url.fragment() {
+            Some(fragment) => Some(format!("{}#{}", page_file_name, fragment)),
+            None => Some(page_file_name),
+        }
+    }
+
+    fn get_rate_limit_rule(&self, url: &Url) -> Option<&RateLimitRule> {
+        self.options
+            .rate_limit_rules
+            .iter()
+            .find(|rate_limit_rule| {
+                domain_is_within_domain(url.host_str().unwrap_or_default(), &rate_limit_rule.domain)
+            })
+    }
+
+    fn get_max_connections(&self, url: &Url) -> usize {
+        let max_connections: usize = self
+            .get_rate_limit_rule(url)
+            .and_then(|rate_limit_rule| rate_limit_rule.max_connections)
+            .unwrap_or(self.options.parallel_requests_per_host);
+
+        // No limit if set to 0
+        if max_connections > 0 {
+            max_connections
+        } else {
+            usize::MAX
+        }
+    }
+
+    fn get_request_interval(&self, url: &Url) -> Duration {
+        let rate_limit_rule: Option<&RateLimitRule> = self.get_rate_limit_rule(url);
+        let requests_per_second: f64 = rate_limit_rule
+            .and_then(|rate_limit_rule| rate_limit_rule.requests_per_second)
+            .unwrap_or(self.options.requests_per_second);
+        let delay: u64 = rate_limit_rule
+            .and_then(|rate_limit_rule| rate_limit_rule.delay)
+            .unwrap_or(self.options.request_delay);
+
+        let mut interval: Duration = Duration::from_millis(delay);
+        if requests_per_second > 0.0 {
+            interval = interval.max(
+                Duration::try_from_secs_f64(1.0 / requests_per_second)
+                    .unwrap_or(MAX_REQUEST_INTERVAL),
+            );
+        }
+
+        interval.min(MAX_REQUEST_INTERVAL)
+    }
+
+    // Waits until another request can be made to the host without going over its rate limit
+    fn wait_for_turn(&self, url: &Url) {
+        let interval: Duration = self.get_request_interval(url);
+        if interval.is_zero() {
+            return;
+        }
+
+        // Turns get reserved right away, which keeps requests made concurrently apart as well
+        let now: Instant = Instant::now();
+        let turn: Instant = {
+            let mut next_requests = self.next_requests.lock().unwrap();
+            let next_request: &mut Instant = next_requests
+                .entry(url.host_str().unwrap_or_default().to_string())
+                .or_insert(now);
+            let turn: Instant = (*next_request).max(now);
+            *next_request = turn + interval;
+
+            turn
+        };
+
+        self.sleep(turn - now);
+    }
+
+    // Waits for given amount of time, unless the capture gets aborted in the meantime
+    fn sleep(&self, duration: Duration) {
+        let until: Instant = Instant::now() + duration;
+        while self.abort_reason().is_none() {
+            let now: Instant = Instant::now();
+            if now >= until {
+                break;
+            }
+            thread::sleep((until - now).min(ABORT_CHECK_INTERVAL));
+        }
+    }
+
+    fn answer_auth_challenge(&self, url: &Url, www_authenticate: &[String]) -> Option<String> {
+        self.options
+            .auth_rules
+            .iter()
+            .filter(|auth_rule| auth_rule.matches_url(url))
+            .find_map(|auth_rule| match &auth_rule.credentials {
+                Credentials::Password(user, password) => {
+                    create_authorization(user, password, www_authenticate, url)
+                }
+                Credentials::Token(_) => None,
+            })
+    }
+
+    fn compose_request_headers(
+        &self,
+        parent_url: &Url,
+        original_url: &Url,
+        url: &Url,
+    ) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let Some(cookies) = &self.cookies {
+            for cookie in cookies {
+                if !cookie.is_expired() && cookie.matches_url(url.as_str()) {
+                    let cookie_header_value: String = cookie.name.clone() + "=" + &cookie.value;
+                    headers.insert(COOKIE, HeaderValue::from_str(&cookie_header_value).unwrap());
+                }
+            }
+        }
+        // Add referer header for page resource requests
+        if ["https", "http"].contains(&parent_url.scheme()) && parent_url != original_url {
+            headers.insert(
+                REFERER,
+                HeaderValue::from_str(get_referer_url(parent_url.clone()).as_str()).unwrap(),
+            );
+        }
+        // Add bearer token meant for this particular origin
+        for auth_rule in &self.options.auth_rules {
+            if let Credentials::Token(token) = &auth_rule.credentials {
+                if auth_rule.matches_url(url) {
+                    if let Ok(value) = HeaderValue::from_str(&format!("Bearer {}", token)) {
+                        headers.insert(AUTHORIZATION, value);
+                    }
+                }
+            }
+        }
+        // Add custom headers meant for this particular domain
+        for header_rule in &self.options.header_rules {
+            if header_rule.matches_url(url) {
+                if let (Ok(name), Ok(value)) = (
+                    HeaderName::from_bytes(header_rule.name.as_bytes()),
+                    HeaderValue::from_str(&header_rule.value),
+                ) {
+                    headers.insert(name, value);
+                }
+            }
+        }
+
+        headers
+    }
+
+    // Goes through the document without keeping any changes, retrieving assets it needs concurrently
+    pub fn prefetch_assets(&mut self, mut dry_run: impl FnMut(&mut Session)) {
+        self.prefetched_assets.clear();
+
+        // Nothing would be retrieved while offline anyway
+        if self.options.parallel_requests < 2 || self.options.offline {
+            return;
+        }
+
+        self.enter_phase(DocumentPhase::Prefetching);
+
+        let assets_count: usize = self.assets.len();
+        let asset_file_names: HashSet<String> = self.asset_file_names.clone();
+        let exchanges_count: usize = self.exchanges.len();
+        self.dry_run = true;
+
+        // Assets found within retrieved stylesheets and frames need another pass
+        loop {
+            dry_run(self);
+
+            self.assets.truncate(assets_count);
+            self.asset_file_names = asset_file_names.clone();
+            self.exchanges.truncate(exchanges_count);
+
+            let requests: Vec<(Url, Url)> = std::mem::take(&mut self.pending_requests);
+            if requests.is_empty() {
+                break;
+            }
+
+            let requested_assets = self.request_assets(requests);
+            self.prefetched_assets.extend(requested_assets);
+        }
+
+        self.dry_run = false;
+    }
+
+    fn record_exchange(&mut self, exchange: HttpExchange) {
+        // Only keep exchanges around if something is going to make use of them
+        if self.options.record_exchanges || self.options.output_format == MonolithOutputFormat::WARC
+        {
+            self.exchanges.push(exchange);
+        }
+    }
+
+    // Assets embedded into other assets (e.g. fonts within stylesheets) are referenced relative to them
+    pub fn within_asset<T>(&mut self, embed: impl FnOnce(&mut Session) -> T) -> T {
+        self.asset_depth += 1;
+        let result: T = embed(self);
+        self.asset_depth -= 1;
+
+        result
+    }
+
+    pub fn set_fetcher(&mut self, fetcher: impl Fetcher + 'static) {
+        self.fetcher = Box::new(fetcher);
+    }
+
+    pub fn set_archive(&mut self, archive: Archive) {
+        // Once set, assets are only ever retrieved from the archive
+        self.archive = Some(archive);
+    }
+
+    pub fn set_document_archive(&mut self, archive: Archive) {
+        // Assets of the current document are only retrieved from the archive it came from
+        self.document_archive = Some(archive);
+    }
+
+    pub fn set_document_directory(&mut self, directory: PathBuf) {
+        self.document_directory = directory;
+    }
+
+    // Links to pages within given domain lead to their local copies, new pages only get discovered if allowed
+    pub fn set_link_scope(&mut self, domain: Option<String>, follow_new_links: bool) {
+        self.follow_new_links = follow_new_links;
+        self.link_scope = domain;
+    }
+
+    // Forget assets and links of the previous document, while keeping cache, cookies, and such
+    pub fn start_document(&mut self) {
+        self.asset_errors.clear();
+        self.asset_file_names.clear();
+        self.assets.clear();
+        self.document_archive = None;
+        self.prefetched_assets.clear();
+        self.document_start = self.exchanges.len();
+        self.links.clear();
+        self.offline_misses.clear();
+        self.report_start = self.report.len();
+        self.retried_assets.clear();
+    }
+
+    pub fn take_links(&mut self) -> Vec<Url> {
+        std::mem::take(&mut self.links)
+    }
+
+    pub fn take_offline_misses(&mut self) -> Vec<Url> {
+        std::mem::take(&mut self.offline_misses)
+    }
+
+    // Records asset which was available locally as if it was retrieved over the network
+    fn record_local_exchange(
+        &mut self,
+        parent_url: &Url,
+        url: &Url,
+        data: &[u8],
+        media_type: &str,
+        charset: &str,
+    ) {
+        let headers: HeaderMap = self.compose_request_headers(parent_url, url, url);
+        let mut exchange: HttpExchange = self.start_exchange(url, &headers);
+        exchange.from_cache = true;
+        exchange.status = StatusCode::OK.as_u16();
+        exchange.response_headers = vec![(
+            CONTENT_TYPE.to_string(),
+            if charset.is_empty() {
+                media_type.to_string()
+            } else {
+                format!("{}; charset={}", media_type, charset)
+            },
+        )];
+        exchange.data = data.to_vec();
+        self.record_exchange(exchange);
+    }
+
+    fn start_exchange(&self, url: &Url, headers: &HeaderMap) -> HttpExchange {
+        let mut request_headers: Vec<(String, String)> = vec![];
+        if let Some(host) = url.host_str() {
+            let host_header_value: String = match url.port() {
+                Some(port) => format!("{}:{}", host, port),
+                None => host.to_string(),
+            };
+            request_headers.push((HOST.to_string(), host_header_value));
+        }
+        if let Some(user_agent) = &self.options.user_agent {
+            request_headers.push((USER_AGENT.to_string(), user_agent.clone()));
+        }
+        request_headers.extend(header_map_to_vec(headers));
+
+        HttpExchange {
+            date: Utc::now(),
+            url: url.clone(),
+            request_headers,
+            remote_address: None,
+            http_version: "".to_string(),
+            status: 0,
+            response_headers: vec![],
+            data: vec![],
+            wait: Duration::ZERO,
+            receive: Duration::ZERO,
+            from_cache: false,
+            error: None,
+        }
+    }
+
+    fn request_asset(&self, parent_url: &Url, url: &Url) -> RequestedAsset {
+        let mut exchanges: Vec<HttpExchange> = vec![];
+        let mut request_url: Url = url.clone();
+        let mut redirect_count: usize = 0;
+        let mut authorization: Option<HeaderValue> = None;
+        let mut authorized: bool = false;
+        let mut retries: usize = 0;
+
+        loop {
+            let mut headers: HeaderMap =
+                self.compose_request_headers(parent_url, url, &request_url);
+            if let Some(authorization) = &authorization {
+                headers.insert(AUTHORIZATION, authorization.clone());
+            }
+            authorized |= headers.contains_key(AUTHORIZATION);
+            // Ask the server to only send the asset if it's changed since it got cached
+            if redirect_count == 0 {
+                if let Some(cache) = &self.cache {
+                    for (name, value) in cache.get_validators(clean_url(url.clone()).as_str()) {
+                        if let (Ok(name), Ok(value)) = (
+                            HeaderName::from_bytes(name.as_bytes()),
+                            HeaderValue::from_str(&value),
+                        ) {
+                            headers.insert(name, value);
+                        }
+                    }
+                }
+            }
+            self.wait_for_turn(&request_url);
+
+            // Aborted captures don't make any more requests
+            if let Some(error) = self.abort_reason() {
+                return RequestedAsset {
+                    exchanges,
+                    response: Err(error.to_string()),
+                    retries,
+                };
+            }
+            let mut exchange: HttpExchange = self.start_exchange(&request_url, &headers);
+            let started: Instant = Instant::now();
+
+            let mut progress = |received: usize, total: Option<usize>| -> bool {
+                self.emit(SessionEvent::BytesReceived {
+                    url: url.clone(),
+                    received,
+                    total,
+                });
+
+                // Long downloads get cut short
+                self.abort_reason().is_none()
+            };
+
+            // Requests can't outlast the capture
+            let timeout: Option<Duration> = self
+                .deadline
+                .map(|deadline| deadline.saturating_duration_since(Instant::now()));
+
+            let fetched: Result<FetchedResponse, FetchError> = self
+                .fetcher
+                .fetch(&request_url, &headers, timeout, &mut progress)
+                .and_then(|response| match response.error {
+                    // Bodies cut short by running out of time got aborted just like the ones progress stopped
+                    Some(_) if self.abort_reason().is_some() => Err(FetchError {
+                        message: "aborted".to_string(),
+                        retryable: false,
+                    }),
+                    _ => Ok(response),
+                });
+
+            match fetched {
+                Ok(response) => {
+                    exchange.wait = started.elapsed().saturating_sub(response.receive);
+                    exchange.receive = response.receive;
+                    exchange.remote_address = response.remote_address.clone();
+                    exchange.http_version = response.http_version.clone();
+                    exchange.status = response.status.as_u16();
+                    exchange.response_headers = header_map_to_vec(&response.headers);
+
+                    let location: Option<Url> = response
+                        .headers
+                        .get(LOCATION)
+                        .and_then(|header| header.to_str().ok())
+                        .map(|location| resolve_url(&request_url, location));
+                    let status: StatusCode = response.status;
+                    let response_url: Url = response.url.clone();
+                    let content_type: String = response
+                        .headers
+                        .get(CONTENT_TYPE)
+                        .and_then(|header| header.to_str().ok())
+                        .unwrap_or("")
+                        .to_string();
+                    let get_header = |name: HeaderName| -> Option<String> {
+                        response
+                            .headers
+                            .get(name)
+                            .and_then(|header| header.to_str().ok())
+                            .map(|header| header.to_string())
+                    };
+                    let cache_headers: CacheHeaders = CacheHeaders {
+                        cache_control: get_header(CACHE_CONTROL),
+                        etag: get_header(ETAG),
+                        expires: get_header(EXPIRES),
+                        last_modified: get_header(LAST_MODIFIED),
+                    };
+                    let retry_after: Option<String> = get_header(RETRY_AFTER);
+                    let www_authenticate: Vec<String> = response
+                        .headers
+                        .get_all(WWW_AUTHENTICATE)
+                        .iter()
+                        .filter_map(|header| header.to_str().ok())
+                        .map(|header| header.to_string())
+                        .collect();
+
+                    let (data, error): (Vec<u8>, Option<String>) = (response.data, response.error);
+                    exchange.data = data.clone();
+                    exchanges.push(exchange);
+
+                    // Transient failures get retried after a while
+                    if retries < self.options.retries
+                        && (is_retryable_status(status) || error.is_some())
+                    {
+                        // Only these statuses come with meaningful Retry-After headers
+                        let retry_after: Option<&str> = retry_after.as_deref().filter(|_| {
+                            status == StatusCode::TOO_MANY_REQUESTS
+                                || status == StatusCode::SERVICE_UNAVAILABLE
+                        });
+                        if let Some(delay) =
+                            get_retry_delay(retry_after, retries, self.options.retry_delay)
+                        {
+                            self.sleep(delay);
+                            retries += 1;
+                            continue;
+                        }
+                    }
+
+                    // Answer authentication challenge once, using credentials meant for this origin
+                    if status == StatusCode::UNAUTHORIZED && authorization.is_none() {
+                        authorization = self
+                            .answer_auth_challenge(&request_url, &www_authenticate)
+                            .and_then(|answer| HeaderValue::from_str(&answer).ok());
+                        if authorization.is_some() {
+                            continue;
+                        }
+                    }
+
+                    match location {
+                        Some(location)
+                            if status.is_redirection() && redirect_count < MAX_REDIRECTS =>
+                        {
+                            request_url = location;
+                            redirect_count += 1;
+                            authorization = None;
+                        }
+                        _ => {
+                            return RequestedAsset {
+                                exchanges,
+                                response: Ok(RequestedResponse {
+                                    status,
+                                    url: response_url,
+                                    content_type,
+                                    cache_headers,
+                                    data,
+                                    error,
+                                    authorized,
+                                }),
+                                retries,
+                            };
+                        }
+                    }
+                }
+                Err(error) => {
+                    exchange.wait = started.elapsed();
+                    exchange.error = Some(error.message.clone());
+                    exchanges.push(exchange);
+
+                    if retries < self.options.retries && error.retryable {
+                        if let Some(delay) =
+                            get_retry_delay(None, retries, self.options.retry_delay)
+                        {
+                            self.sleep(delay);
+                            retries += 1;
+                            continue;
+                        }
+                    }
+
+                    return RequestedAsset {
+                        exchanges,
+                        response: Err(error.message),
+                        retries,
+                    };
+                }
+            }
+        }
+    }
+
+    fn request_assets(&self, requests: Vec<(Url, Url)>) -> Vec<(RequestKey, RequestedAsset)> {
+        let workers: usize = self.options.parallel_requests.min(requests.len());
+        // Requests which are yet to be made, along with how many are in progress for each host
+        let queue = Mutex::new((VecDeque::from(requests), HashMap::<String, usize>::new()));
+        let queue_changed: Condvar = Condvar::new();
+        let results: Mutex<Vec<(RequestKey, RequestedAsset)>> = Mutex::new(vec![]);
+
+        thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| loop {
+                    // Requests which are left get dropped once the capture is aborted
+                    if self.abort_reason().is_some() {
+                        return;
+                    }
+
+                    // Pick the first request whose host isn't busy enough yet
+                    let mut guard = queue.lock().unwrap();
+                    let (parent_url, url, host) = loop {
+                        let (requests, active_requests) = &mut *guard;
+                        if requests.is_empty() {
+                            return;
+                        }
+
+                        let available: Option<usize> = requests.iter().position(|(_, url)| {
+                            active_requests
+                                .get(url.host_str().unwrap_or_default())
+                                .copied()
+                                .unwrap_or(0)
+                                < self.get_max_connections(url)
+                        });
+                        if let Some(index) = available {
+                            let (parent_url, url) = requests.remove(index).unwrap();
+                            let host: String = url.host_str().unwrap_or_default().to_string();
+                            *active_requests.entry(host.clone()).or_insert(0) += 1;
+
+                            break (parent_url, url, host);
+                        }
+
+                        guard = queue_changed.wait(guard).unwrap();
+                    };
+                    drop(guard);
+
+                    let requested_asset: RequestedAsset = self.request_asset(&parent_url, &url);
+                    results
+                        .lock()
+                        .unwrap()
+                        .push((clean_url(url).to_string(), requested_asset));
+
+                    if let Some(count) = queue.lock().unwrap().1.get_mut(&host) {
+                        *count -= 1;
+                    }
+                    queue_changed.notify_all();
+                });
+            }
+        });
+
+        results.into_inner().unwrap()
+    }
+
     pub fn retrieve_asset(
         &mut self,
         parent_url: &Url,
         url: &Url,
-    ) -> Result<(Vec<u8>, Url, String, String), reqwest::Error> {
-        let cache_key: String = clean_url(url.clone()).as_str().to_string();
+    ) -> Result<(Vec<u8>, Url, String, String), MonolithError> {
+        // Outcomes of the dry run are either going to be repeated or resolved later on
+        let announce: bool = !self.dry_run && url.scheme() != "data";
+        if announce {
+            self.emit(SessionEvent::AssetStarted { url: url.clone() });
+        }
+
+        self.from_cache = false;
+        let result = self.retrieve(parent_url, url);
+
+        if !self.dry_run {
+            let retrieval: Retrieval = match &result {
+                Ok((data, final_url, media_type, _charset)) => Retrieval {
+                    final_url: Some(final_url.clone()),
+                    media_type: media_type.clone(),
+                    size: data.len(),
+                    from_cache: self.from_cache,
+                    error: None,
+                },
+                Err(error) => {
+                    self.asset_errors.push(error.clone());
+                    if announce {
+                        self.emit(SessionEvent::AssetFailed {
+                            url: url.clone(),
+                            error: error.clone(),
+                        });
+                    }
 
-        if !self.urls.contains(&url.as_str().to_string()) {
-            self.urls.push(url.as_str().to_string());
+                    Retrieval {
+                        final_url: None,
+                        media_type: "".to_string(),
+                        size: 0,
+                        from_cache: false,
+                        error: Some(error.clone()),
+                    }
+                }
+            };
+            self.retrievals.insert(url.to_string(), retrieval);
         }
 
+        result
+    }
+
+    fn retrieve(
+        &mut self,
+        parent_url: &Url,
+        url: &Url,
+    ) -> Result<(Vec<u8>, Url, String, String), MonolithError> {
+        let cache_key: String = clean_url(url.clone()).as_str().to_string();
+
         if url.scheme() == "data" {
             let (media_type, charset, data) = parse_data_url(url);
             Ok((data, url.clone(), media_type, charset))
+        } else if let Some(archive) = self.document_archive.as_ref().or(self.archive.as_ref()) {
+            match archive.get(url) {
+                Some(ArchivedResource {
+                    url: final_url,
+                    media_type,
+                    charset,
+                    data,
+                }) => {
+                    if !self.dry_run {
+                        self.emit(SessionEvent::AssetFromSource {
+                            url: url.clone(),
+                            final_url: final_url.clone(),
+                            size: data.len(),
+                            source: "archive".to_string(),
+                        });
+                    }
+
+                    Ok((
+                        data.clone(),
+                        final_url.clone(),
+                        media_type.clone(),
+                        charset.clone(),
+                    ))
+                }
+                None => Err(MonolithError::NotInArchive { url: url.clone() }),
+            }
         } else if url.scheme() == "file" {
             // Check if parent_url is also a file:// URL (if not, then we don't embed the asset)
             if parent_url.scheme() != "file" {
-                if !self.options.silent {
-                    print_error_message(&format!("{} (security error)", &cache_key));
-                }
-
-                // Provoke error
-                self.client.get("").send()?;
+                return Err(MonolithError::SecurityError { url: url.clone() });
             }
 
-            let path_buf: PathBuf = url.to_file_path().unwrap().clone();
+            let path_buf: PathBuf = url.to_file_path().unwrap_or_default();
             let path: &Path = path_buf.as_path();
             if path.exists() {
                 if path.is_dir() {
-                    if !self.options.silent {
-                        print_error_message(&format!("{} (is a directory)", &cache_key));
-                    }
-
-                    // Provoke error
-                    Err(self.client.get("").send().unwrap_err())
+                    Err(MonolithError::IsDirectory { url: url.clone() })
                 } else {
-                    if !self.options.silent {
-                        print_info_message(&cache_key.to_string());
-                    }
+                    let file_blob: Vec<u8> = match fs::read(path) {
+                        Ok(file_blob) => file_blob,
+                        Err(error) => {
+                            return Err(MonolithError::FileUnreadable {
+                                url: url.clone(),
+                                cause: error.to_string(),
+                            });
+                        }
+                    };
 
-                    let file_blob: Vec<u8> = fs::read(path).expect("unable to read file");
+                    if !self.dry_run {
+                        self.emit(SessionEvent::AssetFinished {
+                            url: url.clone(),
+                            final_url: url.clone(),
+                            size: file_blob.len(),
+                        });
+                    }
 
                     Ok((
                         file_blob.clone(),
@@ -105,31 +1144,35 @@ impl Session {
                     ))
                 }
             } else {
-                if !self.options.silent {
-                    print_error_message(&format!("{} (file not found)", &url));
-                }
-
-                // Provoke error
-                Err(self.client.get("").send().unwrap_err())
-            }
-        } else if self.cache.is_some() && self.cache.as_ref().unwrap().contains_key(&cache_key) {
-            // URL is in cache, we get and return it
-            if !self.options.silent {
-                print_info_message(&format!("{} (from cache)", &cache_key));
-            }
-
-            Ok((
-                self.cache
-                    .as_ref()
-                    .unwrap()
-                    .get(&cache_key)
-                    .unwrap()
-                    .0
-                    .to_vec(),
-                url.clone(),
-                self.cache.as_ref().unwrap().get(&cache_key).unwrap().1,
-                self.cache.as_ref().unwrap().get(&cache_key).unwrap().2,
-            ))
+                Err(MonolithError::FileNotFound { url: url.clone() })
+            }
+        } else if self.cache.as_ref().is_some_and(|cache| {
+            cache.is_fresh(&cache_key) || (self.options.offline && cache.contains_key(&cache_key))
+        }) {
+            // URL is in cache (and doesn't need to be revalidated, or can't be), we get and return it
+            let (data, media_type, charset) = self.cache.as_ref().unwrap().get(&cache_key).unwrap();
+            self.from_cache = true;
+
+            self.record_local_exchange(parent_url, url, &data, &media_type, &charset);
+
+            let final_url: Url = self
+                .cache
+                .as_ref()
+                .unwrap()
+                .get_url(&cache_key)
+                .and_then(|final_url| Url::parse(&final_url).ok())
+                .unwrap_or(url.clone());
+
+            if !self.dry_run {
+                self.emit(SessionEvent::AssetFromCache {
+                    url: url.clone(),
+                    final_url: final_url.clone(),
+                    size: data.len(),
+                    revalidated: false,
+                });
+            }
+
+            Ok((data, final_url, media_type, charset))
         } else {
             if let Some(domains) = &self.options.domains {
                 let domain_matches = domains
@@ -138,94 +1181,300 @@ impl Session {
                 if (self.options.blacklist_domains && domain_matches)
                     || (!self.options.blacklist_domains && !domain_matches)
                 {
-                    return Err(self.client.get("").send().unwrap_err());
+                    let mut exchange: HttpExchange = self.start_exchange(url, &HeaderMap::new());
+                    exchange.error = Some("blocked by domain rules".to_string());
+                    self.record_exchange(exchange);
+
+                    return Err(MonolithError::BlockedDomain { url: url.clone() });
                 }
             }
 
-            // URL not in cache, we retrieve the file
-            let mut headers = HeaderMap::new();
-            if self.cookies.is_some() && !self.cookies.as_ref().unwrap().is_empty() {
-                for cookie in self.cookies.as_ref().unwrap() {
-                    if !cookie.is_expired() && cookie.matches_url(url.as_str()) {
-                        let cookie_header_value: String = cookie.name.clone() + "=" + &cookie.value;
-                        headers
-                            .insert(COOKIE, HeaderValue::from_str(&cookie_header_value).unwrap());
-                    }
+            // Configured sources get asked before going to the network
+            if let Some((source_name, asset)) = self.sources.iter().find_map(|source| {
+                source
+                    .get(url)
+                    .map(|asset| (source.name().to_string(), asset))
+            }) {
+                if !self.dry_run {
+                    self.emit(SessionEvent::AssetFromSource {
+                        url: url.clone(),
+                        final_url: asset.url.clone(),
+                        size: asset.data.len(),
+                        source: source_name,
+                    });
                 }
-            }
-            // Add referer header for page resource requests
-            if ["https", "http"].contains(&parent_url.scheme()) && parent_url != url {
-                headers.insert(
-                    REFERER,
-                    HeaderValue::from_str(get_referer_url(parent_url.clone()).as_str()).unwrap(),
+
+                self.record_local_exchange(
+                    parent_url,
+                    url,
+                    &asset.data,
+                    &asset.media_type,
+                    &asset.charset,
                 );
+
+                return Ok((asset.data, asset.url, asset.media_type, asset.charset));
             }
-            match self.client.get(url.as_str()).headers(headers).send() {
-                Ok(response) => {
-                    if !self.options.ignore_errors && response.status() != reqwest::StatusCode::OK {
-                        if !self.options.silent {
-                            print_error_message(&format!("{} ({})", &cache_key, response.status()));
-                        }
 
-                        // Provoke error
-                        return Err(self.client.get("").send().unwrap_err());
+            // Nothing can be retrieved over the network while offline
+            if self.options.offline {
+                let mut exchange: HttpExchange = self.start_exchange(url, &HeaderMap::new());
+                exchange.error = Some("not available offline".to_string());
+                self.record_exchange(exchange);
+
+                return match self.options.offline_policy {
+                    MonolithOfflinePolicy::Placeholder => {
+                        let file_name: &str = url.path().rsplit('/').next().unwrap_or_default();
+
+                        Ok((
+                            vec![],
+                            url.clone(),
+                            detect_media_type_by_file_name(file_name),
+                            "".to_string(),
+                        ))
+                    }
+                    MonolithOfflinePolicy::Fail => {
+                        self.offline_misses.push(url.clone());
+
+                        Err(MonolithError::Offline { url: url.clone() })
                     }
+                    MonolithOfflinePolicy::Keep => Err(MonolithError::Offline { url: url.clone() }),
+                };
+            }
 
-                    let response_url: Url = response.url().clone();
+            // URL not in cache, we retrieve the file (unless that's already been done ahead of time)
+            let requested_asset: RequestedAsset = if self.dry_run {
+                match self.prefetched_assets.get(&cache_key) {
+                    Some(requested_asset) => requested_asset.clone(),
+                    None => {
+                        if let Some(error) = self.abort_reason() {
+                            return Err(error);
+                        }
 
-                    if !self.options.silent {
-                        if url.as_str() == response_url.as_str() {
-                            print_info_message(&cache_key.to_string());
-                        } else {
-                            print_info_message(&format!("{} -> {}", &cache_key, &response_url));
+                        // Assets referenced from multiple places only get requested once
+                        if !self.pending_requests.iter().any(|(_, pending_url)| {
+                            clean_url(pending_url.clone()).as_str() == cache_key
+                        }) {
+                            self.pending_requests
+                                .push((parent_url.clone(), url.clone()));
                         }
+
+                        return Err(MonolithError::Pending { url: url.clone() });
                     }
+                }
+            } else {
+                match self.prefetched_assets.remove(&cache_key) {
+                    Some(requested_asset) => requested_asset,
+                    None => self.request_asset(parent_url, url),
+                }
+            };
 
-                    // Attempt to obtain media type and charset by reading Content-Type header
-                    let content_type: &str = response
-                        .headers()
-                        .get(CONTENT_TYPE)
-                        .and_then(|header| header.to_str().ok())
-                        .unwrap_or("");
+            // Exchanges get recorded in the order assets are used, regardless of when they were retrieved
+            for exchange in requested_asset.exchanges {
+                self.record_exchange(exchange);
+            }
 
-                    let (media_type, charset, _is_base64) = parse_content_type(content_type);
+            if requested_asset.retries > 0 && !self.dry_run {
+                self.emit(SessionEvent::AssetRetried {
+                    url: url.clone(),
+                    retries: requested_asset.retries,
+                });
 
-                    // Convert response into a byte array
-                    let mut data: Vec<u8> = vec![];
-                    match response.bytes() {
-                        Ok(b) => {
-                            data = b.to_vec();
+                self.retried_assets
+                    .push((url.clone(), requested_asset.retries));
+            }
+
+            match requested_asset.response {
+                Ok(response) => {
+                    // Cached asset is still good to use
+                    if response.status == StatusCode::NOT_MODIFIED
+                        && self
+                            .cache
+                            .as_ref()
+                            .is_some_and(|cache| cache.contains_key(&cache_key))
+                    {
+                        let cache: &mut Cache = self.cache.as_mut().unwrap();
+                        let (data, media_type, charset) = cache.get(&cache_key).unwrap();
+                        let final_url: Url = cache
+                            .get_url(&cache_key)
+                            .and_then(|final_url| Url::parse(&final_url).ok())
+                            .unwrap_or(url.clone());
+                        if !self.dry_run {
+                            cache.revalidate(&cache_key, response.cache_headers);
+                            self.emit(SessionEvent::AssetFromCache {
+                                url: url.clone(),
+                                final_url: final_url.clone(),
+                                size: data.len(),
+                                revalidated: true,
+                            });
                         }
-                        Err(error) => {
-                            if !self.options.silent {
-                                print_error_message(&format!("{}", error));
-                            }
+                        self.from_cache = true;
+
+                        // Response to revalidation has no body, the cached one gets recorded along with it
+                        self.record_local_exchange(parent_url, url, &data, &media_type, &charset);
+
+                        return Ok((data, final_url, media_type, charset));
+                    }
+
+                    if !self.options.ignore_errors && response.status != StatusCode::OK {
+                        return Err(MonolithError::HttpStatus {
+                            url: url.clone(),
+                            status: response.status.as_u16(),
+                        });
+                    }
+
+                    if !self.dry_run {
+                        self.emit(SessionEvent::AssetFinished {
+                            url: url.clone(),
+                            final_url: response.url.clone(),
+                            size: response.data.len(),
+                        });
+
+                        if let Some(error) = &response.error {
+                            self.emit(SessionEvent::AssetIncomplete {
+                                url: url.clone(),
+                                cause: error.clone(),
+                            });
                         }
                     }
 
-                    // Add retrieved resource to cache
-                    if self.cache.is_some() {
-                        let new_cache_key: String = clean_url(response_url.clone()).to_string();
+                    // Attempt to obtain media type and charset by reading Content-Type header
+                    let (media_type, charset, _is_base64) =
+                        parse_content_type(&response.content_type);
+
+                    // Add retrieved resource to cache (which the dry run should leave untouched),
+                    // private assets must not be served to future runs which may not have credentials
+                    if self
+                        .cache
+                        .as_ref()
+                        .is_some_and(|cache| !(response.authorized && cache.is_persistent()))
+                        && !self.dry_run
+                    {
+                        let cache: &mut Cache = self.cache.as_mut().unwrap();
+                        let new_cache_key: String = clean_url(response.url.clone()).to_string();
 
-                        self.cache.as_mut().unwrap().set(
+                        cache.set_response(
                             &new_cache_key,
-                            &data,
+                            &response.data,
                             media_type.clone(),
                             charset.clone(),
+                            None,
+                            response.cache_headers.clone(),
                         );
+
+                        // Redirected assets can also be found by their original URLs
+                        if new_cache_key != cache_key {
+                            cache.set_response(
+                                &cache_key,
+                                &response.data,
+                                media_type.clone(),
+                                charset.clone(),
+                                Some(response.url.to_string()),
+                                response.cache_headers,
+                            );
+                        }
                     }
 
                     // Return
-                    Ok((data, response_url, media_type, charset))
+                    Ok((response.data, response.url, media_type, charset))
                 }
-                Err(error) => {
-                    if !self.options.silent {
-                        print_error_message(&format!("{} ({})", &cache_key, error));
-                    }
+                // Requests cut short by aborting the capture aren't network errors
+                Err(error) => Err(self.abort_reason().unwrap_or(MonolithError::Network {
+                    url: url.clone(),
+                    cause: error,
+                })),
+            }
+        }
+    }
+}
 
-                    Err(self.client.get("").send().unwrap_err())
-                }
+pub fn is_valid_rate(requests_per_second: &f64) -> bool {
+    requests_per_second.is_finite() && *requests_per_second >= 0.0
+}
+
+fn get_deadline(time_limit: u64) -> Option<Instant> {
+    if time_limit > 0 {
+        Some(Instant::now() + Duration::from_secs(time_limit))
+    } else {
+        None
+    }
+}
+
+fn get_retry_delay(
+    retry_after: Option<&str>,
+    retries: usize,
+    retry_delay: u64,
+) -> Option<Duration> {
+    // Servers know better when they're going to be available again
+    if let Some(retry_after) = retry_after {
+        return parse_retry_after(retry_after).filter(|delay| *delay <= MAX_RETRY_AFTER);
+    }
+
+    // Exponential backoff, with jitter which prevents parallel requests from being retried all at once
+    let delay: Duration = Duration::from_millis(retry_delay)
+        .saturating_mul(2u32.saturating_pow(retries as u32))
+        .min(MAX_RETRY_DELAY);
+    let jitter: f64 = Utc::now().timestamp_subsec_nanos() as f64 / 1_000_000_000.0;
+
+    Some(delay.mul_f64(0.5 + jitter / 2.0))
+}
+
+fn header_map_to_vec(header_map: &HeaderMap) -> Vec<(String, String)> {
+    header_map
+        .iter()
+        .map(|(name, value)| {
+            (
+                name.to_string(),
+                String::from_utf8_lossy(value.as_bytes()).to_string(),
+            )
+        })
+        .collect()
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    [
+        StatusCode::REQUEST_TIMEOUT,
+        StatusCode::TOO_MANY_REQUESTS,
+        StatusCode::INTERNAL_SERVER_ERROR,
+        StatusCode::BAD_GATEWAY,
+        StatusCode::SERVICE_UNAVAILABLE,
+        StatusCode::GATEWAY_TIMEOUT,
+    ]
+    .contains(&status)
+}
+
+pub fn parse_rate_limit_rule(input: &str) -> Option<RateLimitRule> {
+    let mut parts = input.split_whitespace();
+    let mut rate_limit_rule: RateLimitRule = RateLimitRule {
+        domain: parts.next()?.to_string(),
+        ..Default::default()
+    };
+
+    // Domain is followed by limits, e.g. "example.com rate=0.5 delay=1000 connections=1"
+    for part in parts {
+        match part.split_once('=')? {
+            ("rate", value) => {
+                rate_limit_rule.requests_per_second =
+                    Some(value.parse::<f64>().ok().filter(is_valid_rate)?)
+            }
+            ("delay", value) => rate_limit_rule.delay = Some(value.parse::<u64>().ok()?),
+            ("connections", value) => {
+                rate_limit_rule.max_connections = Some(value.parse::<usize>().ok()?)
             }
+            _ => return None,
+        }
+    }
+
+    Some(rate_limit_rule)
+}
+
+pub fn parse_retry_after(retry_after: &str) -> Option<Duration> {
+    // Either number of seconds, or date
+    match retry_after.trim().parse::<u64>() {
+        Ok(seconds) => Some(Duration::from_secs(seconds)),
+        Err(_) => {
+            let date: DateTime<Utc> = parse_http_date(retry_after)?;
+
+            Some((date - Utc::now()).to_std().unwrap_or_default())
         }
     }
 }
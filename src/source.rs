@@ -0,0 +1,333 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use reqwest::blocking::Client;
+use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
+use reqwest::redirect::Policy;
+use reqwest::{Proxy, StatusCode};
+
+use crate::archive::{Archive, ArchivedResource};
+use crate::core::{
+    detect_media_type, detect_media_type_by_file_name, MonolithError, MonolithOptions,
+};
+use crate::url::{clean_url, domain_is_within_domain, parse_proxy_url, Url};
+
+// Single HTTP response, as it was received
+pub struct FetchedResponse {
+    pub status: StatusCode,
+    pub url: Url,
+    pub headers: HeaderMap,
+    pub http_version: String,
+    pub remote_address: Option<String>,
+    pub data: Vec<u8>,
+    pub error: Option<String>, // Reason why the body couldn't be read
+    pub receive: Duration,     // Time spent reading the body
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FetchError {
+    pub message: String,
+    pub retryable: bool, // Trying again later may work
+}
+
+// Asset which is available without going through the network
+#[derive(Clone, Debug, PartialEq)]
+pub struct SourcedAsset {
+    pub url: Url, // Final location of the asset
+    pub media_type: String,
+    pub charset: String,
+    pub data: Vec<u8>,
+}
+
+const READ_CHUNK_SIZE: usize = 1024 * 64; // How much of the body gets read before reporting progress (in bytes)
+
+// Makes single HTTP requests, while the session takes care of redirects, authentication, retries and caching
+pub trait Fetcher: Send + Sync {
+    // Progress gets reported as the body is being read (bytes received so far, expected total if known),
+    // reading has to stop as soon as it returns false; the request may not take longer than timeout, if any
+    fn fetch(
+        &self,
+        url: &Url,
+        headers: &HeaderMap,
+        timeout: Option<Duration>,
+        progress: &mut dyn FnMut(usize, Option<usize>) -> bool,
+    ) -> Result<FetchedResponse, FetchError>;
+}
+
+// Provides assets in place of the network, sources get asked one after another until one has the asset
+pub trait AssetSource: Send + Sync {
+    fn name(&self) -> &str; // Shown next to URLs of assets which came from this source
+    fn get(&self, url: &Url) -> Option<SourcedAsset>;
+}
+
+// Default fetcher, which makes requests using reqwest (through proxies, if any)
+pub struct ClientFetcher {
+    client: Client,
+    proxy_clients: Vec<(String, Client)>, // Used for domains which have their own proxy rules
+    timeout: Duration,                    // How long requests may take unless told otherwise
+}
+
+impl ClientFetcher {
+    pub fn new(options: &MonolithOptions) -> Result<ClientFetcher, MonolithError> {
+        let mut header_map = HeaderMap::new();
+        if let Some(user_agent) = &options.user_agent {
+            header_map.insert(
+                USER_AGENT,
+                HeaderValue::from_str(user_agent).map_err(|_| {
+                    MonolithError::new(&format!("invalid User-Agent \"{}\"", user_agent))
+                })?,
+            );
+        }
+        let timeout: Duration = Duration::from_secs(if options.timeout > 0 {
+            options.timeout
+        } else {
+            // We have to specify something that eventually makes the program fail
+            // (prevent it from hanging forever)
+            600 // 10 minutes in seconds
+        });
+        let build_client = |proxy: Option<&str>| -> Result<Client, MonolithError> {
+            let mut client_builder = Client::builder()
+                .timeout(timeout)
+                .danger_accept_invalid_certs(options.insecure)
+                .default_headers(header_map.clone())
+                // Redirects are followed manually, in order to be able to record every hop
+                .redirect(Policy::none());
+            if let Some(proxy) = proxy {
+                let invalid_proxy = || MonolithError::InvalidProxy {
+                    proxy: proxy.to_string(),
+                };
+                parse_proxy_url(proxy).ok_or_else(invalid_proxy)?;
+                let mut proxy = Proxy::all(proxy).map_err(|_| invalid_proxy())?;
+                if let Some((user, password)) = options
+                    .proxy_user
+                    .as_deref()
+                    .map(|credentials| credentials.split_once(':').unwrap_or((credentials, "")))
+                {
+                    proxy = proxy.basic_auth(user, password);
+                }
+                client_builder = client_builder.proxy(proxy);
+            } else if options.proxy.is_some()
+                || !options.proxy_rules.is_empty()
+                || !options.no_proxy.is_empty()
+            {
+                // Explicit proxy settings take place of the ones set via environment variables
+                client_builder = client_builder.no_proxy();
+            }
+            client_builder.build().map_err(|error| {
+                MonolithError::new(&format!("could not initialize HTTP client: {}", error))
+            })
+        };
+        let client = build_client(options.proxy.as_deref())?;
+
+        // Proxy rules for specific domains take precedence over the list of domains to not proxy
+        let mut proxy_clients: Vec<(String, Client)> = options
+            .proxy_rules
+            .iter()
+            .map(|(domain, proxy)| Ok((domain.clone(), build_client(Some(proxy))?)))
+            .collect::<Result<_, MonolithError>>()?;
+        if !options.no_proxy.is_empty() {
+            let direct_client = build_client(None)?;
+            for domain in &options.no_proxy {
+                proxy_clients.push((domain.clone(), direct_client.clone()));
+            }
+        }
+
+        Ok(ClientFetcher {
+            client,
+            proxy_clients,
+            timeout,
+        })
+    }
+
+    fn get_client(&self, url: &Url) -> &Client {
+        self.proxy_clients
+            .iter()
+            .find(|(domain, _)| domain_is_within_domain(url.host_str().unwrap_or_default(), domain))
+            .map(|(_, client)| client)
+            .unwrap_or(&self.client)
+    }
+}
+
+impl Fetcher for ClientFetcher {
+    fn fetch(
+        &self,
+        url: &Url,
+        headers: &HeaderMap,
+        timeout: Option<Duration>,
+        progress: &mut dyn FnMut(usize, Option<usize>) -> bool,
+    ) -> Result<FetchedResponse, FetchError> {
+        match self
+            .get_client(url)
+            .get(url.as_str())
+            .headers(headers.clone())
+            .timeout(timeout.map_or(self.timeout, |timeout| timeout.min(self.timeout)))
+            .send()
+        {
+            Ok(mut response) => {
+                let status: StatusCode = response.status();
+                let response_url: Url = response.url().clone();
+                let response_headers: HeaderMap = response.headers().clone();
+                let http_version: String = format!("{:?}", response.version());
+                let remote_address: Option<String> =
+                    response.remote_addr().map(|a| a.ip().to_string());
+
+                let total: Option<usize> = response.content_length().map(|length| length as usize);
+
+                // Read response body into a byte array, chunk by chunk
+                let receiving: Instant = Instant::now();
+                let mut data: Vec<u8> = vec![];
+                let mut error: Option<String> = None;
+                let mut chunk: Vec<u8> = vec![0; READ_CHUNK_SIZE];
+                loop {
+                    match response.read(&mut chunk) {
+                        Ok(0) => break,
+                        Ok(length) => {
+                            data.extend_from_slice(&chunk[..length]);
+                            if !progress(data.len(), total) {
+                                return Err(FetchError {
+                                    message: "aborted".to_string(),
+                                    retryable: false,
+                                });
+                            }
+                        }
+                        Err(read_error) if read_error.kind() == std::io::ErrorKind::Interrupted => {
+                        }
+                        Err(read_error) => {
+                            data.clear();
+                            error = Some(read_error.to_string());
+                            break;
+                        }
+                    }
+                }
+
+                Ok(FetchedResponse {
+                    status,
+                    url: response_url,
+                    headers: response_headers,
+                    http_version,
+                    remote_address,
+                    data,
+                    error,
+                    receive: receiving.elapsed(),
+                })
+            }
+            Err(error) => Err(FetchError {
+                message: error.to_string(),
+                // Requests which couldn't even be built would fail the same way again
+                retryable: !error.is_builder()
+                    && (error.is_connect() || error.is_timeout() || error.is_request()),
+            }),
+        }
+    }
+}
+
+// Collection of assets kept in memory (e.g. fixtures)
+#[derive(Default)]
+pub struct MemorySource {
+    assets: HashMap<String, SourcedAsset>,
+}
+
+impl MemorySource {
+    pub fn new() -> MemorySource {
+        MemorySource::default()
+    }
+
+    pub fn insert(&mut self, url: &Url, data: Vec<u8>, media_type: &str, charset: &str) {
+        self.assets.insert(
+            clean_url(url.clone()).to_string(),
+            SourcedAsset {
+                url: url.clone(),
+                media_type: media_type.to_string(),
+                charset: charset.to_string(),
+                data,
+            },
+        );
+    }
+}
+
+impl AssetSource for MemorySource {
+    fn name(&self) -> &str {
+        "memory"
+    }
+
+    fn get(&self, url: &Url) -> Option<SourcedAsset> {
+        self.assets.get(clean_url(url.clone()).as_str()).cloned()
+    }
+}
+
+// Local mirror, which has assets stored as files named after their URLs (host/path/to/file)
+pub struct DirectorySource {
+    directory: PathBuf,
+}
+
+impl DirectorySource {
+    pub fn new(directory: &str) -> DirectorySource {
+        DirectorySource {
+            directory: PathBuf::from(directory),
+        }
+    }
+}
+
+impl AssetSource for DirectorySource {
+    fn name(&self) -> &str {
+        "mirror"
+    }
+
+    fn get(&self, url: &Url) -> Option<SourcedAsset> {
+        let mut path: PathBuf = self.directory.join(url.host_str()?);
+        for segment in url.path_segments()? {
+            // Never look outside of the mirror
+            if segment == ".." {
+                return None;
+            }
+            if !segment.is_empty() {
+                path.push(segment);
+            }
+        }
+        // Directories are represented by their index files
+        if url.path().ends_with('/') || path.is_dir() {
+            path.push("index.html");
+        }
+
+        let data: Vec<u8> = fs::read(&path).ok()?;
+        let mut media_type: String = detect_media_type(&data, url);
+        if media_type.is_empty() {
+            // URLs of directories don't have file names to guess media type from
+            media_type = detect_media_type_by_file_name(
+                &path.file_name().unwrap_or_default().to_string_lossy(),
+            );
+        }
+
+        Some(SourcedAsset {
+            url: url.clone(),
+            media_type,
+            charset: "".to_string(),
+            data,
+        })
+    }
+}
+
+impl AssetSource for Archive {
+    fn name(&self) -> &str {
+        "archive"
+    }
+
+    fn get(&self, url: &Url) -> Option<SourcedAsset> {
+        let ArchivedResource {
+            url,
+            media_type,
+            charset,
+            data,
+        } = Archive::get(self, url)?;
+
+        Some(SourcedAsset {
+            url: url.clone(),
+            media_type: media_type.clone(),
+            charset: charset.clone(),
+            data: data.clone(),
+        })
+    }
+}
@@ -1,5 +1,7 @@
+use std::path::{Component, Path, PathBuf};
+
 use base64::{prelude::BASE64_STANDARD, Engine};
-use percent_encoding::percent_decode_str;
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, CONTROLS};
 pub use url::Url;
 
 use crate::core::{detect_media_type, parse_content_type};
@@ -7,6 +9,22 @@ use crate::core::{detect_media_type, parse_content_type};
 pub const EMPTY_IMAGE_DATA_URL: &str = "data:image/png,\
 %89PNG%0D%0A%1A%0A%00%00%00%0DIHDR%00%00%00%0D%00%00%00%0D%08%04%00%00%00%D8%E2%2C%F7%00%00%00%11IDATx%DAcd%C0%09%18G%A5%28%96%02%00%0A%F8%00%0E%CB%8A%EB%16%00%00%00%00IEND%AEB%60%82";
 
+// Characters which can't be part of a path segment as they are
+const PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'/')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'\\')
+    .add(b'`')
+    .add(b'{')
+    .add(b'|')
+    .add(b'}');
+
 pub fn clean_url(url: Url) -> Url {
     let mut url = url.clone();
 
@@ -46,6 +64,54 @@ pub fn create_data_url(media_type: &str, charset: &str, data: &[u8], final_asset
     data_url
 }
 
+pub fn create_relative_url(path: &Path, base_directory: &Path) -> String {
+    // Relative paths already start from the base directory
+    let relative_path: PathBuf = if path.is_absolute() {
+        let base_directory: PathBuf = normalize_path(
+            &std::path::absolute(if base_directory.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                base_directory
+            })
+            .unwrap_or_default(),
+        );
+        let path: PathBuf = normalize_path(path);
+        let common: usize = path
+            .components()
+            .zip(base_directory.components())
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        // Paths on different drives can't be relative to each other
+        if common == 0 {
+            return Url::from_file_path(&path)
+                .map(|url| url.to_string())
+                .unwrap_or_default();
+        }
+
+        base_directory
+            .components()
+            .skip(common)
+            .map(|_| Component::ParentDir)
+            .chain(path.components().skip(common))
+            .collect()
+    } else {
+        path.to_path_buf()
+    };
+
+    relative_path
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => {
+                Some(utf8_percent_encode(&name.to_string_lossy(), PATH_SEGMENT).to_string())
+            }
+            Component::ParentDir => Some("..".to_string()),
+            _ => None,
+        })
+        .collect::<Vec<String>>()
+        .join("/")
+}
+
 pub fn domain_is_within_domain(domain: &str, domain_to_match_against: &str) -> bool {
     if domain_to_match_against.is_empty() {
         return false;
@@ -109,6 +175,21 @@ pub fn is_url_and_has_protocol(input: &str) -> bool {
     }
 }
 
+pub fn parse_proxy_url(input: &str) -> Option<Url> {
+    match Url::parse(input) {
+        Ok(parsed_url)
+            if parsed_url.has_host()
+                && matches!(
+                    parsed_url.scheme(),
+                    "http" | "https" | "socks4" | "socks4a" | "socks5" | "socks5h"
+                ) =>
+        {
+            Some(parsed_url)
+        }
+        _ => None,
+    }
+}
+
 pub fn parse_data_url(url: &Url) -> (String, String, Vec<u8>) {
     let path: String = url.path().to_string();
     let comma_loc: usize = path.find(',').unwrap_or(path.len());
@@ -151,3 +232,19 @@ pub fn resolve_url(from: &Url, to: &str) -> Url {
         },
     }
 }
+
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized_path: PathBuf = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized_path.pop();
+            }
+            _ => normalized_path.push(component),
+        }
+    }
+
+    normalized_path
+}
@@ -0,0 +1,464 @@
+use std::collections::HashSet;
+use std::io::Read;
+
+use brotli_decompressor::Decompressor;
+use chrono::{DateTime, SecondsFormat, Utc};
+use flate2::read::{DeflateDecoder, MultiGzDecoder, ZlibDecoder};
+use sha1::{Digest, Sha1};
+
+use crate::archive::{Archive, ArchivedResource};
+use crate::core::{get_header, parse_content_type};
+use crate::session::HttpExchange;
+use crate::url::{resolve_url, Url};
+
+const BROTLI_BUFFER_SIZE: usize = 4096;
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const WARC_VERSION: &str = "WARC/1.1";
+const WARC_SPEC_URL: &str =
+    "http://iipc.github.io/warc-specifications/specifications/warc-format/warc-1.1/";
+// Namespace for name-based (v5) UUIDs used as WARC record IDs (RFC 4122's URL namespace)
+const UUID_NAMESPACE_URL: [u8; 16] = [
+    0x6b, 0xa7, 0xb8, 0x11, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30, 0xc8,
+];
+// Hop-by-hop and encoding-related response headers which no longer describe the stored payload
+// (reqwest transparently decodes chunked and compressed bodies)
+const STRIPPED_RESPONSE_HEADERS: &[&str] =
+    &["content-encoding", "content-length", "transfer-encoding"];
+
+// HTTP response as it was stored within a WARC record
+struct RecordedResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    payload: Vec<u8>,
+}
+
+pub fn create_warc(document_url: &Url, document: &[u8], exchanges: &[HttpExchange]) -> Vec<u8> {
+    let mut warc: Vec<u8> = Vec::new();
+    let date: DateTime<Utc> = Utc::now();
+    let mut record_index: usize = 0;
+    let mut next_record_id = |uri: &str, date: &DateTime<Utc>| -> String {
+        record_index += 1;
+        create_warc_record_id(&format!(
+            "{}\n{}\n{}",
+            record_index,
+            uri,
+            date.timestamp_nanos_opt().unwrap_or_default()
+        ))
+    };
+
+    // Describe the software that created this file
+    let warcinfo_id: String = next_record_id("", &date);
+    let warcinfo: String = format!(
+        "software: {} v{}\r\nformat: WARC File Format 1.1\r\nconformsTo: {}\r\n",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        WARC_SPEC_URL,
+    );
+    warc.extend(create_warc_record(
+        "warcinfo",
+        vec![
+            ("WARC-Date", format_warc_date(&date)),
+            ("WARC-Record-ID", warcinfo_id.clone()),
+            ("Content-Type", "application/warc-fields".to_string()),
+        ],
+        warcinfo.as_bytes(),
+    ));
+
+    // Store every HTTP transaction that took place while creating the document,
+    // along with assets reused from cache (or other sources) which aren't stored yet
+    let mut stored_urls: HashSet<&Url> = HashSet::new();
+    for exchange in exchanges.iter().filter(|exchange| exchange.error.is_none()) {
+        if exchange.from_cache && stored_urls.contains(&exchange.url) {
+            continue;
+        }
+        // Responses to revalidation requests have no bodies, unlike cached ones recorded after them
+        if exchange.status != reqwest::StatusCode::NOT_MODIFIED.as_u16() {
+            stored_urls.insert(&exchange.url);
+        }
+
+        let request_id: String = next_record_id(exchange.url.as_str(), &exchange.date);
+        let response_id: String = next_record_id(exchange.url.as_str(), &exchange.date);
+
+        let request_block: Vec<u8> = format_http_request(exchange);
+        warc.extend(create_warc_record(
+            "request",
+            vec![
+                ("WARC-Date", format_warc_date(&exchange.date)),
+                ("WARC-Record-ID", request_id),
+                ("WARC-Warcinfo-ID", warcinfo_id.clone()),
+                ("WARC-Concurrent-To", response_id.clone()),
+                ("WARC-Target-URI", exchange.url.to_string()),
+                ("WARC-Block-Digest", create_sha1_digest(&request_block)),
+                (
+                    "Content-Type",
+                    "application/http; msgtype=request".to_string(),
+                ),
+            ],
+            &request_block,
+        ));
+
+        let mut response_fields: Vec<(&str, String)> = vec![
+            ("WARC-Date", format_warc_date(&exchange.date)),
+            ("WARC-Record-ID", response_id),
+            ("WARC-Warcinfo-ID", warcinfo_id.clone()),
+            ("WARC-Target-URI", exchange.url.to_string()),
+        ];
+        if let Some(remote_address) = &exchange.remote_address {
+            response_fields.push(("WARC-IP-Address", remote_address.clone()));
+        }
+        let response_block: Vec<u8> = format_http_response(exchange);
+        response_fields.push(("WARC-Payload-Digest", create_sha1_digest(&exchange.data)));
+        response_fields.push(("WARC-Block-Digest", create_sha1_digest(&response_block)));
+        response_fields.push((
+            "Content-Type",
+            "application/http; msgtype=response".to_string(),
+        ));
+        warc.extend(create_warc_record(
+            "response",
+            response_fields,
+            &response_block,
+        ));
+    }
+
+    // Store the resulting monolithic document
+    warc.extend(create_warc_record(
+        "resource",
+        vec![
+            ("WARC-Date", format_warc_date(&date)),
+            (
+                "WARC-Record-ID",
+                next_record_id(document_url.as_str(), &date),
+            ),
+            ("WARC-Warcinfo-ID", warcinfo_id),
+            ("WARC-Target-URI", document_url.to_string()),
+            ("WARC-Block-Digest", create_sha1_digest(document)),
+            ("Content-Type", "text/html".to_string()),
+        ],
+        document,
+    ));
+
+    warc
+}
+
+pub fn parse_warc(data: &[u8]) -> Option<Archive> {
+    // Compressed WARC files consist of individually gzipped records
+    let decompressed: Vec<u8>;
+    let data: &[u8] = if data.starts_with(&[0x1f, 0x8b]) {
+        let mut buffer: Vec<u8> = vec![];
+        MultiGzDecoder::new(data).read_to_end(&mut buffer).ok()?;
+        decompressed = buffer;
+        &decompressed
+    } else {
+        data
+    };
+
+    if !data.starts_with(b"WARC/") {
+        return None;
+    }
+
+    let mut archive: Archive = Archive::new();
+    let mut position: usize = 0;
+
+    while let Some(offset) = find_bytes(&data[position..], b"WARC/") {
+        let record_start: usize = position + offset;
+        let headers_end: usize = match find_bytes(&data[record_start..], b"\r\n\r\n") {
+            Some(i) => record_start + i,
+            None => break,
+        };
+        let fields: Vec<(String, String)> = parse_http_headers(&data[record_start..headers_end]);
+        let content_length: usize = get_header(&fields, "Content-Length")
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(0);
+        let block_start: usize = headers_end + 4;
+        let block_end: usize = (block_start + content_length).min(data.len());
+        let block: &[u8] = &data[block_start..block_end];
+        position = block_end;
+
+        // WARC 1.0 allows target URIs to be enclosed in angle brackets
+        let target_url: Url = match get_header(&fields, "WARC-Target-URI")
+            .and_then(|uri| Url::parse(uri.trim_start_matches('<').trim_end_matches('>')).ok())
+        {
+            Some(url) => url,
+            None => continue,
+        };
+
+        match get_header(&fields, "WARC-Type").unwrap_or_default() {
+            "response" => {
+                let RecordedResponse {
+                    status,
+                    headers,
+                    payload,
+                } = match parse_http_response(block) {
+                    Some(response) => response,
+                    None => continue,
+                };
+
+                if (300..400).contains(&status) {
+                    if let Some(location) = get_header(&headers, "Location") {
+                        archive.add_redirect(&target_url, &resolve_url(&target_url, location));
+                    }
+                } else if (200..300).contains(&status) {
+                    let (media_type, charset) = match get_header(&headers, "Content-Type") {
+                        Some(content_type) => {
+                            let (media_type, charset, _is_base64) =
+                                parse_content_type(content_type);
+                            (media_type, charset)
+                        }
+                        None => ("".to_string(), "".to_string()),
+                    };
+
+                    archive.add(ArchivedResource {
+                        url: target_url,
+                        media_type,
+                        charset,
+                        data: payload,
+                    });
+                }
+            }
+            // Captured responses take precedence over resources (e.g. documents created out of them)
+            "resource" if archive.get(&target_url).is_none() => {
+                let (media_type, charset, _is_base64) =
+                    parse_content_type(get_header(&fields, "Content-Type").unwrap_or_default());
+
+                archive.add(ArchivedResource {
+                    url: target_url,
+                    media_type,
+                    charset,
+                    data: block.to_vec(),
+                });
+            }
+            "revisit" => {
+                // Payload of revisit records is the same as the one of the record they refer to
+                if let Some(refers_to_url) = get_header(&fields, "WARC-Refers-To-Target-URI")
+                    .and_then(|uri| {
+                        Url::parse(uri.trim_start_matches('<').trim_end_matches('>')).ok()
+                    })
+                {
+                    if refers_to_url != target_url {
+                        archive.add_redirect(&target_url, &refers_to_url);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Some(archive)
+}
+
+pub fn create_warc_record(warc_type: &str, fields: Vec<(&str, String)>, block: &[u8]) -> Vec<u8> {
+    let mut record: Vec<u8> = Vec::new();
+
+    record.extend_from_slice(format!("{}\r\n", WARC_VERSION).as_bytes());
+    record.extend_from_slice(format!("WARC-Type: {}\r\n", warc_type).as_bytes());
+    for (name, value) in fields {
+        record.extend_from_slice(format!("{}: {}\r\n", name, value).as_bytes());
+    }
+    record.extend_from_slice(format!("Content-Length: {}\r\n", block.len()).as_bytes());
+    record.extend_from_slice(b"\r\n");
+    record.extend_from_slice(block);
+    // Every record is followed by two newlines
+    record.extend_from_slice(b"\r\n\r\n");
+
+    record
+}
+
+pub fn create_warc_record_id(name: &str) -> String {
+    // Version 5 (name-based, SHA-1) UUID
+    let mut hasher = Sha1::new();
+    hasher.update(UUID_NAMESPACE_URL);
+    hasher.update(name.as_bytes());
+    let mut bytes: Vec<u8> = hasher.finalize()[..16].to_vec();
+    bytes[6] = (bytes[6] & 0x0f) | 0x50;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+
+    format!(
+        "<urn:uuid:{}-{}-{}-{}-{}>",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+fn create_sha1_digest(data: &[u8]) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(data);
+
+    format!("sha1:{}", encode_base32(&hasher.finalize()))
+}
+
+fn encode_base32(data: &[u8]) -> String {
+    let mut result: String = String::new();
+    let mut buffer: u32 = 0;
+    let mut bits: u32 = 0;
+
+    for byte in data {
+        buffer = (buffer << 8) | *byte as u32;
+        bits += 8;
+
+        while bits >= 5 {
+            bits -= 5;
+            result.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+
+    if bits > 0 {
+        result.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+
+    while !result.len().is_multiple_of(8) {
+        result.push('=');
+    }
+
+    result
+}
+
+fn decode_chunked_body(data: &[u8]) -> Option<Vec<u8>> {
+    let mut result: Vec<u8> = vec![];
+    let mut position: usize = 0;
+
+    loop {
+        let line_end: usize = position + find_bytes(&data[position..], b"\r\n")?;
+        let chunk_size_line: String =
+            String::from_utf8_lossy(&data[position..line_end]).to_string();
+        // Chunk extensions are separated from the chunk size by semicolons
+        let chunk_size: usize =
+            usize::from_str_radix(chunk_size_line.split(';').next()?.trim(), 16).ok()?;
+        if chunk_size == 0 {
+            break;
+        }
+
+        let chunk_start: usize = line_end + 2;
+        let chunk_end: usize = chunk_start.checked_add(chunk_size)?;
+        result.extend_from_slice(data.get(chunk_start..chunk_end)?);
+        position = chunk_end.checked_add(2)?;
+        if position > data.len() {
+            break;
+        }
+    }
+
+    Some(result)
+}
+
+fn decode_content(data: Vec<u8>, content_encoding: &str) -> Vec<u8> {
+    let mut result: Vec<u8> = vec![];
+    let decoded: bool = match content_encoding.trim().to_lowercase().as_str() {
+        "gzip" | "x-gzip" => MultiGzDecoder::new(&data[..])
+            .read_to_end(&mut result)
+            .is_ok(),
+        "deflate" => {
+            // Servers use both zlib-wrapped and raw deflate streams for this encoding
+            ZlibDecoder::new(&data[..]).read_to_end(&mut result).is_ok() || {
+                result.clear();
+                DeflateDecoder::new(&data[..])
+                    .read_to_end(&mut result)
+                    .is_ok()
+            }
+        }
+        "br" => Decompressor::new(&data[..], BROTLI_BUFFER_SIZE)
+            .read_to_end(&mut result)
+            .is_ok(),
+        _ => false,
+    };
+
+    if decoded {
+        result
+    } else {
+        data
+    }
+}
+
+fn find_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn format_http_request(exchange: &HttpExchange) -> Vec<u8> {
+    let mut request_target: String = exchange.url.path().to_string();
+    if let Some(query) = exchange.url.query() {
+        request_target.push('?');
+        request_target.push_str(query);
+    }
+
+    let mut block: String = format!("GET {} HTTP/1.1\r\n", request_target);
+    for (name, value) in &exchange.request_headers {
+        block.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    block.push_str("\r\n");
+
+    block.into_bytes()
+}
+
+fn format_http_response(exchange: &HttpExchange) -> Vec<u8> {
+    let reason: &str = reqwest::StatusCode::from_u16(exchange.status)
+        .ok()
+        .and_then(|status| status.canonical_reason())
+        .unwrap_or("");
+
+    let mut head: String = format!(
+        "{} {} {}\r\n",
+        if exchange.http_version.is_empty() {
+            "HTTP/1.1"
+        } else {
+            &exchange.http_version
+        },
+        exchange.status,
+        reason
+    );
+    for (name, value) in &exchange.response_headers {
+        if !STRIPPED_RESPONSE_HEADERS.contains(&name.to_lowercase().as_str()) {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+    }
+    head.push_str(&format!("Content-Length: {}\r\n", exchange.data.len()));
+    head.push_str("\r\n");
+
+    let mut block: Vec<u8> = head.into_bytes();
+    block.extend_from_slice(&exchange.data);
+
+    block
+}
+
+fn format_warc_date(date: &DateTime<Utc>) -> String {
+    date.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn parse_http_headers(data: &[u8]) -> Vec<(String, String)> {
+    String::from_utf8_lossy(data)
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+fn parse_http_response(block: &[u8]) -> Option<RecordedResponse> {
+    let head_end: usize = find_bytes(block, b"\r\n\r\n")?;
+    let head: String = String::from_utf8_lossy(&block[..head_end]).to_string();
+    let (status_line, header_lines) = head.split_once("\r\n").unwrap_or((&head, ""));
+    let status: u16 = status_line.split_whitespace().nth(1)?.parse().ok()?;
+    let headers: Vec<(String, String)> = parse_http_headers(header_lines.as_bytes());
+
+    // Payloads are stored the way they were sent over the network
+    let mut payload: Vec<u8> = block[head_end + 4..].to_vec();
+    if get_header(&headers, "Transfer-Encoding")
+        .is_some_and(|value| value.eq_ignore_ascii_case("chunked"))
+    {
+        if let Some(decoded_payload) = decode_chunked_body(&payload) {
+            payload = decoded_payload;
+        }
+    }
+    if let Some(content_encoding) = get_header(&headers, "Content-Encoding") {
+        payload = decode_content(payload, content_encoding);
+    }
+
+    Some(RecordedResponse {
+        status,
+        headers,
+        payload,
+    })
+}
@@ -0,0 +1,62 @@
+use plist::{Dictionary, Value};
+
+use crate::core::is_plaintext_media_type;
+use crate::session::EmbeddedAsset;
+
+pub fn create_webarchive(document: &EmbeddedAsset, assets: &[EmbeddedAsset]) -> Vec<u8> {
+    let mut webarchive: Dictionary = Dictionary::new();
+
+    let mut main_resource: Dictionary = create_webarchive_resource(document);
+    // Main resource is expected to always have a frame name, even if it's empty
+    main_resource.insert("WebResourceFrameName".to_string(), Value::from(""));
+    webarchive.insert(
+        "WebMainResource".to_string(),
+        Value::Dictionary(main_resource),
+    );
+
+    // Subresources are looked up by their original URLs when the archive gets opened
+    if !assets.is_empty() {
+        webarchive.insert(
+            "WebSubresources".to_string(),
+            Value::Array(
+                assets
+                    .iter()
+                    .map(|asset| Value::Dictionary(create_webarchive_resource(asset)))
+                    .collect(),
+            ),
+        );
+    }
+
+    let mut result: Vec<u8> = vec![];
+    Value::Dictionary(webarchive)
+        .to_writer_binary(&mut result)
+        .expect("unable to serialize web archive");
+
+    result
+}
+
+fn create_webarchive_resource(asset: &EmbeddedAsset) -> Dictionary {
+    let mut resource: Dictionary = Dictionary::new();
+
+    resource.insert(
+        "WebResourceURL".to_string(),
+        Value::from(asset.url.as_str()),
+    );
+    resource.insert(
+        "WebResourceMIMEType".to_string(),
+        Value::from(asset.media_type.as_str()),
+    );
+    // Binary resources don't have any text encoding
+    if !asset.charset.is_empty() && is_plaintext_media_type(&asset.media_type) {
+        resource.insert(
+            "WebResourceTextEncodingName".to_string(),
+            Value::from(asset.charset.as_str()),
+        );
+    }
+    resource.insert(
+        "WebResourceData".to_string(),
+        Value::Data(asset.data.clone()),
+    );
+
+    resource
+}
@@ -0,0 +1,265 @@
+use chrono::Utc;
+use md5::Md5;
+use sha2::{Digest, Sha256};
+
+use crate::html::create_source_url;
+use crate::session::EmbeddedAsset;
+
+const ZIM_MAGIC_NUMBER: u32 = 72173914;
+const ZIM_MAJOR_VERSION: u16 = 6;
+const ZIM_MINOR_VERSION: u16 = 1; // New namespace scheme (all content is stored within "C")
+const ZIM_HEADER_SIZE: usize = 80;
+const ZIM_NO_PAGE: u32 = 0xffffffff;
+const ZIM_REDIRECT_MIME_TYPE: u16 = 0xffff;
+const ZIM_CLUSTER_UNCOMPRESSED: u8 = 1;
+const ZIM_DOCUMENT_PATH: &str = "index.html";
+
+struct ZimEntry {
+    namespace: u8,
+    path: String,
+    title: String,
+    content: ZimEntryContent,
+}
+
+enum ZimEntryContent {
+    Blob { mime_type: String, data: Vec<u8> },
+    Redirect { target_path: String },
+}
+
+pub fn create_zim(
+    document: &EmbeddedAsset,
+    assets: &[EmbeddedAsset],
+    document_title: Option<String>,
+) -> Vec<u8> {
+    let title: String = document_title.unwrap_or_default();
+    let mut entries: Vec<ZimEntry> = vec![];
+
+    // Content
+    entries.push(ZimEntry {
+        namespace: b'C',
+        path: ZIM_DOCUMENT_PATH.to_string(),
+        title: title.clone(),
+        content: ZimEntryContent::Blob {
+            mime_type: format_mime_type(document),
+            data: document.data.clone(),
+        },
+    });
+    for asset in assets {
+        entries.push(ZimEntry {
+            namespace: b'C',
+            path: asset.file_name(),
+            title: "".to_string(),
+            content: ZimEntryContent::Blob {
+                mime_type: format_mime_type(asset),
+                data: asset.data.clone(),
+            },
+        });
+    }
+    entries.push(ZimEntry {
+        namespace: b'W',
+        path: "mainPage".to_string(),
+        title: "".to_string(),
+        content: ZimEntryContent::Redirect {
+            target_path: ZIM_DOCUMENT_PATH.to_string(),
+        },
+    });
+
+    // Metadata
+    let mut metadata: Vec<(&str, String)> = vec![
+        ("Date", Utc::now().format("%Y-%m-%d").to_string()),
+        (
+            "Scraper",
+            format!("{} v{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")),
+        ),
+    ];
+    if !title.is_empty() {
+        metadata.push(("Title", title.clone()));
+    }
+    if let Some(source_url) = create_source_url(&document.url) {
+        if let Some(host) = source_url.host_str() {
+            metadata.push(("Creator", host.to_string()));
+        }
+        metadata.push(("Source", source_url.to_string()));
+    }
+    for (name, value) in metadata {
+        entries.push(ZimEntry {
+            namespace: b'M',
+            path: name.to_string(),
+            title: "".to_string(),
+            content: ZimEntryContent::Blob {
+                mime_type: "text/plain".to_string(),
+                data: value.into_bytes(),
+            },
+        });
+    }
+
+    write_zim(entries)
+}
+
+fn format_mime_type(asset: &EmbeddedAsset) -> String {
+    let charset: &str = asset.charset.trim();
+
+    if charset.is_empty() || charset.eq_ignore_ascii_case("US-ASCII") {
+        asset.media_type.clone()
+    } else {
+        format!("{}; charset={}", asset.media_type, charset)
+    }
+}
+
+fn write_zim(mut entries: Vec<ZimEntry>) -> Vec<u8> {
+    // Entries have to be ordered by their full path
+    entries.sort_by(|a, b| (a.namespace, &a.path).cmp(&(b.namespace, &b.path)));
+    entries.dedup_by(|a, b| a.namespace == b.namespace && a.path == b.path);
+
+    let mut mime_types: Vec<String> = vec![];
+    let mut blobs: Vec<&[u8]> = vec![];
+    let mut directory_entries: Vec<Vec<u8>> = vec![];
+    let mut main_page: u32 = ZIM_NO_PAGE;
+
+    for (i, entry) in entries.iter().enumerate() {
+        let mut directory_entry: Vec<u8> = vec![];
+
+        match &entry.content {
+            ZimEntryContent::Blob { mime_type, data } => {
+                let mime_type_index: usize = match mime_types.iter().position(|m| m == mime_type) {
+                    Some(index) => index,
+                    None => {
+                        mime_types.push(mime_type.clone());
+                        mime_types.len() - 1
+                    }
+                };
+
+                directory_entry.extend((mime_type_index as u16).to_le_bytes());
+                directory_entry.push(0); // Parameter length
+                directory_entry.push(entry.namespace);
+                directory_entry.extend(0u32.to_le_bytes()); // Revision
+                directory_entry.extend(0u32.to_le_bytes()); // Cluster number
+                directory_entry.extend((blobs.len() as u32).to_le_bytes());
+                blobs.push(data);
+
+                if entry.namespace == b'C' && entry.path == ZIM_DOCUMENT_PATH {
+                    main_page = i as u32;
+                }
+            }
+            ZimEntryContent::Redirect { target_path } => {
+                let target_index: usize = entries
+                    .iter()
+                    .position(|e| e.namespace == b'C' && &e.path == target_path)
+                    .unwrap();
+
+                directory_entry.extend(ZIM_REDIRECT_MIME_TYPE.to_le_bytes());
+                directory_entry.push(0); // Parameter length
+                directory_entry.push(entry.namespace);
+                directory_entry.extend(0u32.to_le_bytes()); // Revision
+                directory_entry.extend((target_index as u32).to_le_bytes());
+            }
+        }
+
+        directory_entry.extend(entry.path.as_bytes());
+        directory_entry.push(0);
+        directory_entry.extend(entry.title.as_bytes());
+        directory_entry.push(0);
+
+        directory_entries.push(directory_entry);
+    }
+
+    // Titles fall back to paths when sorting
+    let mut title_order: Vec<usize> = (0..entries.len()).collect();
+    title_order.sort_by(|a, b| {
+        let title = |e: &ZimEntry| -> (u8, String) {
+            (
+                e.namespace,
+                if e.title.is_empty() {
+                    e.path.clone()
+                } else {
+                    e.title.clone()
+                },
+            )
+        };
+        title(&entries[*a]).cmp(&title(&entries[*b]))
+    });
+
+    // MIME type list
+    let mut mime_type_list: Vec<u8> = vec![];
+    for mime_type in &mime_types {
+        mime_type_list.extend(mime_type.as_bytes());
+        mime_type_list.push(0);
+    }
+    mime_type_list.push(0);
+
+    // Single uncompressed cluster, which holds all blobs
+    let mut cluster: Vec<u8> = vec![ZIM_CLUSTER_UNCOMPRESSED];
+    let mut offset: usize = (blobs.len() + 1) * 4;
+    cluster.extend((offset as u32).to_le_bytes());
+    for blob in &blobs {
+        offset += blob.len();
+        cluster.extend((offset as u32).to_le_bytes());
+    }
+    for blob in &blobs {
+        cluster.extend_from_slice(blob);
+    }
+
+    // Calculate positions of all parts of the file
+    let mime_list_position: usize = ZIM_HEADER_SIZE;
+    let directory_entries_position: usize = mime_list_position + mime_type_list.len();
+    let mut directory_entry_positions: Vec<u64> = vec![];
+    let mut position: usize = directory_entries_position;
+    for directory_entry in &directory_entries {
+        directory_entry_positions.push(position as u64);
+        position += directory_entry.len();
+    }
+    let path_pointers_position: usize = position;
+    let title_pointers_position: usize = path_pointers_position + entries.len() * 8;
+    let cluster_pointers_position: usize = title_pointers_position + entries.len() * 4;
+    let cluster_position: usize = cluster_pointers_position + 8;
+    let checksum_position: usize = cluster_position + cluster.len();
+
+    let mut zim: Vec<u8> = Vec::with_capacity(checksum_position + 16);
+
+    // Header
+    zim.extend(ZIM_MAGIC_NUMBER.to_le_bytes());
+    zim.extend(ZIM_MAJOR_VERSION.to_le_bytes());
+    zim.extend(ZIM_MINOR_VERSION.to_le_bytes());
+    zim.extend(create_zim_uuid(&cluster));
+    zim.extend((entries.len() as u32).to_le_bytes());
+    zim.extend(1u32.to_le_bytes()); // Cluster count
+    zim.extend((path_pointers_position as u64).to_le_bytes());
+    zim.extend((title_pointers_position as u64).to_le_bytes());
+    zim.extend((cluster_pointers_position as u64).to_le_bytes());
+    zim.extend((mime_list_position as u64).to_le_bytes());
+    zim.extend(main_page.to_le_bytes());
+    zim.extend(ZIM_NO_PAGE.to_le_bytes()); // Layout page
+    zim.extend((checksum_position as u64).to_le_bytes());
+
+    zim.extend(mime_type_list);
+    for directory_entry in directory_entries {
+        zim.extend(directory_entry);
+    }
+    for directory_entry_position in directory_entry_positions {
+        zim.extend(directory_entry_position.to_le_bytes());
+    }
+    for i in title_order {
+        zim.extend((i as u32).to_le_bytes());
+    }
+    zim.extend((cluster_position as u64).to_le_bytes());
+    zim.extend(cluster);
+
+    let mut hasher = Md5::new();
+    hasher.update(&zim);
+    zim.extend(hasher.finalize());
+
+    zim
+}
+
+fn create_zim_uuid(cluster: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(cluster);
+    hasher.update(
+        Utc::now()
+            .timestamp_nanos_opt()
+            .unwrap_or_default()
+            .to_le_bytes(),
+    );
+
+    hasher.finalize()[..16].to_vec()
+}
@@ -0,0 +1,99 @@
+use std::io::Write;
+
+use chrono::{Datelike, Local, Timelike};
+use flate2::write::DeflateEncoder;
+use flate2::{Compression, Crc};
+
+const ZIP_VERSION: u16 = 20; // 2.0, needed for deflate
+const ZIP_FLAG_UTF8: u16 = 0x0800; // File names are encoded using UTF-8
+const ZIP_METHOD_STORED: u16 = 0;
+const ZIP_METHOD_DEFLATED: u16 = 8;
+
+pub struct ZipFile {
+    pub path: String,
+    pub data: Vec<u8>,
+    pub stored: bool, // Never compress, some formats require certain files to remain as they are
+}
+
+pub fn create_zip(files: &[ZipFile]) -> Vec<u8> {
+    let (date, time) = create_dos_date_time();
+    let mut zip: Vec<u8> = vec![];
+    let mut central_directory: Vec<u8> = vec![];
+
+    for file in files {
+        let mut crc = Crc::new();
+        crc.update(&file.data);
+
+        let mut method: u16 = ZIP_METHOD_STORED;
+        let mut contents: Vec<u8> = file.data.clone();
+        if !file.stored {
+            let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(&file.data).unwrap();
+            let compressed: Vec<u8> = encoder.finish().unwrap();
+
+            // Files which don't get any smaller are better off stored as they are
+            if compressed.len() < file.data.len() {
+                method = ZIP_METHOD_DEFLATED;
+                contents = compressed;
+            }
+        }
+
+        let offset: u32 = zip.len() as u32;
+        let mut fields: Vec<u8> = vec![];
+        fields.extend(ZIP_VERSION.to_le_bytes()); // Version needed to extract
+        fields.extend(ZIP_FLAG_UTF8.to_le_bytes());
+        fields.extend(method.to_le_bytes());
+        fields.extend(time.to_le_bytes());
+        fields.extend(date.to_le_bytes());
+        fields.extend(crc.sum().to_le_bytes());
+        fields.extend((contents.len() as u32).to_le_bytes());
+        fields.extend((file.data.len() as u32).to_le_bytes());
+        fields.extend((file.path.len() as u16).to_le_bytes());
+        fields.extend(0u16.to_le_bytes()); // Extra field length
+
+        // Local file header, followed by file contents
+        zip.extend(b"PK\x03\x04");
+        zip.extend(&fields);
+        zip.extend(file.path.as_bytes());
+        zip.extend(contents);
+
+        // Central directory record
+        central_directory.extend(b"PK\x01\x02");
+        central_directory.extend(ZIP_VERSION.to_le_bytes()); // Version made by
+        central_directory.extend(&fields);
+        central_directory.extend(0u16.to_le_bytes()); // File comment length
+        central_directory.extend(0u16.to_le_bytes()); // Disk number
+        central_directory.extend(0u16.to_le_bytes()); // Internal file attributes
+        central_directory.extend(0u32.to_le_bytes()); // External file attributes
+        central_directory.extend(offset.to_le_bytes());
+        central_directory.extend(file.path.as_bytes());
+    }
+
+    let central_directory_offset: u32 = zip.len() as u32;
+    zip.extend(&central_directory);
+
+    // End of central directory record
+    zip.extend(b"PK\x05\x06");
+    zip.extend(0u16.to_le_bytes()); // Disk number
+    zip.extend(0u16.to_le_bytes()); // Disk where central directory starts
+    zip.extend((files.len() as u16).to_le_bytes());
+    zip.extend((files.len() as u16).to_le_bytes());
+    zip.extend((central_directory.len() as u32).to_le_bytes());
+    zip.extend(central_directory_offset.to_le_bytes());
+    zip.extend(0u16.to_le_bytes()); // Comment length
+
+    zip
+}
+
+fn create_dos_date_time() -> (u16, u16) {
+    let now = Local::now();
+
+    // MS-DOS timestamps can't go below 1980 and have a precision of two seconds
+    let date: u16 = (((now.year().max(1980) - 1980) as u16) << 9)
+        | ((now.month() as u16) << 5)
+        | now.day() as u16;
+    let time: u16 =
+        ((now.hour() as u16) << 11) | ((now.minute() as u16) << 5) | (now.second() as u16 / 2);
+
+    (date, time)
+}
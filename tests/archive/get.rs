@@ -0,0 +1,129 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::archive::{Archive, ArchivedResource};
+    use monolith::url::Url;
+
+    fn resource(url: &str, data: &[u8]) -> ArchivedResource {
+        ArchivedResource {
+            url: Url::parse(url).unwrap(),
+            media_type: "text/plain".to_string(),
+            charset: "".to_string(),
+            data: data.to_vec(),
+        }
+    }
+
+    #[test]
+    fn ignores_fragments() {
+        let mut archive = Archive::new();
+        archive.add(resource("https://example.com/a#one", b"a"));
+
+        assert_eq!(
+            archive
+                .get(&Url::parse("https://example.com/a#two").unwrap())
+                .unwrap()
+                .data,
+            b"a"
+        );
+    }
+
+    #[test]
+    fn later_resources_take_precedence() {
+        let mut archive = Archive::new();
+        archive.add(resource("https://example.com/a", b"old"));
+        archive.add(resource("https://example.com/a", b"new"));
+
+        assert_eq!(
+            archive
+                .get(&Url::parse("https://example.com/a").unwrap())
+                .unwrap()
+                .data,
+            b"new"
+        );
+    }
+
+    #[test]
+    fn follows_redirects() {
+        let mut archive = Archive::new();
+        archive.add_redirect(
+            &Url::parse("https://example.com/a").unwrap(),
+            &Url::parse("https://example.com/b").unwrap(),
+        );
+        archive.add_redirect(
+            &Url::parse("https://example.com/b").unwrap(),
+            &Url::parse("https://example.com/c").unwrap(),
+        );
+        archive.add(resource("https://example.com/c", b"c"));
+
+        let resource = archive
+            .get(&Url::parse("https://example.com/a").unwrap())
+            .unwrap();
+        assert_eq!(resource.url.as_str(), "https://example.com/c");
+        assert_eq!(resource.data, b"c");
+    }
+
+    #[test]
+    fn resources_take_precedence_over_redirects() {
+        let mut archive = Archive::new();
+        archive.add(resource("https://example.com/a", b"a"));
+        archive.add_redirect(
+            &Url::parse("https://example.com/a").unwrap(),
+            &Url::parse("https://example.com/b").unwrap(),
+        );
+        archive.add(resource("https://example.com/b", b"b"));
+
+        assert_eq!(
+            archive
+                .get(&Url::parse("https://example.com/a").unwrap())
+                .unwrap()
+                .data,
+            b"a"
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::archive::Archive;
+    use monolith::url::Url;
+
+    #[test]
+    fn missing() {
+        let archive = Archive::new();
+
+        assert!(archive.is_empty());
+        assert!(archive
+            .get(&Url::parse("https://example.com/").unwrap())
+            .is_none());
+    }
+
+    #[test]
+    fn redirect_loop() {
+        let mut archive = Archive::new();
+        archive.add_redirect(
+            &Url::parse("https://example.com/a").unwrap(),
+            &Url::parse("https://example.com/b").unwrap(),
+        );
+        archive.add_redirect(
+            &Url::parse("https://example.com/b").unwrap(),
+            &Url::parse("https://example.com/a").unwrap(),
+        );
+
+        assert!(archive
+            .get(&Url::parse("https://example.com/a").unwrap())
+            .is_none());
+    }
+}
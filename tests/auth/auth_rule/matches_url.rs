@@ -0,0 +1,70 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::auth::{AuthRule, Credentials};
+    use monolith::url::Url;
+
+    #[test]
+    fn same_origin() {
+        let auth_rule = AuthRule {
+            origin: String::from("https://wiki.example.com"),
+            credentials: Credentials::Token(String::from("token")),
+        };
+        assert!(auth_rule.matches_url(&Url::parse("https://wiki.example.com/page?id=1").unwrap()));
+    }
+
+    #[test]
+    fn default_port() {
+        let auth_rule = AuthRule {
+            origin: String::from("https://wiki.example.com:443"),
+            credentials: Credentials::Token(String::from("token")),
+        };
+        assert!(auth_rule.matches_url(&Url::parse("https://wiki.example.com/").unwrap()));
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::auth::{AuthRule, Credentials};
+    use monolith::url::Url;
+
+    #[test]
+    fn different_scheme() {
+        let auth_rule = AuthRule {
+            origin: String::from("https://wiki.example.com"),
+            credentials: Credentials::Token(String::from("token")),
+        };
+        assert!(!auth_rule.matches_url(&Url::parse("http://wiki.example.com/").unwrap()));
+    }
+
+    #[test]
+    fn different_port() {
+        let auth_rule = AuthRule {
+            origin: String::from("https://wiki.example.com"),
+            credentials: Credentials::Token(String::from("token")),
+        };
+        assert!(!auth_rule.matches_url(&Url::parse("https://wiki.example.com:8443/").unwrap()));
+    }
+
+    #[test]
+    fn subdomain() {
+        let auth_rule = AuthRule {
+            origin: String::from("https://example.com"),
+            credentials: Credentials::Token(String::from("token")),
+        };
+        assert!(!auth_rule.matches_url(&Url::parse("https://cdn.example.com/").unwrap()));
+    }
+}
@@ -0,0 +1 @@
+mod matches_url;
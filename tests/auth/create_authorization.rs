@@ -0,0 +1,80 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::auth;
+    use monolith::url::Url;
+
+    #[test]
+    fn basic() {
+        assert_eq!(
+            auth::create_authorization(
+                "Aladdin",
+                "open sesame",
+                &[String::from("Basic realm=\"WallyWorld\"")],
+                &Url::parse("https://example.com/").unwrap(),
+            ),
+            Some(String::from("Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ=="))
+        );
+    }
+
+    #[test]
+    fn digest_preferred_over_basic() {
+        let authorization: String = auth::create_authorization(
+            "Mufasa",
+            "Circle Of Life",
+            &[
+                String::from("Basic realm=\"testrealm@host.com\""),
+                String::from("Digest realm=\"testrealm@host.com\", qop=\"auth\", nonce=\"dcd98b7102dd2f0e8b11d0f600bfb0c093\""),
+            ],
+            &Url::parse("https://example.com/dir/index.html?a=b").unwrap(),
+        )
+        .unwrap();
+        assert!(authorization.starts_with("Digest username=\"Mufasa\""));
+        assert!(authorization.contains(", uri=\"/dir/index.html?a=b\","));
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::auth;
+    use monolith::url::Url;
+
+    #[test]
+    fn no_challenge() {
+        assert_eq!(
+            auth::create_authorization(
+                "Aladdin",
+                "open sesame",
+                &[],
+                &Url::parse("https://example.com/").unwrap(),
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn unsupported_scheme() {
+        assert_eq!(
+            auth::create_authorization(
+                "Aladdin",
+                "open sesame",
+                &[String::from("Negotiate")],
+                &Url::parse("https://example.com/").unwrap(),
+            ),
+            None
+        );
+    }
+}
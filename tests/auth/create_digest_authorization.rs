@@ -0,0 +1,90 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::auth;
+
+    #[test]
+    fn rfc_2617_example() {
+        let challenge = &auth::parse_challenges(
+            "Digest realm=\"testrealm@host.com\", qop=\"auth,auth-int\", \
+            nonce=\"dcd98b7102dd2f0e8b11d0f600bfb0c093\", opaque=\"5ccc069c403ebaf9f0171e9517f40e41\"",
+        )[0];
+
+        assert_eq!(
+            auth::create_digest_authorization(
+                challenge,
+                "Mufasa",
+                "Circle Of Life",
+                "/dir/index.html",
+                "0a4f113b"
+            ),
+            Some(String::from(
+                "Digest username=\"Mufasa\", realm=\"testrealm@host.com\", \
+                nonce=\"dcd98b7102dd2f0e8b11d0f600bfb0c093\", uri=\"/dir/index.html\", algorithm=MD5, \
+                response=\"6629fae49393a05397450978507c4ef1\", qop=auth, nc=00000001, cnonce=\"0a4f113b\", \
+                opaque=\"5ccc069c403ebaf9f0171e9517f40e41\""
+            ))
+        );
+    }
+
+    #[test]
+    fn sha_256() {
+        let challenge = &auth::parse_challenges(
+            "Digest realm=\"http-auth@example.org\", qop=\"auth, auth-int\", algorithm=SHA-256, \
+            nonce=\"7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v\", opaque=\"FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS\"",
+        )[0];
+        let authorization: String = auth::create_digest_authorization(
+            challenge,
+            "Mufasa",
+            "Circle of Life",
+            "/dir/index.html",
+            "f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ",
+        )
+        .unwrap();
+
+        // RFC 7616 example
+        assert!(authorization.contains(
+            "response=\"753927fa0e85d155564e2e272a28d1802ca10daf4496794697cf8db5856cb6c1\""
+        ));
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::auth;
+
+    #[test]
+    fn no_nonce() {
+        let challenge = &auth::parse_challenges("Digest realm=\"test\"")[0];
+
+        assert_eq!(
+            auth::create_digest_authorization(challenge, "user", "password", "/", "0a4f113b"),
+            None
+        );
+    }
+
+    #[test]
+    fn unsupported_algorithm() {
+        let challenge =
+            &auth::parse_challenges("Digest realm=\"test\", nonce=\"abc\", algorithm=SHA-512-256")
+                [0];
+
+        assert_eq!(
+            auth::create_digest_authorization(challenge, "user", "password", "/", "0a4f113b"),
+            None
+        );
+    }
+}
@@ -0,0 +1,5 @@
+mod auth_rule;
+mod create_authorization;
+mod create_digest_authorization;
+mod parse_challenges;
+mod parse_netrc;
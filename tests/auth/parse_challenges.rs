@@ -0,0 +1,57 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::auth;
+
+    #[test]
+    fn basic() {
+        let challenges = auth::parse_challenges("Basic realm=\"WallyWorld\"");
+
+        assert_eq!(challenges.len(), 1);
+        assert_eq!(challenges[0].scheme, "basic");
+        assert_eq!(challenges[0].params.get("realm").unwrap(), "WallyWorld");
+    }
+
+    #[test]
+    fn multiple_challenges() {
+        let challenges = auth::parse_challenges(
+            "Newauth realm=\"apps\", type=1, title=\"Login to \\\"apps\\\"\", Basic realm=\"simple, yet effective\"",
+        );
+
+        assert_eq!(challenges.len(), 2);
+        assert_eq!(challenges[0].scheme, "newauth");
+        assert_eq!(challenges[0].params.get("type").unwrap(), "1");
+        assert_eq!(
+            challenges[0].params.get("title").unwrap(),
+            "Login to \"apps\""
+        );
+        assert_eq!(challenges[1].scheme, "basic");
+        assert_eq!(
+            challenges[1].params.get("realm").unwrap(),
+            "simple, yet effective"
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::auth;
+
+    #[test]
+    fn empty() {
+        assert!(auth::parse_challenges("").is_empty());
+    }
+}
@@ -0,0 +1,81 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::auth::{self, AuthRule, Credentials};
+
+    #[test]
+    fn machines() {
+        let netrc = "machine wiki.example.com\n\
+            login user\n\
+            password secret\n\
+            \n\
+            machine example.net login other password 123";
+
+        assert_eq!(
+            auth::parse_netrc(netrc),
+            vec![
+                AuthRule {
+                    origin: String::from("https://wiki.example.com"),
+                    credentials: Credentials::Password(
+                        String::from("user"),
+                        String::from("secret")
+                    ),
+                },
+                AuthRule {
+                    origin: String::from("http://wiki.example.com"),
+                    credentials: Credentials::Password(
+                        String::from("user"),
+                        String::from("secret")
+                    ),
+                },
+                AuthRule {
+                    origin: String::from("https://example.net"),
+                    credentials: Credentials::Password(String::from("other"), String::from("123")),
+                },
+                AuthRule {
+                    origin: String::from("http://example.net"),
+                    credentials: Credentials::Password(String::from("other"), String::from("123")),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn macro_definition() {
+        let netrc = "machine example.com login user password secret\n\
+            macdef init\n\
+            machine not-a-machine password nope\n\
+            \n";
+
+        assert_eq!(auth::parse_netrc(netrc).len(), 2);
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::auth;
+
+    #[test]
+    fn default_entry() {
+        // Default credentials would get sent to any host
+        assert!(auth::parse_netrc("default login anonymous password user@example.com").is_empty());
+    }
+
+    #[test]
+    fn no_password() {
+        assert!(auth::parse_netrc("machine example.com login user").is_empty());
+    }
+}
@@ -0,0 +1 @@
+mod parse_batch;
@@ -0,0 +1,111 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::batch::{parse_batch, BatchTarget};
+
+    fn target(target: &str, output: Option<&str>) -> BatchTarget {
+        BatchTarget {
+            target: target.to_string(),
+            output: output.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn plain_list() {
+        assert_eq!(
+            parse_batch("https://example.com/\n\n# Comment\n  https://example.org/  \r\n").unwrap(),
+            vec![
+                target("https://example.com/", None),
+                target("https://example.org/", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(parse_batch("\n\n").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn csv() {
+        assert_eq!(
+            parse_batch(
+                "URL,Output\nhttps://example.com/,example.html\nhttps://example.org/?a=1,\"org, with comma.html\"\nhttps://example.net/\n"
+            )
+            .unwrap(),
+            vec![
+                target("https://example.com/", Some("example.html")),
+                target("https://example.org/?a=1", Some("org, with comma.html")),
+                target("https://example.net/", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn csv_with_columns_in_different_order() {
+        assert_eq!(
+            parse_batch("output,target\n\"a \"\"b\"\".html\",https://example.com/\n").unwrap(),
+            vec![target("https://example.com/", Some("a \"b\".html"))]
+        );
+    }
+
+    #[test]
+    fn json() {
+        assert_eq!(
+            parse_batch(
+                r#"[
+                    "https://example.com/",
+                    { "target": "https://example.org/", "output": "org.html" },
+                    { "url": "https://example.net/" }
+                ]"#
+            )
+            .unwrap(),
+            vec![
+                target("https://example.com/", None),
+                target("https://example.org/", Some("org.html")),
+                target("https://example.net/", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn url_with_comma() {
+        assert_eq!(
+            parse_batch("https://example.com/a,b\n").unwrap(),
+            vec![target("https://example.com/a,b", None)]
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::batch::parse_batch;
+
+    #[test]
+    fn malformed_json() {
+        assert_eq!(parse_batch("[\"https://example.com/\""), None);
+    }
+
+    #[test]
+    fn json_without_target() {
+        assert_eq!(parse_batch(r#"[{ "output": "example.html" }]"#), None);
+    }
+
+    #[test]
+    fn csv_without_target() {
+        assert_eq!(parse_batch("target,output\n,example.html\n"), None);
+    }
+}
@@ -0,0 +1,149 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::env;
+    use std::fs;
+    use std::process;
+
+    use monolith::cache::{Cache, CacheHeaders};
+
+    // Assets only go through freshness checks once they're cached by a previous run
+    pub fn cache_from_previous_run(name: &str, headers: CacheHeaders) -> bool {
+        let directory = env::temp_dir().join(format!("monolith-cache-{}-{}", name, process::id()));
+        let directory_path: String = directory.display().to_string();
+
+        Cache::open(&directory_path).unwrap().set_response(
+            "https://example.com/",
+            &b"Hello".to_vec(),
+            "text/html".to_string(),
+            "".to_string(),
+            None,
+            headers,
+        );
+        let is_fresh: bool = Cache::open(&directory_path)
+            .unwrap()
+            .is_fresh("https://example.com/");
+
+        fs::remove_dir_all(&directory).unwrap();
+
+        is_fresh
+    }
+
+    #[test]
+    fn current_run() {
+        let mut cache: Cache = Cache::new(0, None);
+        cache.set(
+            "https://example.com/",
+            &b"Hello".to_vec(),
+            "text/html".to_string(),
+            "".to_string(),
+        );
+
+        assert!(cache.is_fresh("https://example.com/"));
+    }
+
+    #[test]
+    fn max_age() {
+        assert!(cache_from_previous_run(
+            "max-age",
+            CacheHeaders {
+                cache_control: Some("public, max-age=3600".to_string()),
+                ..Default::default()
+            }
+        ));
+    }
+
+    #[test]
+    fn expires() {
+        assert!(cache_from_previous_run(
+            "expires",
+            CacheHeaders {
+                expires: Some("Fri, 01 Jan 2100 00:00:00 GMT".to_string()),
+                ..Default::default()
+            }
+        ));
+    }
+
+    #[test]
+    fn not_modified_in_a_while() {
+        assert!(cache_from_previous_run(
+            "last-modified",
+            CacheHeaders {
+                last_modified: Some("Sat, 01 Jan 2000 00:00:00 GMT".to_string()),
+                ..Default::default()
+            }
+        ));
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::cache::{Cache, CacheHeaders};
+
+    use super::passing::cache_from_previous_run;
+
+    #[test]
+    fn not_cached() {
+        let cache: Cache = Cache::new(0, None);
+
+        assert!(!cache.is_fresh("https://example.com/"));
+    }
+
+    #[test]
+    fn max_age_zero() {
+        assert!(!cache_from_previous_run(
+            "max-age-zero",
+            CacheHeaders {
+                cache_control: Some("max-age=0".to_string()),
+                expires: Some("Fri, 01 Jan 2100 00:00:00 GMT".to_string()),
+                ..Default::default()
+            }
+        ));
+    }
+
+    #[test]
+    fn no_cache() {
+        assert!(!cache_from_previous_run(
+            "no-cache",
+            CacheHeaders {
+                cache_control: Some("no-cache, max-age=3600".to_string()),
+                ..Default::default()
+            }
+        ));
+    }
+
+    #[test]
+    fn expired() {
+        assert!(!cache_from_previous_run(
+            "expired",
+            CacheHeaders {
+                expires: Some("Sat, 01 Jan 2000 00:00:00 GMT".to_string()),
+                ..Default::default()
+            }
+        ));
+    }
+
+    #[test]
+    fn no_freshness_information() {
+        assert!(!cache_from_previous_run(
+            "no-freshness",
+            CacheHeaders {
+                etag: Some("\"1\"".to_string()),
+                ..Default::default()
+            }
+        ));
+    }
+}
@@ -0,0 +1,3 @@
+mod is_fresh;
+mod open;
+mod prune;
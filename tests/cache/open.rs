@@ -0,0 +1,122 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::env;
+    use std::fs;
+    use std::process;
+
+    use monolith::cache::{Cache, CacheHeaders};
+
+    #[test]
+    fn persists_between_runs() {
+        let directory = env::temp_dir().join(format!("monolith-cache-persists-{}", process::id()));
+        let directory_path: String = directory.display().to_string();
+
+        {
+            let mut cache: Cache = Cache::open(&directory_path).unwrap();
+            cache.set_response(
+                "https://example.com/old.css",
+                &b"body { color: red; }".to_vec(),
+                "text/css".to_string(),
+                "UTF-8".to_string(),
+                Some("https://example.com/style.css".to_string()),
+                CacheHeaders {
+                    etag: Some("\"1\"".to_string()),
+                    last_modified: Some("Sat, 01 Jan 2000 00:00:00 GMT".to_string()),
+                    ..Default::default()
+                },
+            );
+        }
+
+        let cache: Cache = Cache::open(&directory_path).unwrap();
+        assert!(cache.contains_key("https://example.com/old.css"));
+        assert_eq!(
+            cache.get("https://example.com/old.css").unwrap(),
+            (
+                b"body { color: red; }".to_vec(),
+                "text/css".to_string(),
+                "UTF-8".to_string()
+            )
+        );
+        assert_eq!(
+            cache.get_url("https://example.com/old.css"),
+            Some("https://example.com/style.css".to_string())
+        );
+        assert_eq!(
+            cache.get_validators("https://example.com/old.css"),
+            vec![
+                ("If-None-Match".to_string(), "\"1\"".to_string()),
+                (
+                    "If-Modified-Since".to_string(),
+                    "Sat, 01 Jan 2000 00:00:00 GMT".to_string()
+                ),
+            ]
+        );
+
+        drop(cache);
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn no_store() {
+        let directory = env::temp_dir().join(format!("monolith-cache-no-store-{}", process::id()));
+        let directory_path: String = directory.display().to_string();
+
+        {
+            let mut cache: Cache = Cache::open(&directory_path).unwrap();
+            cache.set_response(
+                "https://example.com/",
+                &b"Hello".to_vec(),
+                "text/html".to_string(),
+                "".to_string(),
+                None,
+                CacheHeaders {
+                    cache_control: Some("private, no-store".to_string()),
+                    ..Default::default()
+                },
+            );
+
+            // Still available during the current run
+            assert!(cache.contains_key("https://example.com/"));
+            assert!(cache.is_fresh("https://example.com/"));
+        }
+
+        let cache: Cache = Cache::open(&directory_path).unwrap();
+        assert!(!cache.contains_key("https://example.com/"));
+
+        drop(cache);
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use std::env;
+    use std::fs;
+    use std::process;
+
+    use monolith::cache::Cache;
+
+    #[test]
+    fn not_a_directory() {
+        let file = env::temp_dir().join(format!("monolith-cache-file-{}", process::id()));
+        fs::write(&file, "").unwrap();
+
+        assert!(Cache::open(&file.display().to_string()).is_none());
+
+        fs::remove_file(&file).unwrap();
+    }
+}
@@ -0,0 +1,73 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::env;
+    use std::fs;
+    use std::process;
+    use std::time::Duration;
+
+    use monolith::cache::Cache;
+
+    fn set(cache: &mut Cache, key: &str, data: &[u8]) {
+        cache.set(
+            key,
+            &data.to_vec(),
+            "text/plain".to_string(),
+            "".to_string(),
+        );
+    }
+
+    #[test]
+    fn by_size() {
+        let directory = env::temp_dir().join(format!("monolith-cache-prune-{}", process::id()));
+        let directory_path: String = directory.display().to_string();
+
+        {
+            let mut cache: Cache = Cache::open(&directory_path).unwrap();
+            set(&mut cache, "https://example.com/1.txt", b"12345");
+            set(&mut cache, "https://example.com/2.txt", b"12345");
+            set(&mut cache, "https://example.com/3.txt", b"12345");
+
+            // Oldest assets go first
+            assert_eq!(cache.prune(Some(10), None), 1);
+            assert!(!cache.contains_key("https://example.com/1.txt"));
+            assert!(cache.contains_key("https://example.com/2.txt"));
+            assert!(cache.contains_key("https://example.com/3.txt"));
+        }
+
+        let cache: Cache = Cache::open(&directory_path).unwrap();
+        assert!(!cache.contains_key("https://example.com/1.txt"));
+        assert!(cache.contains_key("https://example.com/2.txt"));
+
+        drop(cache);
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn by_age() {
+        let mut cache: Cache = Cache::new(0, None);
+        set(&mut cache, "https://example.com/1.txt", b"12345");
+
+        assert_eq!(cache.prune(None, Some(Duration::from_secs(3600))), 0);
+        assert!(cache.contains_key("https://example.com/1.txt"));
+
+        std::thread::sleep(Duration::from_millis(10));
+        assert_eq!(cache.prune(None, Some(Duration::from_millis(1))), 1);
+        assert!(!cache.contains_key("https://example.com/1.txt"));
+    }
+
+    #[test]
+    fn no_limits() {
+        let mut cache: Cache = Cache::new(0, None);
+        set(&mut cache, "https://example.com/1.txt", b"12345");
+
+        assert_eq!(cache.prune(None, None), 0);
+        assert!(cache.contains_key("https://example.com/1.txt"));
+    }
+}
@@ -0,0 +1,133 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use assert_cmd::prelude::*;
+    use std::env;
+    use std::fs;
+    use std::path::MAIN_SEPARATOR;
+    use std::process::{self, Command};
+
+    #[test]
+    fn local_file_target() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let output_directory = env::temp_dir().join(format!("monolith-{}", process::id()));
+        let output_path = output_directory.join("page.html");
+        fs::create_dir_all(&output_directory).unwrap();
+        let out = cmd
+            .arg("-M")
+            .arg("--assets-dir")
+            .arg("page_files")
+            .arg("-o")
+            .arg(&output_path)
+            .arg(format!(
+                "tests{s}_data_{s}assets_dir{s}index.html",
+                s = MAIN_SEPARATOR
+            ))
+            .output()
+            .unwrap();
+
+        // Document should refer to assets within the assets directory
+        assert_eq!(
+            fs::read_to_string(&output_path).unwrap(),
+            r#"<html><head><link rel="stylesheet" href="page_files/05dd1b449fc5b8a1074a4a1cdabd6767.css"><meta name="robots" content="none"></meta></head><body><img src="page_files/42ef5241bb91f857a6645de76b5247a3.svg">
+</body></html>
+"#
+        );
+
+        // Stylesheet should refer to assets located next to it
+        assert_eq!(
+            fs::read_to_string(
+                output_directory
+                    .join("page_files")
+                    .join("05dd1b449fc5b8a1074a4a1cdabd6767.css")
+            )
+            .unwrap(),
+            "body { background: url(\"42ef5241bb91f857a6645de76b5247a3.svg\"); }\n"
+        );
+        assert_eq!(
+            fs::read_dir(output_directory.join("page_files"))
+                .unwrap()
+                .count(),
+            2
+        );
+
+        // Exit code should be 0
+        out.assert().code(0);
+
+        fs::remove_dir_all(&output_directory).unwrap();
+    }
+
+    #[test]
+    fn absolute_path_with_special_characters() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let temp_directory = env::temp_dir().join(format!("monolith-absolute-{}", process::id()));
+        let output_directory = temp_directory.join("pages");
+        let output_path = output_directory.join("page.html");
+        let assets_directory = temp_directory.join("page files #1");
+        fs::create_dir_all(&output_directory).unwrap();
+        let out = cmd
+            .arg("-M")
+            .arg("--assets-dir")
+            .arg(&assets_directory)
+            .arg("-o")
+            .arg(&output_path)
+            .arg(format!(
+                "tests{s}_data_{s}assets_dir{s}index.html",
+                s = MAIN_SEPARATOR
+            ))
+            .output()
+            .unwrap();
+
+        // Document should refer to assets relative to itself, using encoded path
+        assert_eq!(
+            fs::read_to_string(&output_path).unwrap(),
+            r#"<html><head><link rel="stylesheet" href="../page%20files%20%231/05dd1b449fc5b8a1074a4a1cdabd6767.css"><meta name="robots" content="none"></meta></head><body><img src="../page%20files%20%231/42ef5241bb91f857a6645de76b5247a3.svg">
+</body></html>
+"#
+        );
+        assert_eq!(fs::read_dir(&assets_directory).unwrap().count(), 2);
+
+        // Exit code should be 0
+        out.assert().code(0);
+
+        fs::remove_dir_all(&temp_directory).unwrap();
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use assert_cmd::prelude::*;
+    use std::env;
+    use std::process::Command;
+
+    #[test]
+    fn conflicting_output_format() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("--assets-dir")
+            .arg("page_files")
+            .arg("--mhtml")
+            .arg("data:text/html,Hello")
+            .output()
+            .unwrap();
+
+        // STDOUT should be empty
+        assert_eq!(String::from_utf8_lossy(&out.stdout), "");
+
+        // Exit code should be 2
+        out.assert().code(2);
+    }
+}
@@ -0,0 +1,217 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use assert_cmd::prelude::*;
+    use std::env;
+    use std::fs;
+    use std::process::{self, Command};
+
+    #[test]
+    fn json_list() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let output_directory = env::temp_dir().join(format!("monolith-batch-{}", process::id()));
+        let batch_file_path = output_directory.join("targets.json");
+        fs::create_dir_all(&output_directory).unwrap();
+        fs::write(
+            &batch_file_path,
+            format!(
+                r#"[
+                    {{ "target": "data:text/html,Hello", "output": {:?} }},
+                    {{ "target": "data:text/html,World", "output": {:?} }}
+                ]"#,
+                output_directory.join("hello.html"),
+                output_directory.join("world.html")
+            ),
+        )
+        .unwrap();
+        let out = cmd
+            .arg("-M")
+            .arg("--batch")
+            .arg(&batch_file_path)
+            .output()
+            .unwrap();
+
+        // Every target should be saved into its own file
+        assert_eq!(
+            fs::read_to_string(output_directory.join("hello.html")).unwrap(),
+            "<html><head><meta name=\"robots\" content=\"none\"></meta></head><body>Hello</body></html>\n"
+        );
+        assert_eq!(
+            fs::read_to_string(output_directory.join("world.html")).unwrap(),
+            "<html><head><meta name=\"robots\" content=\"none\"></meta></head><body>World</body></html>\n"
+        );
+
+        // STDERR should contain summary
+        assert!(String::from_utf8_lossy(&out.stderr).contains("Saved 2 of 2 targets\n"));
+
+        // Exit code should be 0
+        out.assert().code(0);
+
+        fs::remove_dir_all(&output_directory).unwrap();
+    }
+
+    #[test]
+    fn archive_and_plain_targets() {
+        let cwd = env::current_dir().unwrap();
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let output_directory =
+            env::temp_dir().join(format!("monolith-batch-archive-{}", process::id()));
+        let batch_file_path = output_directory.join("targets.json");
+        fs::create_dir_all(&output_directory).unwrap();
+        fs::write(
+            &batch_file_path,
+            format!(
+                r#"[
+                    {{ "target": {:?}, "output": {:?} }},
+                    {{ "target": {:?}, "output": {:?} }}
+                ]"#,
+                cwd.join("tests/_data_/mhtml/page.mhtml"),
+                output_directory.join("archive.html"),
+                cwd.join("tests/_data_/basic/local-file.html"),
+                output_directory.join("plain.html")
+            ),
+        )
+        .unwrap();
+        let out = cmd
+            .arg("-M")
+            .arg("--batch")
+            .arg(&batch_file_path)
+            .output()
+            .unwrap();
+
+        // Archive of the first target shouldn't be where assets of the second one are looked for
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        assert!(stderr.contains("tests/_data_/basic/local-style.css\n"));
+        assert!(!stderr.contains("local-style.css (not found in archive)"));
+        assert!(stderr.contains("Saved 2 of 2 targets\n"));
+        assert!(fs::read_to_string(output_directory.join("plain.html"))
+            .unwrap()
+            .contains("data:text/css;base64,"));
+
+        // Exit code should be 0
+        out.assert().code(0);
+
+        fs::remove_dir_all(&output_directory).unwrap();
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use assert_cmd::prelude::*;
+    use std::env;
+    use std::fs;
+    use std::process::{self, Command};
+
+    #[test]
+    fn some_targets_missing() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let output_directory =
+            env::temp_dir().join(format!("monolith-batch-missing-{}", process::id()));
+        let batch_file_path = output_directory.join("targets.csv");
+        fs::create_dir_all(&output_directory).unwrap();
+        fs::write(
+            &batch_file_path,
+            format!(
+                "target,output\n\"data:text/html,Hello\",\"{}\"\nno-such-file.html,\"{}\"\n",
+                output_directory.join("hello.html").display(),
+                output_directory.join("missing.html").display()
+            ),
+        )
+        .unwrap();
+        let out = cmd
+            .arg("-M")
+            .arg("--batch")
+            .arg(&batch_file_path)
+            .output()
+            .unwrap();
+
+        // Targets which could be retrieved should still get saved
+        assert!(output_directory.join("hello.html").exists());
+        assert!(!output_directory.join("missing.html").exists());
+
+        // STDERR should list targets which failed
+        let stderr = String::from_utf8_lossy(&out.stderr);
+        assert!(stderr.contains("Saved 1 of 2 targets\n"));
+        assert!(stderr.contains("Failed: no-such-file.html\n"));
+
+        // Exit code should be 1
+        out.assert().code(1);
+
+        fs::remove_dir_all(&output_directory).unwrap();
+    }
+
+    #[test]
+    fn missing_batch_file() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd.arg("--batch").arg("no-such-file.txt").output().unwrap();
+
+        // STDOUT should be empty
+        assert_eq!(String::from_utf8_lossy(&out.stdout), "");
+
+        // Exit code should be 1
+        out.assert().code(1);
+    }
+
+    #[test]
+    fn same_output_for_every_target() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let output_directory =
+            env::temp_dir().join(format!("monolith-batch-same-output-{}", process::id()));
+        let batch_file_path = output_directory.join("targets.txt");
+        fs::create_dir_all(&output_directory).unwrap();
+        fs::write(
+            &batch_file_path,
+            "data:text/html,Hello\ndata:text/html,World\n",
+        )
+        .unwrap();
+        let out = cmd
+            .arg("--batch")
+            .arg(&batch_file_path)
+            .arg("-o")
+            .arg(output_directory.join("out.html"))
+            .output()
+            .unwrap();
+
+        // Nothing should be saved
+        assert!(!output_directory.join("out.html").exists());
+
+        // STDERR should explain what's wrong with the output path
+        assert!(String::from_utf8_lossy(&out.stderr)
+            .contains("has to contain %title% to save multiple targets"));
+
+        // Exit code should be 1
+        out.assert().code(1);
+
+        fs::remove_dir_all(&output_directory).unwrap();
+    }
+
+    #[test]
+    fn batch_along_with_target() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("--batch")
+            .arg("-")
+            .arg("data:text/html,Hello")
+            .output()
+            .unwrap();
+
+        // STDOUT should be empty
+        assert_eq!(String::from_utf8_lossy(&out.stdout), "");
+
+        // Exit code should be 2
+        out.assert().code(2);
+    }
+}
@@ -0,0 +1,120 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use assert_cmd::prelude::*;
+    use std::env;
+    use std::path::MAIN_SEPARATOR;
+    use std::process::Command;
+
+    #[test]
+    fn har_target_input() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let cwd_normalized: String = env::current_dir()
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .replace("\\", "/");
+        let out = cmd
+            .arg("-M")
+            .arg(format!(
+                "tests{s}_data_{s}har{s}page.har",
+                s = MAIN_SEPARATOR
+            ))
+            .output()
+            .unwrap();
+        let file_url_protocol: &str = if cfg!(windows) { "file:///" } else { "file://" };
+
+        // STDERR should list the HAR file, followed by recorded responses
+        assert_eq!(
+            String::from_utf8_lossy(&out.stderr),
+            format!(
+                r#"{file}{cwd}/tests/_data_/har/page.har
+https://example.com/style.css (from archive)
+https://example.com/1.png (from archive)
+https://example.com/2.png (not found in archive)
+https://example.com/favicon.ico (not found in archive)
+"#,
+                file = file_url_protocol,
+                cwd = cwd_normalized
+            )
+        );
+
+        // STDOUT should contain the main document, saved using its original charset
+        assert_eq!(
+            String::from_utf8_lossy(&out.stdout),
+            "<html><head><meta charset=\"windows-1252\"><link rel=\"stylesheet\" href=\"data:text/css;base64,Ym9keXt9\"><meta name=\"robots\" content=\"none\"></meta></head><body>caf\u{FFFD} <img src=\"data:image/png;base64,iVBORw0KGgo=\"><img src=\"https://example.com/2.png\">\n</body></html>\n"
+        );
+
+        // Exit code should be 0
+        out.assert().code(0);
+    }
+
+    #[test]
+    fn har_target_input_with_document_url() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("-M")
+            .arg("-q")
+            .arg("--document")
+            .arg("https://example.com/style.css")
+            .arg(format!(
+                "tests{s}_data_{s}har{s}page.har",
+                s = MAIN_SEPARATOR
+            ))
+            .output()
+            .unwrap();
+
+        // STDOUT should contain the picked document
+        assert_eq!(String::from_utf8_lossy(&out.stdout), "body{}");
+
+        // Exit code should be 0
+        out.assert().code(0);
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use assert_cmd::prelude::*;
+    use std::env;
+    use std::path::MAIN_SEPARATOR;
+    use std::process::Command;
+
+    #[test]
+    fn document_url_missing_from_har() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("-M")
+            .arg("--document")
+            .arg("https://example.com/2.png")
+            .arg(format!(
+                "tests{s}_data_{s}har{s}page.har",
+                s = MAIN_SEPARATOR
+            ))
+            .output()
+            .unwrap();
+
+        // STDERR should contain error description
+        assert!(String::from_utf8_lossy(&out.stderr).ends_with(
+            "Error: could not find document \"https://example.com/2.png\" within archive\n"
+        ));
+
+        // STDOUT should be empty
+        assert_eq!(String::from_utf8_lossy(&out.stdout), "");
+
+        // Exit code should be 1
+        out.assert().code(1);
+    }
+}
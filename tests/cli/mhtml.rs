@@ -0,0 +1,58 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use assert_cmd::prelude::*;
+    use std::env;
+    use std::path::MAIN_SEPARATOR;
+    use std::process::Command;
+
+    #[test]
+    fn mhtml_target_input() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let cwd_normalized: String = env::current_dir()
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .replace("\\", "/");
+        let out = cmd
+            .arg("-M")
+            .arg(format!(
+                "tests{s}_data_{s}mhtml{s}page.mhtml",
+                s = MAIN_SEPARATOR
+            ))
+            .output()
+            .unwrap();
+        let file_url_protocol: &str = if cfg!(windows) { "file:///" } else { "file://" };
+
+        // STDERR should list the archive itself, followed by its parts
+        assert_eq!(
+            String::from_utf8_lossy(&out.stderr),
+            format!(
+                r#"{file}{cwd}/tests/_data_/mhtml/page.mhtml
+https://example.com/style.css (from archive)
+https://example.com/images/pixel.png (from archive)
+cid:frame-2@mhtml.blink (from archive)
+https://example.com/favicon.ico (not found in archive)
+"#,
+                file = file_url_protocol,
+                cwd = cwd_normalized
+            )
+        );
+
+        // STDOUT should contain the root part with all other parts embedded into it
+        assert_eq!(
+            String::from_utf8_lossy(&out.stdout),
+            r#"<html><head><link rel="stylesheet" href="data:text/css;base64,Ym9keSB7IGNvbG9yOiByZWQ7IH0="><meta name="robots" content="none"></meta></head><body><img src="data:image/png;base64,iVBORw0KGgp4eHh4"><iframe src="data:text/html;base64,PGh0bWw+PGhlYWQ+PC9oZWFkPjxib2R5PkZyYW1lPC9ib2R5PjwvaHRtbD4="></iframe></body></html>
+"#
+        );
+
+        // Exit code should be 0
+        out.assert().code(0);
+    }
+}
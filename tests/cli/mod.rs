@@ -1,6 +1,11 @@
+mod assets_dir;
 mod base_url;
 mod basic;
+mod batch;
 mod data_url;
+mod har;
 mod local_files;
+mod mhtml;
 mod noscript;
 mod unusual_encodings;
+mod warc;
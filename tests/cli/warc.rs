@@ -0,0 +1,120 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use assert_cmd::prelude::*;
+    use std::env;
+    use std::path::MAIN_SEPARATOR;
+    use std::process::Command;
+
+    #[test]
+    fn replay_from_warc_file() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("-M")
+            .arg("--from-warc")
+            .arg(format!(
+                "tests{s}_data_{s}warc{s}page.warc",
+                s = MAIN_SEPARATOR
+            ))
+            .arg("https://example.com/")
+            .output()
+            .unwrap();
+
+        // STDERR should list assets found in the archive, as well as missing ones
+        assert_eq!(
+            String::from_utf8_lossy(&out.stderr),
+            r#"https://example.com/ (from archive)
+https://example.com/old.css -> https://example.com/style.css (from archive)
+https://example.com/missing.png (not found in archive)
+"#
+        );
+
+        // STDOUT should contain the document with recorded assets embedded into it
+        assert_eq!(
+            String::from_utf8_lossy(&out.stdout),
+            r#"<html><head><link rel="stylesheet" href="data:text/css;base64,Ym9keXt9"><link rel="icon" href="data:text/plain;base64,"><meta name="robots" content="none"></meta></head><body><img src="https://example.com/missing.png">
+</body></html>
+"#
+        );
+
+        // Exit code should be 0
+        out.assert().code(0);
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use assert_cmd::prelude::*;
+    use std::env;
+    use std::path::MAIN_SEPARATOR;
+    use std::process::Command;
+
+    #[test]
+    fn target_missing_from_warc_file() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("-M")
+            .arg("--from-warc")
+            .arg(format!(
+                "tests{s}_data_{s}warc{s}page.warc",
+                s = MAIN_SEPARATOR
+            ))
+            .arg("https://example.com/other.html")
+            .output()
+            .unwrap();
+
+        // STDERR should contain error description
+        assert_eq!(
+            String::from_utf8_lossy(&out.stderr),
+            r#"https://example.com/other.html (not found in archive)
+Error: could not find "https://example.com/other.html" within archive
+"#
+        );
+
+        // STDOUT should be empty
+        assert_eq!(String::from_utf8_lossy(&out.stdout), "");
+
+        // Exit code should be 1
+        out.assert().code(1);
+    }
+
+    #[test]
+    fn not_a_warc_file() {
+        let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+        let out = cmd
+            .arg("-M")
+            .arg("--from-warc")
+            .arg(format!(
+                "tests{s}_data_{s}basic{s}local-file.html",
+                s = MAIN_SEPARATOR
+            ))
+            .arg("https://example.com/")
+            .output()
+            .unwrap();
+
+        // STDERR should contain error description
+        assert_eq!(
+            String::from_utf8_lossy(&out.stderr),
+            format!(
+                "could not parse specified WARC file \"tests{s}_data_{s}basic{s}local-file.html\"\n",
+                s = MAIN_SEPARATOR
+            )
+        );
+
+        // Exit code should be 1
+        out.assert().code(1);
+    }
+}
@@ -0,0 +1,53 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::core::detect_file_extension_by_media_type;
+
+    #[test]
+    fn known_media_types() {
+        assert_eq!(detect_file_extension_by_media_type("text/css"), "css");
+        assert_eq!(detect_file_extension_by_media_type("image/jpeg"), "jpg");
+        assert_eq!(detect_file_extension_by_media_type("image/svg+xml"), "svg");
+        assert_eq!(detect_file_extension_by_media_type("font/woff2"), "woff2");
+        assert_eq!(
+            detect_file_extension_by_media_type("application/javascript"),
+            "js"
+        );
+    }
+
+    #[test]
+    fn uppercase_media_type() {
+        assert_eq!(detect_file_extension_by_media_type("TEXT/HTML"), "html");
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::core::detect_file_extension_by_media_type;
+
+    #[test]
+    fn unknown_media_type() {
+        assert_eq!(
+            detect_file_extension_by_media_type("application/octet-stream"),
+            ""
+        );
+    }
+
+    #[test]
+    fn empty_media_type() {
+        assert_eq!(detect_file_extension_by_media_type(""), "");
+    }
+}
@@ -101,4 +101,50 @@ mod passing {
 
         assert_eq!(final_destination, r#"Title.mht"#);
     }
+
+    #[test]
+    fn file_name_extension_zim() {
+        let final_destination =
+            format_output_path("%title%.%extension%", "Title", MonolithOutputFormat::ZIM);
+
+        assert_eq!(final_destination, r#"Title.zim"#);
+    }
+
+    #[test]
+    fn file_name_extension_directory() {
+        let final_destination = format_output_path(
+            "%title%.%extension%",
+            "Title",
+            MonolithOutputFormat::Directory,
+        );
+
+        assert_eq!(final_destination, r#"Title.html"#);
+    }
+
+    #[test]
+    fn file_name_extension_epub() {
+        let final_destination =
+            format_output_path("%title%.%ext%", "Title", MonolithOutputFormat::EPUB);
+
+        assert_eq!(final_destination, r#"Title.epub"#);
+    }
+
+    #[test]
+    fn file_name_extension_zip() {
+        let final_destination =
+            format_output_path("%title%.%extension%", "Title", MonolithOutputFormat::ZIP);
+
+        assert_eq!(final_destination, r#"Title.zip"#);
+    }
+
+    #[test]
+    fn file_name_extension_webarchive() {
+        let final_destination = format_output_path(
+            "%title%.%extension%",
+            "Title",
+            MonolithOutputFormat::WebArchive,
+        );
+
+        assert_eq!(final_destination, r#"Title.webarchive"#);
+    }
 }
@@ -0,0 +1,37 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::core::get_header;
+
+    #[test]
+    fn any_case() {
+        let headers: Vec<(String, String)> = vec![
+            ("content-type".to_string(), "text/html".to_string()),
+            ("Location".to_string(), "/new".to_string()),
+        ];
+
+        assert_eq!(get_header(&headers, "Content-Type"), Some("text/html"));
+        assert_eq!(get_header(&headers, "LOCATION"), Some("/new"));
+    }
+
+    #[test]
+    fn first_one_found() {
+        let headers: Vec<(String, String)> = vec![
+            ("Set-Cookie".to_string(), "a=1".to_string()),
+            ("Set-Cookie".to_string(), "b=2".to_string()),
+        ];
+
+        assert_eq!(get_header(&headers, "Set-Cookie"), Some("a=1"));
+    }
+
+    #[test]
+    fn missing() {
+        assert_eq!(get_header(&[], "Content-Type"), None);
+    }
+}
@@ -1,4 +1,6 @@
+mod detect_file_extension_by_media_type;
 mod detect_media_type;
 mod format_output_path;
+mod get_header;
 mod options;
 mod parse_content_type;
@@ -0,0 +1,84 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::core::MonolithOutputFormat;
+    use monolith::crawl::create_page_file_name;
+    use monolith::url::Url;
+
+    #[test]
+    fn host_only() {
+        assert_eq!(
+            create_page_file_name(
+                &Url::parse("https://example.com/").unwrap(),
+                MonolithOutputFormat::HTML
+            ),
+            "example.com.html"
+        );
+    }
+
+    #[test]
+    fn port_and_path() {
+        assert_eq!(
+            create_page_file_name(
+                &Url::parse("http://localhost:8080/docs/intro.html").unwrap(),
+                MonolithOutputFormat::HTML
+            ),
+            "localhost_8080_docs_intro.html"
+        );
+    }
+
+    #[test]
+    fn trailing_slash() {
+        assert_eq!(
+            create_page_file_name(
+                &Url::parse("https://example.com/docs/").unwrap(),
+                MonolithOutputFormat::HTML
+            ),
+            "example.com_docs.html"
+        );
+    }
+
+    #[test]
+    fn query() {
+        let first = create_page_file_name(
+            &Url::parse("https://example.com/search?q=1").unwrap(),
+            MonolithOutputFormat::HTML,
+        );
+        let second = create_page_file_name(
+            &Url::parse("https://example.com/search?q=2").unwrap(),
+            MonolithOutputFormat::HTML,
+        );
+
+        assert!(first.starts_with("example.com_search_"));
+        assert_eq!(first.len(), "example.com_search_12345678.html".len());
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn unusual_characters() {
+        assert_eq!(
+            create_page_file_name(
+                &Url::parse("https://example.com/a%20b/c~d.php").unwrap(),
+                MonolithOutputFormat::HTML
+            ),
+            "example.com_a_20b_c_d.php.html"
+        );
+    }
+
+    #[test]
+    fn mhtml() {
+        assert_eq!(
+            create_page_file_name(
+                &Url::parse("https://example.com/about").unwrap(),
+                MonolithOutputFormat::MHTML
+            ),
+            "example.com_about.mhtml"
+        );
+    }
+}
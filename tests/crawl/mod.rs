@@ -0,0 +1 @@
+mod create_page_file_name;
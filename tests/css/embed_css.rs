@@ -18,7 +18,7 @@ mod passing {
     fn empty_input() {
         let document_url: Url = Url::parse("data:,").unwrap();
         let options = MonolithOptions::default();
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
 
         assert_eq!(css::embed_css(&mut session, &document_url, ""), "");
     }
@@ -27,7 +27,7 @@ mod passing {
     fn trim_if_empty() {
         let document_url: Url = Url::parse("https://doesntmatter.local/").unwrap();
         let options = MonolithOptions::default();
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
 
         assert_eq!(
             css::embed_css(&mut session, &document_url, "\t     \t   "),
@@ -41,7 +41,7 @@ mod passing {
         let mut options = MonolithOptions::default();
         options.no_images = true;
         options.silent = true;
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
 
         const STYLE: &str = "/* border: none;*/\
             background-image: url(https://somewhere.com/bg.png); \
@@ -72,7 +72,7 @@ mod passing {
         let mut options = MonolithOptions::default();
         options.no_images = true;
         options.silent = true;
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
 
         const STYLE: &str = "/* border: none;*/\
             background-image: url('https://somewhere.com/bg.png'); \
@@ -102,7 +102,7 @@ mod passing {
         let document_url: Url = Url::parse("file:///").unwrap();
         let mut options = MonolithOptions::default();
         options.silent = true;
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
 
         const CSS: &str = "\
             #id.class-name:not(:nth-child(3n+0)) {\n  \
@@ -120,7 +120,7 @@ mod passing {
         let document_url: Url = Url::parse("https://doesntmatter.local/").unwrap();
         let mut options = MonolithOptions::default();
         options.silent = true;
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
 
         const CSS: &str = "\
             [data-value] {
@@ -160,7 +160,7 @@ mod passing {
         let document_url: Url = Url::parse("https://doesntmatter.local/").unwrap();
         let mut options = MonolithOptions::default();
         options.silent = true;
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
 
         const CSS: &str = "\
             @charset 'UTF-8';\n\
@@ -187,7 +187,7 @@ mod passing {
         let document_url: Url = Url::parse("https://doesntmatter.local/").unwrap();
         let mut options = MonolithOptions::default();
         options.silent = true;
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
 
         const CSS: &str = "\
             body {\n    \
@@ -207,7 +207,7 @@ mod passing {
         let document_url: Url = Url::parse("https://doesntmatter.local/").unwrap();
         let mut options = MonolithOptions::default();
         options.silent = true;
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
 
         const CSS: &str = "\
             div {\n    \
@@ -225,7 +225,7 @@ mod passing {
         let document_url: Url = Url::parse("https://doesntmatter.local/").unwrap();
         let mut options = MonolithOptions::default();
         options.silent = true;
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
 
         const CSS: &str = "\
             .is\\:good:hover {\n    \
@@ -246,7 +246,7 @@ mod passing {
         let mut options = MonolithOptions::default();
         options.no_fonts = true;
         options.silent = true;
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
 
         const CSS: &str = "\
             @font-face {\n    \
@@ -289,7 +289,7 @@ mod passing {
         let document_url: Url = Url::parse("data:,").unwrap();
         let mut options = MonolithOptions::default();
         options.silent = true;
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
 
         const CSS: &str = "\
             #language a[href=\"#translations\"]:before {\n\
@@ -310,7 +310,7 @@ mod passing {
         let document_url: Url = Url::parse("data:,").unwrap();
         let mut options = MonolithOptions::default();
         options.silent = true;
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
 
         const CSS: &str = "\
             div#p>svg>foreignObject>section:not(\\9) {\n\
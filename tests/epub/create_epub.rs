@@ -0,0 +1,183 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::io::Read;
+
+    use flate2::read::DeflateDecoder;
+
+    use monolith::core::{
+        create_monolithic_document_from_data, MonolithOptions, MonolithOutputFormat,
+    };
+    use monolith::epub::create_epub;
+    use monolith::html::Heading;
+    use monolith::session::{EmbeddedAsset, Session};
+    use monolith::source::MemorySource;
+    use monolith::url::Url;
+
+    fn read_u16(zip: &[u8], position: usize) -> usize {
+        u16::from_le_bytes(zip[position..position + 2].try_into().unwrap()) as usize
+    }
+
+    // Returns path and decompressed contents of every file
+    fn read_files(zip: &[u8]) -> Vec<(String, String)> {
+        let mut files = vec![];
+        let mut position = 0;
+
+        while zip[position..].starts_with(b"PK\x03\x04") {
+            let method = read_u16(zip, position + 8);
+            let compressed_size =
+                u32::from_le_bytes(zip[position + 18..position + 22].try_into().unwrap()) as usize;
+            let path_length = read_u16(zip, position + 26);
+            let path = String::from_utf8(zip[position + 30..position + 30 + path_length].to_vec())
+                .unwrap();
+            let contents = &zip[position + 30 + path_length..][..compressed_size];
+
+            let mut data = vec![];
+            if method == 8 {
+                DeflateDecoder::new(contents)
+                    .read_to_end(&mut data)
+                    .unwrap();
+            } else {
+                data = contents.to_vec();
+            }
+
+            files.push((path, String::from_utf8_lossy(&data).to_string()));
+            position += 30 + path_length + compressed_size;
+        }
+
+        files
+    }
+
+    fn document() -> EmbeddedAsset {
+        EmbeddedAsset {
+            url: Url::parse("https://example.com/").unwrap(),
+            media_type: "application/xhtml+xml".to_string(),
+            charset: "UTF-8".to_string(),
+            data: br#"<html xmlns="http://www.w3.org/1999/xhtml"><body><h1 id="a">A &amp; B</h1></body></html>"#.to_vec(),
+        }
+    }
+
+    #[test]
+    fn layout() {
+        let asset = EmbeddedAsset {
+            url: Url::parse("https://example.com/1.png").unwrap(),
+            media_type: "image/png".to_string(),
+            charset: "".to_string(),
+            data: vec![],
+        };
+        let epub = create_epub(&document(), &[asset], Some("Title".to_string()), &[]);
+        let files = read_files(&epub);
+
+        // Mimetype has to come first, and be stored uncompressed
+        assert_eq!(read_u16(&epub, 8), 0);
+        assert_eq!(
+            files
+                .iter()
+                .map(|(path, _)| path.as_str())
+                .collect::<Vec<&str>>(),
+            vec![
+                "mimetype",
+                "META-INF/container.xml",
+                "EPUB/package.opf",
+                "EPUB/nav.xhtml",
+                "EPUB/toc.ncx",
+                "EPUB/index.xhtml",
+                "EPUB/e3b0c44298fc1c149afbf4c8996fb924.png",
+            ]
+        );
+        assert_eq!(files[0].1, "application/epub+zip");
+        assert!(files[1].1.contains(r#"full-path="EPUB/package.opf""#));
+
+        let package = &files[2].1;
+        assert!(package.contains("<dc:title>Title</dc:title>"));
+        assert!(package.contains("<dc:source>https://example.com/</dc:source>"));
+        assert!(package.contains(
+            r#"<item id="asset-1" href="e3b0c44298fc1c149afbf4c8996fb924.png" media-type="image/png"/>"#
+        ));
+        assert!(package.contains(r#"<itemref idref="document"/>"#));
+
+        // Without any headings, table of contents points to the document itself
+        assert!(files[3]
+            .1
+            .contains(r#"<li><a href="index.xhtml">Title</a></li>"#));
+        assert_eq!(files[5].1.as_bytes(), document().data);
+    }
+
+    #[test]
+    fn scripts_and_frames() {
+        let mut options = MonolithOptions::default();
+        options.output_format = MonolithOutputFormat::EPUB;
+        options.silent = true;
+
+        let mut source = MemorySource::new();
+        source.insert(
+            &Url::parse("https://example.com/frame.html").unwrap(),
+            b"<script>alert(2)</script><p>Frame<br></p>".to_vec(),
+            "text/html",
+            "utf-8",
+        );
+
+        let mut session: Session = Session::new(None, None, options).unwrap();
+        session.add_source(source);
+        let (epub, _title) = create_monolithic_document_from_data(
+            &mut session,
+            b"<html><body><script>alert(1)</script><iframe src=\"frame.html\"></iframe></body></html>".to_vec(),
+            None,
+            Some("https://example.com/".to_string()),
+        )
+        .unwrap();
+        let files = read_files(&epub);
+
+        // JavaScript gets removed even though it wasn't asked for explicitly
+        assert!(files.iter().all(|(_, data)| !data.contains("<script")));
+
+        // Frames are stored as XHTML documents, same as the main one
+        let package: &str = &files
+            .iter()
+            .find(|(path, _)| path == "EPUB/package.opf")
+            .unwrap()
+            .1;
+        assert!(!package.contains("text/html"));
+        let (frame_path, frame) = files
+            .iter()
+            .find(|(path, _)| {
+                path.ends_with(".xhtml") && path != "EPUB/index.xhtml" && path != "EPUB/nav.xhtml"
+            })
+            .unwrap();
+        assert!(package.contains(&format!(
+            "href=\"{}\" media-type=\"application/xhtml+xml\"",
+            frame_path.trim_start_matches("EPUB/")
+        )));
+        assert!(frame.contains("<p>Frame<br></br></p>"));
+    }
+
+    #[test]
+    fn headings() {
+        let headings = vec![
+            Heading {
+                id: "a".to_string(),
+                title: "A & B".to_string(),
+            },
+            Heading {
+                id: "heading-2".to_string(),
+                title: "C".to_string(),
+            },
+        ];
+        let epub = create_epub(&document(), &[], None, &headings);
+        let files = read_files(&epub);
+
+        assert!(files[2].1.contains("<dc:title>Untitled</dc:title>"));
+        assert!(files[3].1.contains(
+            "<li><a href=\"index.xhtml#a\">A &amp; B</a></li>\n        <li><a href=\"index.xhtml#heading-2\">C</a></li>\n"
+        ));
+        assert!(files[4].1.contains(
+            "<navPoint id=\"navpoint-2\" playOrder=\"2\">\n      <navLabel><text>C</text></navLabel>\n      <content src=\"index.xhtml#heading-2\"/>"
+        ));
+    }
+}
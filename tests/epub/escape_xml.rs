@@ -0,0 +1,24 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::epub::escape_xml;
+
+    #[test]
+    fn special_characters() {
+        assert_eq!(
+            escape_xml(r#"<a href="?a=1&b='2'">"#),
+            "&lt;a href=&quot;?a=1&amp;b=&apos;2&apos;&quot;&gt;"
+        );
+    }
+
+    #[test]
+    fn plain_text() {
+        assert_eq!(escape_xml("Café"), "Café");
+    }
+}
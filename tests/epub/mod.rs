@@ -0,0 +1,2 @@
+mod create_epub;
+mod escape_xml;
@@ -0,0 +1,156 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use chrono::{TimeZone, Utc};
+    use serde_json::Value;
+    use std::time::Duration;
+
+    use monolith::har::create_har;
+    use monolith::session::HttpExchange;
+    use monolith::url::Url;
+
+    fn exchange(url: &str, status: u16) -> HttpExchange {
+        HttpExchange {
+            date: Utc.with_ymd_and_hms(2025, 1, 2, 3, 4, 5).unwrap(),
+            url: Url::parse(url).unwrap(),
+            request_headers: vec![
+                ("host".to_string(), "example.com".to_string()),
+                ("cookie".to_string(), "a=1; b=2".to_string()),
+            ],
+            remote_address: Some("93.184.216.34".to_string()),
+            http_version: "HTTP/1.1".to_string(),
+            status,
+            response_headers: vec![],
+            data: vec![],
+            wait: Duration::from_micros(12500),
+            receive: Duration::from_millis(3),
+            from_cache: false,
+            error: None,
+        }
+    }
+
+    fn parse(har: &[u8]) -> Value {
+        serde_json::from_slice(har).unwrap()
+    }
+
+    #[test]
+    fn no_exchanges() {
+        let har = parse(&create_har("Example", &[]));
+
+        assert_eq!(har["log"]["version"], "1.2");
+        assert_eq!(har["log"]["creator"]["name"], "monolith");
+        assert_eq!(har["log"]["pages"][0]["title"], "Example");
+        assert_eq!(har["log"]["entries"], Value::Array(vec![]));
+    }
+
+    #[test]
+    fn request() {
+        let har = parse(&create_har(
+            "Example",
+            &[exchange("https://example.com/style.css?v=1&w=2", 200)],
+        ));
+        let entry = &har["log"]["entries"][0];
+
+        assert_eq!(entry["pageref"], har["log"]["pages"][0]["id"]);
+        assert_eq!(entry["startedDateTime"], "2025-01-02T03:04:05.000Z");
+        assert_eq!(entry["serverIPAddress"], "93.184.216.34");
+        assert_eq!(entry["request"]["method"], "GET");
+        assert_eq!(
+            entry["request"]["url"],
+            "https://example.com/style.css?v=1&w=2"
+        );
+        assert_eq!(entry["request"]["headers"][1]["name"], "cookie");
+        assert_eq!(entry["request"]["headers"][1]["value"], "a=1; b=2");
+        assert_eq!(entry["request"]["cookies"][1]["name"], "b");
+        assert_eq!(entry["request"]["cookies"][1]["value"], "2");
+        assert_eq!(entry["request"]["queryString"][0]["name"], "v");
+        assert_eq!(entry["request"]["queryString"][1]["value"], "2");
+    }
+
+    #[test]
+    fn timings() {
+        let har = parse(&create_har("", &[exchange("https://example.com/", 200)]));
+        let entry = &har["log"]["entries"][0];
+
+        assert_eq!(entry["time"], 15.5);
+        assert_eq!(entry["timings"]["wait"], 12.5);
+        assert_eq!(entry["timings"]["receive"], 3.0);
+        assert_eq!(entry["timings"]["dns"], -1);
+    }
+
+    #[test]
+    fn text_response() {
+        let mut text_exchange = exchange("https://example.com/style.css", 200);
+        text_exchange.response_headers = vec![(
+            "Content-Type".to_string(),
+            "text/css; charset=utf-8".to_string(),
+        )];
+        text_exchange.data = b"body{}".to_vec();
+        let har = parse(&create_har("", &[text_exchange]));
+        let response = &har["log"]["entries"][0]["response"];
+
+        assert_eq!(response["status"], 200);
+        assert_eq!(response["statusText"], "OK");
+        assert_eq!(response["bodySize"], 6);
+        assert_eq!(response["content"]["size"], 6);
+        assert_eq!(response["content"]["mimeType"], "text/css; charset=utf-8");
+        assert_eq!(response["content"]["text"], "body{}");
+        assert!(response["content"].get("encoding").is_none());
+    }
+
+    #[test]
+    fn binary_response() {
+        let mut binary_exchange = exchange("https://example.com/1.png", 200);
+        binary_exchange.response_headers =
+            vec![("content-type".to_string(), "image/png".to_string())];
+        binary_exchange.data = b"\x89PNG".to_vec();
+        let har = parse(&create_har("", &[binary_exchange]));
+        let content = &har["log"]["entries"][0]["response"]["content"];
+
+        assert_eq!(content["text"], "iVBORw==");
+        assert_eq!(content["encoding"], "base64");
+    }
+
+    #[test]
+    fn redirect() {
+        let mut redirect_exchange = exchange("https://example.com/old/", 301);
+        redirect_exchange.response_headers = vec![("location".to_string(), "../new/".to_string())];
+        let har = parse(&create_har("", &[redirect_exchange]));
+        let response = &har["log"]["entries"][0]["response"];
+
+        assert_eq!(response["statusText"], "Moved Permanently");
+        assert_eq!(response["redirectURL"], "https://example.com/new/");
+    }
+
+    #[test]
+    fn cache_hit() {
+        let mut cached_exchange = exchange("https://example.com/1.png", 200);
+        cached_exchange.from_cache = true;
+        cached_exchange.data = b"\x89PNG".to_vec();
+        let har = parse(&create_har("", &[cached_exchange]));
+        let entry = &har["log"]["entries"][0];
+
+        assert_eq!(entry["_fromCache"], "disk");
+        assert_eq!(entry["cache"]["beforeRequest"]["hitCount"], 1);
+        assert_eq!(entry["response"]["bodySize"], 0);
+        assert_eq!(entry["response"]["content"]["size"], 4);
+    }
+
+    #[test]
+    fn failed_request() {
+        let mut failed_exchange = exchange("https://example.com/", 0);
+        failed_exchange.error = Some("blocked by domain rules".to_string());
+        let har = parse(&create_har("", &[failed_exchange]));
+        let entry = &har["log"]["entries"][0];
+
+        assert_eq!(entry["_error"], "blocked by domain rules");
+        assert_eq!(entry["response"]["status"], 0);
+        assert_eq!(entry["response"]["statusText"], "");
+    }
+}
@@ -0,0 +1,2 @@
+mod create_har;
+mod parse_har;
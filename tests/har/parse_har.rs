@@ -0,0 +1,121 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::fs;
+
+    use monolith::har::{create_har, parse_har};
+    use monolith::session::HttpExchange;
+    use monolith::url::Url;
+
+    #[test]
+    fn document_and_assets() {
+        let data = fs::read("tests/_data_/har/page.har").unwrap();
+        let (document_url, archive) = parse_har(&data).unwrap();
+
+        assert_eq!(document_url.as_str(), "https://example.com/index.html");
+        assert_eq!(archive.len(), 3);
+
+        let image = archive
+            .get(&Url::parse("https://example.com/1.png").unwrap())
+            .unwrap();
+        assert_eq!(image.media_type, "image/png");
+        assert_eq!(image.charset, "");
+        assert_eq!(image.data, b"\x89PNG\r\n\x1a\n");
+
+        let stylesheet = archive
+            .get(&Url::parse("https://example.com/style.css").unwrap())
+            .unwrap();
+        assert_eq!(stylesheet.media_type, "text/css");
+        assert_eq!(stylesheet.data, b"body{}");
+    }
+
+    #[test]
+    fn text_gets_encoded_back_into_original_charset() {
+        let data = fs::read("tests/_data_/har/page.har").unwrap();
+        let (document_url, archive) = parse_har(&data).unwrap();
+        let document = archive.get(&document_url).unwrap();
+
+        assert_eq!(document.charset, "windows-1252");
+        assert!(document
+            .data
+            .windows(6)
+            .any(|window| window == b"caf\xe9 <"));
+    }
+
+    #[test]
+    fn redirects() {
+        let data = fs::read("tests/_data_/har/page.har").unwrap();
+        let (_, archive) = parse_har(&data).unwrap();
+
+        assert_eq!(
+            archive
+                .get(&Url::parse("https://example.com/").unwrap())
+                .unwrap()
+                .url
+                .as_str(),
+            "https://example.com/index.html"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let exchange = HttpExchange {
+            date: chrono::Utc::now(),
+            url: Url::parse("https://example.com/").unwrap(),
+            request_headers: vec![],
+            remote_address: None,
+            http_version: "HTTP/1.1".to_string(),
+            status: 200,
+            response_headers: vec![("content-type".to_string(), "text/html".to_string())],
+            data: b"<html></html>".to_vec(),
+            wait: std::time::Duration::ZERO,
+            receive: std::time::Duration::ZERO,
+            from_cache: false,
+            error: None,
+        };
+        let (document_url, archive) =
+            parse_har(&create_har("Example", std::slice::from_ref(&exchange))).unwrap();
+
+        assert_eq!(document_url, exchange.url);
+        assert_eq!(archive.get(&document_url).unwrap().data, exchange.data);
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::har::parse_har;
+
+    #[test]
+    fn not_json() {
+        assert!(parse_har(b"<html></html>").is_none());
+    }
+
+    #[test]
+    fn no_entries() {
+        assert!(parse_har(br#"{"log": {"version": "1.2", "entries": []}}"#).is_none());
+    }
+
+    #[test]
+    fn no_stored_responses() {
+        assert!(parse_har(
+            br#"{"log": {"entries": [{
+                "request": {"url": "https://example.com/"},
+                "response": {"status": 200, "content": {"mimeType": "text/html"}}
+            }]}}"#
+        )
+        .is_none());
+    }
+}
@@ -0,0 +1,75 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::headers::HeaderRule;
+    use monolith::url::Url;
+
+    #[test]
+    fn exact_host() {
+        let header_rule = HeaderRule {
+            domain: String::from("wiki.example.com"),
+            name: String::from("Authorization"),
+            value: String::from("Bearer token"),
+        };
+        assert!(header_rule.matches_url(&Url::parse("https://wiki.example.com/page").unwrap()));
+    }
+
+    #[test]
+    fn subdomain() {
+        let header_rule = HeaderRule {
+            domain: String::from(".example.com"),
+            name: String::from("Authorization"),
+            value: String::from("Bearer token"),
+        };
+        assert!(header_rule.matches_url(&Url::parse("http://wiki.example.com/page").unwrap()));
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::headers::HeaderRule;
+    use monolith::url::Url;
+
+    #[test]
+    fn third_party_host() {
+        let header_rule = HeaderRule {
+            domain: String::from("wiki.example.com"),
+            name: String::from("Authorization"),
+            value: String::from("Bearer token"),
+        };
+        assert!(!header_rule.matches_url(&Url::parse("https://cdn.example.net/style.css").unwrap()));
+    }
+
+    #[test]
+    fn subdomain_of_exact_host() {
+        let header_rule = HeaderRule {
+            domain: String::from("example.com"),
+            name: String::from("Authorization"),
+            value: String::from("Bearer token"),
+        };
+        assert!(!header_rule.matches_url(&Url::parse("https://cdn.example.com/style.css").unwrap()));
+    }
+
+    #[test]
+    fn non_http_url() {
+        let header_rule = HeaderRule {
+            domain: String::from("."),
+            name: String::from("Authorization"),
+            value: String::from("Bearer token"),
+        };
+        assert!(!header_rule.matches_url(&Url::parse("file:///etc/passwd").unwrap()));
+    }
+}
@@ -0,0 +1,3 @@
+mod header_rule;
+mod parse_header;
+mod parse_header_rules_file_contents;
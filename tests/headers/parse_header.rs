@@ -0,0 +1,54 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::headers;
+
+    #[test]
+    fn name_and_value() {
+        assert_eq!(
+            headers::parse_header("Authorization: Bearer token"),
+            Some(("Authorization".to_string(), "Bearer token".to_string()))
+        );
+    }
+
+    #[test]
+    fn value_with_colons() {
+        assert_eq!(
+            headers::parse_header("X-Forwarded-Host:localhost:8080 "),
+            Some(("X-Forwarded-Host".to_string(), "localhost:8080".to_string()))
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::headers;
+
+    #[test]
+    fn no_colon() {
+        assert_eq!(headers::parse_header("Authorization"), None);
+    }
+
+    #[test]
+    fn invalid_name() {
+        assert_eq!(headers::parse_header("Bad Name: value"), None);
+    }
+
+    #[test]
+    fn invalid_value() {
+        assert_eq!(headers::parse_header("X-Test: line\nbreak"), None);
+    }
+}
@@ -0,0 +1,64 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::headers::{self, HeaderRule};
+
+    #[test]
+    fn rules_with_comments() {
+        let file_contents = "# Internal wiki\n\
+            wiki.example.com Authorization: Bearer token\n\
+            \n\
+            .example.net   X-Test: 1\n";
+        assert_eq!(
+            headers::parse_header_rules_file_contents(file_contents).unwrap(),
+            vec![
+                HeaderRule {
+                    domain: String::from("wiki.example.com"),
+                    name: String::from("Authorization"),
+                    value: String::from("Bearer token"),
+                },
+                HeaderRule {
+                    domain: String::from(".example.net"),
+                    name: String::from("X-Test"),
+                    value: String::from("1"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(
+            headers::parse_header_rules_file_contents("").unwrap(),
+            vec![]
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::headers;
+
+    #[test]
+    fn no_domain() {
+        assert!(headers::parse_header_rules_file_contents("Authorization: Bearer token").is_none());
+    }
+
+    #[test]
+    fn no_header() {
+        assert!(headers::parse_header_rules_file_contents("wiki.example.com").is_none());
+    }
+}
@@ -7,7 +7,7 @@
 
 #[cfg(test)]
 mod passing {
-    use monolith::core::MonolithOptions;
+    use monolith::core::{MonolithOptions, MonolithOutputFormat};
     use monolith::html;
 
     #[test]
@@ -22,6 +22,39 @@ mod passing {
         );
     }
 
+    #[test]
+    fn isolated_mhtml() {
+        let mut options = MonolithOptions::default();
+        options.isolate = true;
+        options.output_format = MonolithOutputFormat::MHTML;
+        let csp_content = html::compose_csp(&options);
+
+        assert_eq!(csp_content, "");
+    }
+
+    #[test]
+    fn isolated_webarchive() {
+        let mut options = MonolithOptions::default();
+        options.isolate = true;
+        options.output_format = MonolithOutputFormat::WebArchive;
+        let csp_content = html::compose_csp(&options);
+
+        assert_eq!(csp_content, "");
+    }
+
+    #[test]
+    fn isolated_zim() {
+        let mut options = MonolithOptions::default();
+        options.isolate = true;
+        options.output_format = MonolithOutputFormat::ZIM;
+        let csp_content = html::compose_csp(&options);
+
+        assert_eq!(
+            csp_content,
+            "default-src 'self' 'unsafe-eval' 'unsafe-inline' data:;"
+        );
+    }
+
     #[test]
     fn no_css() {
         let mut options = MonolithOptions::default();
@@ -20,7 +20,7 @@ mod passing {
         let mut options = MonolithOptions::default();
         options.no_images = true;
         options.silent = true;
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
         let embedded_css =
             html::embed_srcset(&mut session, &Url::parse("data:,").unwrap(), srcset_value);
 
@@ -39,7 +39,7 @@ mod passing {
         let mut options = MonolithOptions::default();
         options.no_images = true;
         options.silent = true;
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
         let embedded_css =
             html::embed_srcset(&mut session, &Url::parse("data:,").unwrap(), srcset_value);
 
@@ -55,7 +55,7 @@ mod passing {
         let mut options = MonolithOptions::default();
         options.no_images = true;
         options.silent = true;
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
         let embedded_css =
             html::embed_srcset(&mut session, &Url::parse("data:,").unwrap(), srcset_value);
 
@@ -71,7 +71,7 @@ mod passing {
         let mut options = MonolithOptions::default();
         options.no_images = true;
         options.silent = true;
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
         let embedded_css =
             html::embed_srcset(&mut session, &Url::parse("data:,").unwrap(), srcset_value);
 
@@ -87,7 +87,7 @@ mod passing {
         let mut options = MonolithOptions::default();
         options.no_images = true;
         options.silent = true;
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
         let embedded_css =
             html::embed_srcset(&mut session, &Url::parse("data:,").unwrap(), srcset_value);
 
@@ -106,7 +106,7 @@ mod passing {
         let mut options = MonolithOptions::default();
         options.no_images = true;
         options.silent = true;
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
         let embedded_css =
             html::embed_srcset(&mut session, &Url::parse("data:,").unwrap(), srcset_value);
 
@@ -125,7 +125,7 @@ mod passing {
         let mut options = MonolithOptions::default();
         options.no_images = true;
         options.silent = true;
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
         let embedded_css =
             html::embed_srcset(&mut session, &Url::parse("data:,").unwrap(), srcset_value);
 
@@ -161,7 +161,7 @@ mod failing {
         let mut options = MonolithOptions::default();
         options.no_images = true;
         options.silent = true;
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
         let embedded_css =
             html::embed_srcset(&mut session, &Url::parse("data:,").unwrap(), srcset_value);
 
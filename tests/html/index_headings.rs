@@ -0,0 +1,60 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::core::MonolithOptions;
+    use monolith::html;
+
+    #[test]
+    fn assign_ids() {
+        let html = "<h1>Title</h1><section><h2 id=\"intro\">  Intro\n<b>duction</b></h2><h3></h3></section><h2>Outro</h2>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let headings = html::index_headings(&dom.document);
+
+        assert_eq!(
+            headings
+                .iter()
+                .map(|heading| (heading.id.as_str(), heading.title.as_str()))
+                .collect::<Vec<(&str, &str)>>(),
+            vec![
+                ("heading-1", "Title"),
+                ("intro", "Intro duction"),
+                ("heading-3", "Outro"),
+            ]
+        );
+
+        assert_eq!(
+            String::from_utf8_lossy(&html::serialize_document(
+                dom,
+                "".to_string(),
+                &MonolithOptions::default()
+            )),
+            "<html><head></head><body><h1 id=\"heading-1\">Title</h1><section><h2 id=\"intro\">  Intro\n<b>duction</b></h2><h3></h3></section><h2 id=\"heading-3\">Outro</h2></body></html>"
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::html;
+
+    #[test]
+    fn no_headings() {
+        let html = "<p>Paragraph</p>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+
+        assert!(html::index_headings(&dom.document).is_empty());
+    }
+}
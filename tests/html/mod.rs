@@ -8,9 +8,11 @@ mod get_charset;
 mod get_node_attr;
 mod get_node_name;
 mod has_favicon;
+mod index_headings;
 mod is_favicon;
 mod parse_link_type;
 mod parse_srcset;
+mod retrieve_and_embed_asset;
 mod serialize_document;
 mod set_node_attr;
 mod walk;
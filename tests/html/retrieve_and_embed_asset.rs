@@ -0,0 +1,114 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use markup5ever_rcdom::Handle;
+
+    use monolith::core::MonolithOptions;
+    use monolith::html;
+    use monolith::session::Session;
+    use monolith::url::Url;
+
+    pub fn find_script(html: &str) -> (markup5ever_rcdom::RcDom, Handle) {
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let script: Handle =
+            html::find_nodes(&dom.document, vec!["html", "head", "script"])[0].clone();
+        (dom, script)
+    }
+
+    #[test]
+    fn matching_integrity() {
+        let (_dom, script) = find_script(
+            "<script src=\"data:text/javascript,alert(1)\" integrity=\"sha256-bhHHL3z2vDgxUt0W3dWQOrprscmda2Y5pLsLg4GF+pI=\"></script>",
+        );
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options).unwrap();
+
+        html::retrieve_and_embed_asset(
+            &mut session,
+            &Url::parse("https://example.com/").unwrap(),
+            &script,
+            "src",
+            "data:text/javascript,alert(1)",
+        );
+        assert_eq!(html::get_node_attr(&script, "src"), None);
+        assert_eq!(html::get_node_attr(&script, "integrity"), None);
+        assert!(session.asset_errors().is_empty());
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use super::passing::find_script;
+    use monolith::core::{MonolithError, MonolithOptions};
+    use monolith::html;
+    use monolith::session::Session;
+    use monolith::url::Url;
+
+    #[test]
+    fn integrity_mismatch() {
+        let (_dom, script) = find_script(
+            "<script src=\"data:text/javascript,alert(2)\" integrity=\"sha256-bhHHL3z2vDgxUt0W3dWQOrprscmda2Y5pLsLg4GF+pI=\"></script>",
+        );
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options).unwrap();
+
+        html::retrieve_and_embed_asset(
+            &mut session,
+            &Url::parse("https://example.com/").unwrap(),
+            &script,
+            "src",
+            "data:text/javascript,alert(2)",
+        );
+        assert_eq!(
+            session.asset_errors(),
+            &[MonolithError::IntegrityMismatch {
+                url: Url::parse("data:text/javascript,alert(2)").unwrap()
+            }]
+        );
+    }
+
+    #[test]
+    fn local_file_within_remote_document() {
+        let (_dom, script) = find_script("<script src=\"file:///etc/passwd\"></script>");
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options).unwrap();
+
+        html::retrieve_and_embed_asset(
+            &mut session,
+            &Url::parse("https://example.com/").unwrap(),
+            &script,
+            "src",
+            "file:///etc/passwd",
+        );
+        assert_eq!(
+            session.asset_errors(),
+            &[MonolithError::SecurityError {
+                url: Url::parse("file:///etc/passwd").unwrap()
+            }]
+        );
+        // Local references get removed
+        assert_eq!(html::get_node_attr(&script, "src"), None);
+    }
+}
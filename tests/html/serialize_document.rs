@@ -7,7 +7,7 @@
 
 #[cfg(test)]
 mod passing {
-    use monolith::core::MonolithOptions;
+    use monolith::core::{MonolithOptions, MonolithOutputFormat};
     use monolith::html;
 
     #[test]
@@ -22,6 +22,19 @@ mod passing {
         );
     }
 
+    #[test]
+    fn xhtml_for_epub() {
+        let html = "<!doctype html><p title='\"1\" & 2'>a<br>b &lt; c</p>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+        let mut options = MonolithOptions::default();
+        options.output_format = MonolithOutputFormat::EPUB;
+
+        assert_eq!(
+            String::from_utf8_lossy(&html::serialize_document(dom, "".to_string(), &options)),
+            "<!DOCTYPE html><html xmlns=\"http://www.w3.org/1999/xhtml\"><head></head><body><p title=\"&quot;1&quot; &amp; 2\">a<br></br>b &lt; c</p></body></html>"
+        );
+    }
+
     #[test]
     fn full_page_with_no_html_head_or_body() {
         let html = "<title>Isolated document</title>\
@@ -25,7 +25,7 @@ mod passing {
         let mut options = MonolithOptions::default();
         options.silent = true;
 
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
 
         html::walk(&mut session, &url, &dom.document);
 
@@ -52,7 +52,7 @@ mod passing {
         let mut options = MonolithOptions::default();
         options.silent = true;
 
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
 
         html::walk(&mut session, &url, &dom.document);
 
@@ -79,7 +79,7 @@ mod passing {
         let mut options = MonolithOptions::default();
         options.silent = true;
 
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
 
         html::walk(&mut session, &url, &dom.document);
 
@@ -112,7 +112,7 @@ mod passing {
         options.no_css = true;
         options.silent = true;
 
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
 
         html::walk(&mut session, &url, &dom.document);
 
@@ -152,7 +152,7 @@ mod passing {
         options.no_images = true;
         options.silent = true;
 
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
 
         html::walk(&mut session, &url, &dom.document);
 
@@ -193,7 +193,7 @@ mod passing {
         options.no_images = true;
         options.silent = true;
 
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
 
         html::walk(&mut session, &url, &dom.document);
 
@@ -221,7 +221,7 @@ mod passing {
         options.no_frames = true;
         options.silent = true;
 
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
 
         html::walk(&mut session, &url, &dom.document);
 
@@ -257,7 +257,7 @@ mod passing {
         options.no_frames = true;
         options.silent = true;
 
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
 
         html::walk(&mut session, &url, &dom.document);
 
@@ -297,7 +297,7 @@ mod passing {
         options.no_js = true;
         options.silent = true;
 
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
 
         html::walk(&mut session, &url, &dom.document);
 
@@ -335,7 +335,7 @@ mod passing {
         let mut options = MonolithOptions::default();
         options.silent = true;
 
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
 
         html::walk(&mut session, &url, &dom.document);
 
@@ -376,7 +376,7 @@ mod passing {
         options.no_js = true;
         options.silent = true;
 
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
 
         html::walk(&mut session, &url, &dom.document);
 
@@ -418,7 +418,7 @@ mod passing {
         options.no_js = true;
         options.silent = true;
 
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
 
         html::walk(&mut session, &url, &dom.document);
 
@@ -468,7 +468,7 @@ mod passing {
         options.no_images = true;
         options.silent = true;
 
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
 
         html::walk(&mut session, &url, &dom.document);
 
@@ -511,7 +511,7 @@ mod passing {
         options.no_images = true;
         options.silent = true;
 
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
 
         html::walk(&mut session, &url, &dom.document);
 
@@ -550,7 +550,7 @@ mod passing {
         let mut options = MonolithOptions::default();
         options.silent = true;
 
-        let mut session: Session = Session::new(None, None, options);
+        let mut session: Session = Session::new(None, None, options).unwrap();
 
         html::walk(&mut session, &url, &dom.document);
 
@@ -0,0 +1,109 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::mhtml::create_mhtml;
+    use monolith::session::EmbeddedAsset;
+    use monolith::url::Url;
+
+    fn document() -> EmbeddedAsset {
+        EmbeddedAsset {
+            url: Url::parse("https://example.com/").unwrap(),
+            media_type: "text/html".to_string(),
+            charset: "UTF-8".to_string(),
+            data: b"<html><body><img src=\"https://example.com/1.png\"></body></html>\n".to_vec(),
+        }
+    }
+
+    fn boundary(mhtml: &str) -> String {
+        let start = mhtml.find("boundary=\"").unwrap() + 10;
+        let end = start + mhtml[start..].find('"').unwrap();
+
+        mhtml[start..end].to_string()
+    }
+
+    #[test]
+    fn document_only() {
+        let mhtml = String::from_utf8(create_mhtml(&document(), &[], None)).unwrap();
+        let boundary = boundary(&mhtml);
+
+        assert!(mhtml.contains("Snapshot-Content-Location: https://example.com/\r\n"));
+        assert!(mhtml.contains("MIME-Version: 1.0\r\n"));
+        assert!(mhtml.contains("Content-Type: multipart/related;\r\n\ttype=\"text/html\";\r\n"));
+        assert!(!mhtml.contains("Subject:"));
+        assert_eq!(mhtml.matches(&format!("--{}\r\n", boundary)).count(), 1);
+        assert!(mhtml.ends_with(&format!("--{}--\r\n", boundary)));
+        assert!(mhtml.contains(
+            "Content-Type: text/html; charset=UTF-8\r\nContent-Transfer-Encoding: quoted-printable\r\nContent-Location: https://example.com/\r\n\r\n<html><body><img src=3D\"https://example.com/1.png\"></body></html>\r\n\r\n"
+        ));
+    }
+
+    #[test]
+    fn binary_asset() {
+        let asset = EmbeddedAsset {
+            url: Url::parse("https://example.com/1.png").unwrap(),
+            media_type: "image/png".to_string(),
+            charset: "".to_string(),
+            data: vec![0; 100],
+        };
+        let mhtml = String::from_utf8(create_mhtml(&document(), &[asset], None)).unwrap();
+        let boundary = boundary(&mhtml);
+
+        assert_eq!(mhtml.matches(&format!("--{}\r\n", boundary)).count(), 2);
+        assert!(mhtml.contains(&format!(
+            "--{}\r\nContent-Type: image/png\r\nContent-Transfer-Encoding: base64\r\nContent-Location: https://example.com/1.png\r\n\r\n{}\r\n{}\r\n--{}--\r\n",
+            boundary,
+            "A".repeat(76),
+            "A".repeat(58) + "==",
+            boundary
+        )));
+    }
+
+    #[test]
+    fn text_asset_without_charset() {
+        let asset = EmbeddedAsset {
+            url: Url::parse("https://example.com/style.css").unwrap(),
+            media_type: "text/css".to_string(),
+            charset: "US-ASCII".to_string(),
+            data: b"body{}".to_vec(),
+        };
+        let mhtml = String::from_utf8(create_mhtml(&document(), &[asset], None)).unwrap();
+
+        assert!(mhtml.contains(
+            "Content-Type: text/css\r\nContent-Transfer-Encoding: quoted-printable\r\nContent-Location: https://example.com/style.css\r\n\r\nbody{}\r\n"
+        ));
+    }
+
+    #[test]
+    fn ascii_title() {
+        let mhtml = String::from_utf8(create_mhtml(
+            &document(),
+            &[],
+            Some("Hello, World!".to_string()),
+        ))
+        .unwrap();
+
+        assert!(mhtml.contains("Subject: Hello, World!\r\n"));
+    }
+
+    #[test]
+    fn non_ascii_title() {
+        let mhtml =
+            String::from_utf8(create_mhtml(&document(), &[], Some("Привет".to_string()))).unwrap();
+
+        assert!(mhtml.contains("Subject: =?utf-8?B?0J/RgNC40LLQtdGC?=\r\n"));
+    }
+
+    #[test]
+    fn boundary_is_deterministic() {
+        let first = String::from_utf8(create_mhtml(&document(), &[], None)).unwrap();
+        let second = String::from_utf8(create_mhtml(&document(), &[], None)).unwrap();
+
+        assert_eq!(boundary(&first), boundary(&second));
+    }
+}
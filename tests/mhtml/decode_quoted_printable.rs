@@ -0,0 +1,50 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::mhtml::decode_quoted_printable;
+
+    #[test]
+    fn plain_ascii() {
+        assert_eq!(decode_quoted_printable(b"<p>Hello</p>"), b"<p>Hello</p>");
+    }
+
+    #[test]
+    fn escape_sequences() {
+        assert_eq!(
+            decode_quoted_printable(b"<a href=3D\"#\">=C3=A9=c3=a9"),
+            "<a href=\"#\">éé".as_bytes()
+        );
+    }
+
+    #[test]
+    fn soft_line_breaks() {
+        assert_eq!(decode_quoted_printable(b"ab=\r\ncd=\nef"), b"abcdef");
+    }
+
+    #[test]
+    fn hard_line_breaks() {
+        assert_eq!(decode_quoted_printable(b"ab\r\ncd"), b"ab\r\ncd");
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::mhtml::decode_quoted_printable;
+
+    #[test]
+    fn malformed_escape_sequences() {
+        assert_eq!(decode_quoted_printable(b"a=ZZb=4"), b"a=ZZb=4");
+    }
+}
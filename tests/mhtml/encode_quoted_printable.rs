@@ -0,0 +1,56 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::mhtml::encode_quoted_printable;
+
+    #[test]
+    fn plain_ascii() {
+        assert_eq!(encode_quoted_printable(b"<p>Hello</p>"), "<p>Hello</p>");
+    }
+
+    #[test]
+    fn equal_sign() {
+        assert_eq!(
+            encode_quoted_printable(b"<a href=\"#\">"),
+            "<a href=3D\"#\">"
+        );
+    }
+
+    #[test]
+    fn non_ascii() {
+        assert_eq!(encode_quoted_printable("é".as_bytes()), "=C3=A9");
+    }
+
+    #[test]
+    fn hard_line_breaks() {
+        assert_eq!(encode_quoted_printable(b"a\nb\r\nc"), "a\r\nb\r\nc");
+    }
+
+    #[test]
+    fn trailing_whitespace() {
+        assert_eq!(encode_quoted_printable(b"a \nb\t"), "a=20\r\nb=09");
+    }
+
+    #[test]
+    fn soft_line_breaks() {
+        let encoded = encode_quoted_printable("x".repeat(100).as_bytes());
+
+        assert_eq!(
+            encoded,
+            format!("{}=\r\n{}", "x".repeat(75), "x".repeat(25))
+        );
+    }
+
+    #[test]
+    fn soft_line_breaks_do_not_split_escape_sequences() {
+        let encoded = encode_quoted_printable(format!("{}=", "x".repeat(74)).as_bytes());
+
+        assert_eq!(encoded, format!("{}=\r\n=3D", "x".repeat(74)));
+    }
+}
@@ -0,0 +1,127 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::fs;
+
+    use monolith::mhtml::{create_mhtml, parse_mhtml};
+    use monolith::session::EmbeddedAsset;
+    use monolith::url::Url;
+
+    #[test]
+    fn parts_by_content_location() {
+        let data = fs::read("tests/_data_/mhtml/page.mhtml").unwrap();
+        let (root_url, archive) = parse_mhtml(&data).unwrap();
+
+        assert_eq!(root_url.as_str(), "https://example.com/page.html");
+        assert_eq!(archive.len(), 4);
+
+        let root = archive.get(&root_url).unwrap();
+        assert_eq!(root.media_type, "text/html");
+        assert_eq!(root.charset, "");
+        assert_eq!(
+            String::from_utf8_lossy(&root.data),
+            "<html><head><link rel=\"stylesheet\" href=\"style.css\"></head><body><img src=\"images/pixel.png\"><iframe src=\"cid:frame-2@mhtml.blink\"></iframe></body></html>"
+        );
+
+        let stylesheet = archive
+            .get(&Url::parse("https://example.com/style.css").unwrap())
+            .unwrap();
+        assert_eq!(stylesheet.media_type, "text/css");
+        assert_eq!(stylesheet.data, b"body { color: red; }");
+    }
+
+    #[test]
+    fn relative_content_location() {
+        let data = fs::read("tests/_data_/mhtml/page.mhtml").unwrap();
+        let (_, archive) = parse_mhtml(&data).unwrap();
+
+        let image = archive
+            .get(&Url::parse("https://example.com/images/pixel.png").unwrap())
+            .unwrap();
+        assert_eq!(image.media_type, "image/png");
+        assert_eq!(image.data, b"\x89PNG\r\n\x1a\nxxxx");
+    }
+
+    #[test]
+    fn parts_by_content_id() {
+        let data = fs::read("tests/_data_/mhtml/page.mhtml").unwrap();
+        let (root_url, archive) = parse_mhtml(&data).unwrap();
+
+        // Parts can be referred to by their Content-ID as well as by their location
+        let root = archive
+            .get(&Url::parse("cid:frame-1@mhtml.blink").unwrap())
+            .unwrap();
+        assert_eq!(root.url, root_url);
+
+        let frame = archive
+            .get(&Url::parse("cid:frame-2@mhtml.blink").unwrap())
+            .unwrap();
+        assert_eq!(frame.url.as_str(), "cid:frame-2@mhtml.blink");
+        assert_eq!(frame.data, b"<html><body>Frame</body></html>");
+    }
+
+    #[test]
+    fn start_parameter() {
+        let data = b"Content-Type: multipart/related; boundary=b; start=\"<two>\"\n\n--b\nContent-Type: text/plain\nContent-ID: <one>\n\nOne\n--b\nContent-Type: text/html; charset=\"UTF-8\"\nContent-ID: <two>\n\nTwo\n--b--\n";
+        let (root_url, archive) = parse_mhtml(data).unwrap();
+
+        assert_eq!(root_url.as_str(), "cid:two");
+        let root = archive.get(&root_url).unwrap();
+        assert_eq!(root.charset, "UTF-8");
+        assert_eq!(root.data, b"Two");
+    }
+
+    #[test]
+    fn round_trip() {
+        let document = EmbeddedAsset {
+            url: Url::parse("https://example.com/").unwrap(),
+            media_type: "text/html".to_string(),
+            charset: "UTF-8".to_string(),
+            data: "<html><body>Привет, <img src=\"https://example.com/1.png\"></body></html>"
+                .as_bytes()
+                .to_vec(),
+        };
+        let asset = EmbeddedAsset {
+            url: Url::parse("https://example.com/1.png").unwrap(),
+            media_type: "image/png".to_string(),
+            charset: "".to_string(),
+            data: (0..=255).collect(),
+        };
+        let mhtml = create_mhtml(&document, std::slice::from_ref(&asset), None);
+        let (root_url, archive) = parse_mhtml(&mhtml).unwrap();
+
+        assert_eq!(root_url, document.url);
+        assert_eq!(archive.get(&root_url).unwrap().data, document.data);
+        assert_eq!(archive.get(&root_url).unwrap().charset, "UTF-8");
+        assert_eq!(archive.get(&asset.url).unwrap().data, asset.data);
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::mhtml::parse_mhtml;
+
+    #[test]
+    fn not_multipart() {
+        assert!(parse_mhtml(b"Content-Type: text/html\r\n\r\n<html></html>").is_none());
+    }
+
+    #[test]
+    fn no_parts() {
+        assert!(
+            parse_mhtml(b"Content-Type: multipart/related; boundary=b\r\n\r\n--b--\r\n").is_none()
+        );
+    }
+}
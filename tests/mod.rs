@@ -1,8 +1,23 @@
+mod archive;
+mod auth;
+mod batch;
+mod cache;
 mod cli;
 mod cookies;
 mod core;
+mod crawl;
 mod css;
+mod epub;
+mod har;
+mod headers;
 mod html;
 mod js;
+mod mhtml;
+mod report;
 mod session;
+mod source;
 mod url;
+mod warc;
+mod webarchive;
+mod zim;
+mod zip;
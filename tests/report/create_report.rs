@@ -0,0 +1,74 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use serde_json::Value;
+
+    use monolith::core::MonolithError;
+    use monolith::report::{create_report, AssetOutcome, ReportedAsset};
+    use monolith::url::Url;
+
+    #[test]
+    fn embedded_and_failed_assets() {
+        let report: Value = serde_json::from_slice(&create_report(&[
+            ReportedAsset {
+                url: Url::parse("https://example.com/old.css").unwrap(),
+                parent_url: Url::parse("https://example.com/").unwrap(),
+                context: String::from("link[href]"),
+                final_url: Some(Url::parse("https://example.com/style.css").unwrap()),
+                media_type: String::from("text/css"),
+                size: 12,
+                from_cache: true,
+                outcome: AssetOutcome::Embedded,
+                error: None,
+            },
+            ReportedAsset {
+                url: Url::parse("https://example.com/image.png").unwrap(),
+                parent_url: Url::parse("https://example.com/style.css").unwrap(),
+                context: String::from("background-image: url()"),
+                final_url: None,
+                media_type: String::from(""),
+                size: 0,
+                from_cache: false,
+                outcome: AssetOutcome::Remote,
+                error: Some(MonolithError::HttpStatus {
+                    url: Url::parse("https://example.com/image.png").unwrap(),
+                    status: 404,
+                }),
+            },
+        ]))
+        .unwrap();
+
+        assert_eq!(report["creator"]["name"], "monolith");
+
+        let assets: &Vec<Value> = report["assets"].as_array().unwrap();
+        assert_eq!(assets.len(), 2);
+        assert_eq!(assets[0]["url"], "https://example.com/old.css");
+        assert_eq!(assets[0]["parent_url"], "https://example.com/");
+        assert_eq!(assets[0]["context"], "link[href]");
+        assert_eq!(assets[0]["final_url"], "https://example.com/style.css");
+        assert_eq!(assets[0]["media_type"], "text/css");
+        assert_eq!(assets[0]["size"], 12);
+        assert_eq!(assets[0]["from_cache"], true);
+        assert_eq!(assets[0]["outcome"], "embedded");
+        assert_eq!(assets[0]["error"], Value::Null);
+        assert_eq!(assets[1]["final_url"], Value::Null);
+        assert_eq!(assets[1]["outcome"], "remote");
+        assert_eq!(
+            assets[1]["error"],
+            "could not retrieve \"https://example.com/image.png\" (404 Not Found)"
+        );
+    }
+
+    #[test]
+    fn no_assets() {
+        let report: Value = serde_json::from_slice(&create_report(&[])).unwrap();
+
+        assert_eq!(report["assets"], Value::Array(vec![]));
+    }
+}
@@ -0,0 +1,206 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::thread;
+    use std::time::Duration;
+
+    use monolith::core::{create_monolithic_document_from_data, MonolithError, MonolithOptions};
+    use monolith::session::Session;
+
+    #[test]
+    fn not_aborted() {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let session: Session = Session::new(None, None, options).unwrap();
+
+        assert_eq!(session.abort_reason(), None);
+    }
+
+    #[test]
+    fn cancelled() {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let session: Session = Session::new(None, None, options).unwrap();
+        let cancellation_token = session.cancellation_token();
+        cancellation_token.cancel();
+
+        assert!(cancellation_token.is_cancelled());
+        assert_eq!(session.abort_reason(), Some(MonolithError::Cancelled));
+    }
+
+    #[test]
+    fn out_of_time() {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.time_limit = 1;
+
+        let mut session: Session = Session::new(None, None, options).unwrap();
+        thread::sleep(Duration::from_millis(1100));
+        assert_eq!(
+            session.abort_reason(),
+            Some(MonolithError::DeadlineExceeded)
+        );
+
+        // Time limit applies to the whole capture, not to each document
+        session.start_document();
+        assert_eq!(
+            session.abort_reason(),
+            Some(MonolithError::DeadlineExceeded)
+        );
+    }
+
+    #[test]
+    fn partial_document() {
+        let mut options = MonolithOptions::default();
+        options.keep_partial = true;
+        options.no_metadata = true;
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options).unwrap();
+        session.cancellation_token().cancel();
+
+        let (result, _title) = create_monolithic_document_from_data(
+            &mut session,
+            b"<html><body><img src=\"http://127.0.0.1:9/image.png\"></body></html>".to_vec(),
+            None,
+            Some("http://127.0.0.1:9/".to_string()),
+        )
+        .unwrap();
+
+        // Assets which didn't get retrieved keep pointing to where they are
+        assert!(String::from_utf8_lossy(&result).contains("src=\"http://127.0.0.1:9/image.png\""));
+        assert_eq!(
+            session.asset_errors(),
+            &[MonolithError::Cancelled, MonolithError::Cancelled]
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use monolith::core::{create_monolithic_document_from_data, MonolithError, MonolithOptions};
+    use monolith::session::Session;
+    use monolith::url::Url;
+
+    #[test]
+    fn retrieve_after_cancelling() {
+        let mut options = MonolithOptions::default();
+        options.record_exchanges = true;
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options).unwrap();
+        session.cancellation_token().cancel();
+
+        let url: Url = Url::parse("http://127.0.0.1:9/style.css").unwrap();
+        assert_eq!(
+            session.retrieve_asset(&url, &url),
+            Err(MonolithError::Cancelled)
+        );
+        assert!(session.exchanges().is_empty());
+    }
+
+    #[test]
+    fn document_after_cancelling() {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options).unwrap();
+        session.cancellation_token().cancel();
+
+        assert_eq!(
+            create_monolithic_document_from_data(
+                &mut session,
+                b"<html><body><img src=\"http://127.0.0.1:9/image.png\"></body></html>".to_vec(),
+                None,
+                Some("http://127.0.0.1:9/".to_string()),
+            ),
+            Err(MonolithError::Cancelled)
+        );
+    }
+
+    #[test]
+    fn endless_download() {
+        // Server which keeps sending the body, never getting to its end
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url: Url = Url::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request: Vec<u8> = vec![];
+            let mut buffer = [0; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let size: usize = stream.read(&mut buffer).unwrap();
+                if size == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buffer[..size]);
+            }
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: 1000000000\r\n\r\n"
+            )
+            .unwrap();
+            while stream.write_all(&[b'.'; 1024]).is_ok() {
+                thread::sleep(Duration::from_millis(10));
+            }
+        });
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.time_limit = 1;
+
+        let mut session: Session = Session::new(None, None, options).unwrap();
+        let started: Instant = Instant::now();
+
+        assert_eq!(
+            session.retrieve_asset(&url, &url),
+            Err(MonolithError::DeadlineExceeded)
+        );
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn unresponsive_server() {
+        // Server which takes the request, but never responds to it
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url: Url = Url::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+        thread::spawn(move || {
+            let (_stream, _) = listener.accept().unwrap();
+            thread::sleep(Duration::from_secs(30));
+        });
+
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+        options.time_limit = 1;
+        options.timeout = 30;
+
+        let mut session: Session = Session::new(None, None, options).unwrap();
+        let started: Instant = Instant::now();
+
+        // Request gets cut short along with the capture, instead of running until its own timeout
+        assert_eq!(
+            session.retrieve_asset(&url, &url),
+            Err(MonolithError::DeadlineExceeded)
+        );
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+}
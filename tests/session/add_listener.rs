@@ -0,0 +1,184 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use reqwest::header::HeaderMap;
+    use reqwest::StatusCode;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use monolith::core::{create_monolithic_document_from_data, MonolithOptions};
+    use monolith::events::{DocumentPhase, SessionEvent};
+    use monolith::session::Session;
+    use monolith::source::{FetchError, FetchedResponse, Fetcher};
+    use monolith::url::Url;
+
+    pub struct StubFetcher {
+        pub status: StatusCode,
+    }
+
+    impl Fetcher for StubFetcher {
+        fn fetch(
+            &self,
+            url: &Url,
+            _headers: &HeaderMap,
+            _timeout: Option<Duration>,
+            progress: &mut dyn FnMut(usize, Option<usize>) -> bool,
+        ) -> Result<FetchedResponse, FetchError> {
+            // Body arrives in two halves
+            let data: Vec<u8> = b"0123456789".to_vec();
+            progress(5, Some(data.len()));
+            progress(10, Some(data.len()));
+
+            Ok(FetchedResponse {
+                status: self.status,
+                url: url.clone(),
+                headers: HeaderMap::new(),
+                http_version: String::from("HTTP/1.1"),
+                remote_address: None,
+                data,
+                error: None,
+                receive: Duration::ZERO,
+            })
+        }
+    }
+
+    pub fn collect_events(session: &mut Session) -> Arc<Mutex<Vec<SessionEvent>>> {
+        let events: Arc<Mutex<Vec<SessionEvent>>> = Arc::new(Mutex::new(vec![]));
+        let listener_events = events.clone();
+        session.add_listener(move |event: &SessionEvent| {
+            listener_events.lock().unwrap().push(event.clone());
+        });
+
+        events
+    }
+
+    #[test]
+    fn retrieved_asset() {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options).unwrap();
+        session.set_fetcher(StubFetcher {
+            status: StatusCode::OK,
+        });
+        let events = collect_events(&mut session);
+
+        let url: Url = Url::parse("http://127.0.0.1:9/file.txt").unwrap();
+        session.retrieve_asset(&url, &url).unwrap();
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                SessionEvent::AssetStarted { url: url.clone() },
+                SessionEvent::BytesReceived {
+                    url: url.clone(),
+                    received: 5,
+                    total: Some(10),
+                },
+                SessionEvent::BytesReceived {
+                    url: url.clone(),
+                    received: 10,
+                    total: Some(10),
+                },
+                SessionEvent::AssetFinished {
+                    url: url.clone(),
+                    final_url: url.clone(),
+                    size: 10,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn data_url() {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options).unwrap();
+        let events = collect_events(&mut session);
+
+        let url: Url = Url::parse("data:text/plain,test").unwrap();
+        session.retrieve_asset(&url, &url).unwrap();
+
+        // Data URLs don't need to be retrieved
+        assert!(events.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn document_phases() {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options).unwrap();
+        let events = collect_events(&mut session);
+
+        create_monolithic_document_from_data(
+            &mut session,
+            b"<html><body></body></html>".to_vec(),
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                SessionEvent::PhaseChanged(DocumentPhase::Embedding),
+                SessionEvent::PhaseChanged(DocumentPhase::Serializing),
+            ]
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use reqwest::StatusCode;
+
+    use monolith::core::{MonolithError, MonolithOptions};
+    use monolith::events::SessionEvent;
+    use monolith::session::Session;
+    use monolith::url::Url;
+
+    use super::passing::{collect_events, StubFetcher};
+
+    #[test]
+    fn http_status() {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options).unwrap();
+        session.set_fetcher(StubFetcher {
+            status: StatusCode::NOT_FOUND,
+        });
+        let events = collect_events(&mut session);
+
+        let url: Url = Url::parse("http://127.0.0.1:9/missing.txt").unwrap();
+        session.retrieve_asset(&url, &url).unwrap_err();
+
+        let events = events.lock().unwrap();
+        assert_eq!(
+            events.first(),
+            Some(&SessionEvent::AssetStarted { url: url.clone() })
+        );
+        assert_eq!(
+            events.last(),
+            Some(&SessionEvent::AssetFailed {
+                url: url.clone(),
+                error: MonolithError::HttpStatus { url, status: 404 },
+            })
+        );
+    }
+}
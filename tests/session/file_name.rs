@@ -0,0 +1,78 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::session::EmbeddedAsset;
+    use monolith::url::Url;
+
+    fn asset(url: &str, data: &[u8]) -> EmbeddedAsset {
+        EmbeddedAsset {
+            url: Url::parse(url).unwrap(),
+            media_type: "".to_string(),
+            charset: "".to_string(),
+            data: data.to_vec(),
+        }
+    }
+
+    #[test]
+    fn with_extension() {
+        assert_eq!(
+            asset("https://example.com/images/1.PNG?v=2", b"").file_name(),
+            "e3b0c44298fc1c149afbf4c8996fb924.png"
+        );
+    }
+
+    #[test]
+    fn without_extension() {
+        assert_eq!(
+            asset("https://example.com/images/", b"").file_name(),
+            "e3b0c44298fc1c149afbf4c8996fb924"
+        );
+    }
+
+    #[test]
+    fn extension_from_media_type() {
+        let mut asset = asset("https://example.com/fonts/1.php?name=icons", b"");
+        asset.media_type = "font/woff2".to_string();
+
+        assert_eq!(asset.file_name(), "e3b0c44298fc1c149afbf4c8996fb924.woff2");
+    }
+
+    #[test]
+    fn same_data_same_name() {
+        assert_eq!(
+            asset("https://example.com/1.css", b"body{}").file_name(),
+            asset("https://example.org/2.css", b"body{}").file_name()
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::session::EmbeddedAsset;
+    use monolith::url::Url;
+
+    #[test]
+    fn unusual_extension() {
+        let asset = EmbeddedAsset {
+            url: Url::parse("https://example.com/script.php-x").unwrap(),
+            media_type: "".to_string(),
+            charset: "".to_string(),
+            data: vec![],
+        };
+
+        assert_eq!(asset.file_name(), "e3b0c44298fc1c149afbf4c8996fb924");
+    }
+}
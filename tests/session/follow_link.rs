@@ -0,0 +1,126 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::core::MonolithOptions;
+    use monolith::session::Session;
+    use monolith::url::Url;
+
+    fn session() -> Session {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        Session::new(None, None, options).unwrap()
+    }
+
+    #[test]
+    fn new_page_within_scope() {
+        let mut session = session();
+        session.set_link_scope(Some("example.com".to_string()), true);
+
+        assert_eq!(
+            session.follow_link(&Url::parse("https://example.com/about.html#team").unwrap()),
+            Some("example.com_about.html#team".to_string())
+        );
+        assert_eq!(
+            session.take_links(),
+            vec![Url::parse("https://example.com/about.html").unwrap()]
+        );
+    }
+
+    #[test]
+    fn known_page() {
+        let mut session = session();
+        session.add_page(&Url::parse("https://example.com/").unwrap());
+        session.set_link_scope(Some("example.com".to_string()), false);
+
+        assert_eq!(
+            session.follow_link(&Url::parse("https://example.com/").unwrap()),
+            Some("example.com.html".to_string())
+        );
+        assert!(session.take_links().is_empty());
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::core::MonolithOptions;
+    use monolith::session::Session;
+    use monolith::url::Url;
+
+    fn session() -> Session {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        Session::new(None, None, options).unwrap()
+    }
+
+    #[test]
+    fn no_scope() {
+        let mut session = session();
+
+        assert_eq!(
+            session.follow_link(&Url::parse("https://example.com/about.html").unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    fn other_domain() {
+        let mut session = session();
+        session.set_link_scope(Some("example.com".to_string()), true);
+
+        assert_eq!(
+            session.follow_link(&Url::parse("https://example.org/").unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    fn not_a_page() {
+        let mut session = session();
+        session.set_link_scope(Some("example.com".to_string()), true);
+
+        assert_eq!(
+            session.follow_link(&Url::parse("https://example.com/manual.pdf").unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    fn new_page_too_deep() {
+        let mut session = session();
+        session.set_link_scope(Some("example.com".to_string()), false);
+
+        assert_eq!(
+            session.follow_link(&Url::parse("https://example.com/about.html").unwrap()),
+            None
+        );
+        assert!(session.take_links().is_empty());
+    }
+
+    #[test]
+    fn within_asset() {
+        let mut session = session();
+        session.set_link_scope(Some("example.com".to_string()), true);
+
+        assert_eq!(
+            session.within_asset(|session| {
+                session.follow_link(&Url::parse("https://example.com/about.html").unwrap())
+            }),
+            None
+        );
+    }
+}
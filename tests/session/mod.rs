@@ -1 +1,10 @@
+mod abort_reason;
+mod add_listener;
+mod file_name;
+mod follow_link;
+mod new;
+mod parse_rate_limit_rule;
+mod parse_retry_after;
+mod prefetch_assets;
+mod report_asset;
 mod retrieve_asset;
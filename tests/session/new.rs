@@ -0,0 +1,66 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::core::MonolithOptions;
+    use monolith::session::Session;
+
+    #[test]
+    fn valid_proxies() {
+        let mut options = MonolithOptions::default();
+        options.proxy = Some("socks5h://localhost:9050".to_string());
+        options.proxy_rules = vec![(
+            "example.com".to_string(),
+            "http://localhost:8080".to_string(),
+        )];
+        options.silent = true;
+
+        assert!(Session::new(None, None, options).is_ok());
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::core::{MonolithError, MonolithOptions};
+    use monolith::session::Session;
+
+    #[test]
+    fn invalid_proxy() {
+        let mut options = MonolithOptions::default();
+        options.proxy = Some("localhost:9050".to_string());
+        options.silent = true;
+
+        assert_eq!(
+            Session::new(None, None, options).err(),
+            Some(MonolithError::InvalidProxy {
+                proxy: "localhost:9050".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_proxy_rule() {
+        let mut options = MonolithOptions::default();
+        options.proxy_rules = vec![("example.com".to_string(), "ftp://localhost".to_string())];
+        options.silent = true;
+
+        assert_eq!(
+            Session::new(None, None, options).err(),
+            Some(MonolithError::InvalidProxy {
+                proxy: "ftp://localhost".to_string()
+            })
+        );
+    }
+}
@@ -0,0 +1,70 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::session::{self, RateLimitRule};
+
+    #[test]
+    fn all_limits() {
+        assert_eq!(
+            session::parse_rate_limit_rule("example.com rate=0.5 delay=1000 connections=1"),
+            Some(RateLimitRule {
+                domain: String::from("example.com"),
+                requests_per_second: Some(0.5),
+                delay: Some(1000),
+                max_connections: Some(1),
+            })
+        );
+    }
+
+    #[test]
+    fn some_limits() {
+        assert_eq!(
+            session::parse_rate_limit_rule(".example.com  delay=250"),
+            Some(RateLimitRule {
+                domain: String::from(".example.com"),
+                requests_per_second: None,
+                delay: Some(250),
+                max_connections: None,
+            })
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::session;
+
+    #[test]
+    fn empty() {
+        assert_eq!(session::parse_rate_limit_rule(""), None);
+    }
+
+    #[test]
+    fn unknown_limit() {
+        assert_eq!(session::parse_rate_limit_rule("example.com burst=5"), None);
+    }
+
+    #[test]
+    fn invalid_value() {
+        assert_eq!(session::parse_rate_limit_rule("example.com rate=-1"), None);
+        assert_eq!(session::parse_rate_limit_rule("example.com rate=inf"), None);
+        assert_eq!(session::parse_rate_limit_rule("example.com rate=NaN"), None);
+        assert_eq!(
+            session::parse_rate_limit_rule("example.com delay=soon"),
+            None
+        );
+    }
+}
@@ -0,0 +1,58 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::time::Duration;
+
+    use monolith::session;
+
+    #[test]
+    fn seconds() {
+        assert_eq!(
+            session::parse_retry_after("120"),
+            Some(Duration::from_secs(120))
+        );
+    }
+
+    #[test]
+    fn date_in_the_past() {
+        assert_eq!(
+            session::parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn date_in_the_future() {
+        let delay: Duration = session::parse_retry_after("Fri, 31 Dec 9999 23:59:59 GMT").unwrap();
+
+        assert!(delay > Duration::from_secs(3600));
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::session;
+
+    #[test]
+    fn negative_seconds() {
+        assert_eq!(session::parse_retry_after("-1"), None);
+    }
+
+    #[test]
+    fn garbage() {
+        assert_eq!(session::parse_retry_after("soon"), None);
+    }
+}
@@ -0,0 +1,194 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    use monolith::cache::Cache;
+    use monolith::core::{
+        create_monolithic_document_from_data, MonolithOptions, MonolithOutputFormat,
+    };
+    use monolith::events::{DocumentPhase, SessionEvent};
+    use monolith::session::Session;
+    use monolith::url::Url;
+
+    #[test]
+    fn sequential() {
+        let mut options = MonolithOptions::default();
+        options.parallel_requests = 1;
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options).unwrap();
+        let mut dry_runs: usize = 0;
+        session.prefetch_assets(|_| dry_runs += 1);
+
+        assert_eq!(dry_runs, 0);
+    }
+
+    #[test]
+    fn dry_run_leaves_no_traces() {
+        let mut options = MonolithOptions::default();
+        options.parallel_requests = 4;
+        options.record_exchanges = true;
+
+        let mut session: Session = Session::new(None, None, options).unwrap();
+        let events: Arc<Mutex<Vec<SessionEvent>>> = Arc::new(Mutex::new(vec![]));
+        let listener_events = events.clone();
+        session.add_listener(move |event: &SessionEvent| {
+            listener_events.lock().unwrap().push(event.clone());
+        });
+        let mut dry_runs: usize = 0;
+        session.prefetch_assets(|session| {
+            let url: Url = Url::parse("data:text/plain,Hello").unwrap();
+            let (data, final_url, media_type, charset) =
+                session.retrieve_asset(&url, &url).unwrap();
+            session.embed_asset(&media_type, &charset, &data, &final_url, None);
+
+            let url: Url = Url::parse("file:///missing.txt").unwrap();
+            session.retrieve_asset(&url, &url).unwrap_err();
+
+            dry_runs += 1;
+        });
+
+        // Nothing was requested, the document only had to be gone through once
+        assert_eq!(dry_runs, 1);
+        assert!(session.assets().is_empty());
+        assert!(session.exchanges().is_empty());
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![SessionEvent::PhaseChanged(DocumentPhase::Prefetching)]
+        );
+    }
+
+    #[test]
+    fn assets_stored_after_dry_run() {
+        let mut options = MonolithOptions::default();
+        options.output_format = MonolithOutputFormat::Directory;
+        options.parallel_requests = 4;
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options).unwrap();
+        let url: Url = Url::parse("http://127.0.0.1:9/hello.txt").unwrap();
+        session.prefetch_assets(|session| {
+            session.embed_asset("text/plain", "", b"Hello", &url, None);
+        });
+        assert!(session.assets().is_empty());
+
+        // Assets embedded during the dry run still get stored once the document is gone through for real
+        session.embed_asset("text/plain", "", b"Hello", &url, None);
+        assert_eq!(session.assets().len(), 1);
+    }
+
+    #[test]
+    fn same_output_as_sequential() {
+        let html: &str = "<html><head><link rel=\"stylesheet\" href=\"http://127.0.0.1:9/style.css\"></head><body><img src=\"http://127.0.0.1:9/image.png\"><img src=\"data:image/png;base64,AAAA\"></body></html>";
+        let mut results: Vec<(Vec<u8>, usize)> = vec![];
+
+        for parallel_requests in [1, 4] {
+            let mut options = MonolithOptions::default();
+            options.no_metadata = true;
+            options.parallel_requests = parallel_requests;
+            options.record_exchanges = true;
+            options.silent = true;
+
+            let mut session: Session = Session::new(None, None, options).unwrap();
+            let (result, _title) = create_monolithic_document_from_data(
+                &mut session,
+                html.as_bytes().to_vec(),
+                None,
+                Some("http://127.0.0.1:9/".to_string()),
+            )
+            .unwrap();
+
+            results.push((result, session.exchanges().len()));
+        }
+
+        assert_eq!(results[0], results[1]);
+    }
+
+    #[test]
+    fn shared_assets_requested_once() {
+        // Server which counts requests made to it
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let server: String = format!("http://{}", listener.local_addr().unwrap());
+        let requests: Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+        let server_requests = requests.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut request: Vec<u8> = vec![];
+                let mut buffer = [0; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let size: usize = stream.read(&mut buffer).unwrap();
+                    if size == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buffer[..size]);
+                }
+                server_requests.fetch_add(1, Ordering::SeqCst);
+
+                let (content_type, body): (&str, &str) = if request.starts_with(b"GET /style.css ")
+                {
+                    ("text/css", "body { background: url(image.png); }")
+                } else {
+                    ("image/png", "PNG")
+                };
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    content_type,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+
+        // Image is referenced from the document twice, and from its stylesheet once
+        let html: String = format!(
+            "<html><head><link rel=\"stylesheet\" href=\"{s}/style.css\"></head><body><img src=\"{s}/image.png\"><img src=\"{s}/image.png#again\"></body></html>",
+            s = server
+        );
+        let mut results: Vec<(Vec<u8>, usize)> = vec![];
+
+        for parallel_requests in [1, 4] {
+            let mut options = MonolithOptions::default();
+            options.no_metadata = true;
+            options.parallel_requests = parallel_requests;
+            options.record_exchanges = true;
+            options.silent = true;
+
+            let mut session: Session =
+                Session::new(Some(Cache::new(usize::MAX, None)), None, options).unwrap();
+            requests.store(0, Ordering::SeqCst);
+            let (result, _title) = create_monolithic_document_from_data(
+                &mut session,
+                html.as_bytes().to_vec(),
+                None,
+                Some(format!("{}/", server)),
+            )
+            .unwrap();
+
+            // Every request which was made got recorded
+            let exchanges: usize = session
+                .exchanges()
+                .iter()
+                .filter(|exchange| !exchange.from_cache)
+                .count();
+            assert_eq!(exchanges, requests.load(Ordering::SeqCst));
+
+            results.push((result, exchanges));
+        }
+
+        assert_eq!(results[0], results[1]);
+    }
+}
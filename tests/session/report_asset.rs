@@ -0,0 +1,193 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::env;
+
+    use monolith::core::MonolithOptions;
+    use monolith::html;
+    use monolith::report::AssetOutcome;
+    use monolith::session::Session;
+    use monolith::url::Url;
+
+    #[test]
+    fn retrieved_asset() {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options).unwrap();
+
+        let document_url: Url = Url::from_file_path(
+            env::current_dir()
+                .unwrap()
+                .join("tests/_data_/basic/local-file.html"),
+        )
+        .unwrap();
+        let style_url: Url = Url::from_file_path(
+            env::current_dir()
+                .unwrap()
+                .join("tests/_data_/basic/local-style.css"),
+        )
+        .unwrap();
+        let (data, _, _, _) = session.retrieve_asset(&document_url, &style_url).unwrap();
+        session.report_asset(
+            &document_url,
+            &style_url,
+            "link[href]",
+            AssetOutcome::Embedded,
+        );
+
+        let report = session.report();
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].url, style_url);
+        assert_eq!(report[0].parent_url, document_url);
+        assert_eq!(report[0].context, "link[href]");
+        assert_eq!(report[0].final_url, Some(style_url.clone()));
+        assert_eq!(report[0].media_type, "text/css");
+        assert_eq!(report[0].size, data.len());
+        assert!(!report[0].from_cache);
+        assert_eq!(report[0].outcome, AssetOutcome::Embedded);
+        assert_eq!(report[0].error, None);
+    }
+
+    #[test]
+    fn excluded_by_options() {
+        let html: &str = "<img src=\"a.png\" srcset=\"b.png 2x\"><script src=\"c.js\"></script>";
+        let dom = html::html_to_dom(&html.as_bytes().to_vec(), "".to_string());
+
+        let mut options = MonolithOptions::default();
+        options.no_images = true;
+        options.no_js = true;
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options).unwrap();
+
+        html::walk(
+            &mut session,
+            &Url::parse("https://example.com/").unwrap(),
+            &dom.document,
+        );
+
+        let report: Vec<(&str, &str, AssetOutcome)> = session
+            .report()
+            .iter()
+            .map(|reported_asset| {
+                (
+                    reported_asset.url.as_str(),
+                    reported_asset.context.as_str(),
+                    reported_asset.outcome.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            report,
+            vec![
+                (
+                    "https://example.com/a.png",
+                    "img[src]",
+                    AssetOutcome::Excluded
+                ),
+                (
+                    "https://example.com/b.png",
+                    "srcset",
+                    AssetOutcome::Excluded
+                ),
+                (
+                    "https://example.com/c.js",
+                    "script[src]",
+                    AssetOutcome::Excluded
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn report_of_current_document() {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options).unwrap();
+
+        let document_url: Url = Url::parse("https://example.com/").unwrap();
+        session.report_asset(
+            &document_url,
+            &Url::parse("https://example.com/1.png").unwrap(),
+            "img[src]",
+            AssetOutcome::Excluded,
+        );
+        session.start_document();
+        session.report_asset(
+            &document_url,
+            &Url::parse("https://example.com/2.png").unwrap(),
+            "img[src]",
+            AssetOutcome::Excluded,
+        );
+
+        assert_eq!(session.report().len(), 2);
+        assert_eq!(session.document_report().len(), 1);
+        assert_eq!(
+            session.document_report()[0].url.as_str(),
+            "https://example.com/2.png"
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::core::{MonolithError, MonolithOptions};
+    use monolith::report::AssetOutcome;
+    use monolith::session::Session;
+    use monolith::url::Url;
+
+    #[test]
+    fn data_url() {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options).unwrap();
+
+        // Data URLs are already part of the document
+        session.report_asset(
+            &Url::parse("https://example.com/").unwrap(),
+            &Url::parse("data:image/png;base64,").unwrap(),
+            "img[src]",
+            AssetOutcome::Embedded,
+        );
+
+        assert!(session.report().is_empty());
+    }
+
+    #[test]
+    fn failed_retrieval() {
+        let mut options = MonolithOptions::default();
+        options.silent = true;
+
+        let mut session: Session = Session::new(None, None, options).unwrap();
+
+        let document_url: Url = Url::parse("https://example.com/").unwrap();
+        let file_url: Url = Url::parse("file:///etc/passwd").unwrap();
+        assert!(session.retrieve_asset(&document_url, &file_url).is_err());
+        session.report_asset(&document_url, &file_url, "img[src]", AssetOutcome::Dropped);
+
+        let report = session.report();
+        assert_eq!(report[0].final_url, None);
+        assert_eq!(report[0].size, 0);
+        assert_eq!(report[0].outcome, AssetOutcome::Dropped);
+        assert_eq!(
+            report[0].error,
+            Some(MonolithError::SecurityError { url: file_url })
+        );
+    }
+}
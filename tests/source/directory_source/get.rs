@@ -0,0 +1,109 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::process;
+
+    use monolith::source::{AssetSource, DirectorySource};
+    use monolith::url::Url;
+
+    pub fn create_mirror(name: &str) -> PathBuf {
+        let directory = env::temp_dir().join(format!("monolith-mirror-{}-{}", name, process::id()));
+        fs::create_dir_all(directory.join("example.com/css")).unwrap();
+        fs::write(directory.join("example.com/css/style.css"), "body{}").unwrap();
+        fs::write(directory.join("example.com/index.html"), "<html></html>").unwrap();
+        fs::write(directory.join("secret.txt"), "secret").unwrap();
+        directory
+    }
+
+    #[test]
+    fn file() {
+        let directory = create_mirror("file");
+        let source = DirectorySource::new(&directory.display().to_string());
+
+        let asset = source
+            .get(&Url::parse("https://example.com/css/style.css").unwrap())
+            .unwrap();
+
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(asset.url.as_str(), "https://example.com/css/style.css");
+        assert_eq!(asset.media_type, "text/css");
+        assert_eq!(asset.data, b"body{}");
+    }
+
+    #[test]
+    fn directory_index() {
+        let directory = create_mirror("index");
+        let source = DirectorySource::new(&directory.display().to_string());
+
+        let asset = source
+            .get(&Url::parse("https://example.com/").unwrap())
+            .unwrap();
+
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(asset.media_type, "text/html");
+        assert_eq!(asset.data, b"<html></html>");
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use std::fs;
+
+    use super::passing::create_mirror;
+    use monolith::source::{AssetSource, DirectorySource};
+    use monolith::url::Url;
+
+    #[test]
+    fn missing_file() {
+        let directory = create_mirror("missing");
+        let source = DirectorySource::new(&directory.display().to_string());
+
+        let asset = source.get(&Url::parse("https://example.com/script.js").unwrap());
+
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert!(asset.is_none());
+    }
+
+    #[test]
+    fn other_host() {
+        let directory = create_mirror("host");
+        let source = DirectorySource::new(&directory.display().to_string());
+
+        let asset = source.get(&Url::parse("https://example.org/css/style.css").unwrap());
+
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert!(asset.is_none());
+    }
+
+    #[test]
+    fn data_url() {
+        let directory = create_mirror("data");
+        let source = DirectorySource::new(&directory.display().to_string());
+
+        let asset = source.get(&Url::parse("data:text/plain,secret").unwrap());
+
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert!(asset.is_none());
+    }
+}
@@ -0,0 +1,74 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::source::{AssetSource, MemorySource};
+    use monolith::url::Url;
+
+    #[test]
+    fn inserted_asset() {
+        let mut source = MemorySource::new();
+        source.insert(
+            &Url::parse("https://example.com/image.png").unwrap(),
+            vec![1, 2, 3],
+            "image/png",
+            "",
+        );
+
+        let asset = source
+            .get(&Url::parse("https://example.com/image.png").unwrap())
+            .unwrap();
+        assert_eq!(asset.url.as_str(), "https://example.com/image.png");
+        assert_eq!(asset.media_type, "image/png");
+        assert_eq!(asset.charset, "");
+        assert_eq!(asset.data, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn ignores_fragment() {
+        let mut source = MemorySource::new();
+        source.insert(
+            &Url::parse("https://example.com/icons.svg").unwrap(),
+            b"<svg></svg>".to_vec(),
+            "image/svg+xml",
+            "",
+        );
+
+        assert!(source
+            .get(&Url::parse("https://example.com/icons.svg#home").unwrap())
+            .is_some());
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::source::{AssetSource, MemorySource};
+    use monolith::url::Url;
+
+    #[test]
+    fn missing_asset() {
+        let mut source = MemorySource::new();
+        source.insert(
+            &Url::parse("https://example.com/image.png").unwrap(),
+            vec![1, 2, 3],
+            "image/png",
+            "",
+        );
+
+        assert!(source
+            .get(&Url::parse("https://example.com/image.png?size=2").unwrap())
+            .is_none());
+    }
+}
@@ -0,0 +1,2 @@
+mod directory_source;
+mod memory_source;
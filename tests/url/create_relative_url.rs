@@ -0,0 +1,81 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::env;
+    use std::path::Path;
+
+    use monolith::url;
+
+    #[test]
+    fn relative_path() {
+        assert_eq!(
+            url::create_relative_url(Path::new("page_files/"), Path::new("output")),
+            "page_files"
+        );
+    }
+
+    #[test]
+    fn relative_path_with_parent_directory() {
+        assert_eq!(
+            url::create_relative_url(Path::new("./../assets/page_files"), Path::new("")),
+            "../assets/page_files"
+        );
+    }
+
+    #[test]
+    fn special_characters() {
+        assert_eq!(
+            url::create_relative_url(Path::new("page files/#1?/100%"), Path::new("")),
+            "page%20files/%231%3F/100%25"
+        );
+    }
+
+    #[test]
+    fn absolute_path() {
+        let base_directory = env::temp_dir().join("monolith").join("pages");
+
+        assert_eq!(
+            url::create_relative_url(
+                &env::temp_dir().join("monolith").join("assets"),
+                &base_directory
+            ),
+            "../assets"
+        );
+    }
+
+    #[test]
+    fn absolute_path_within_base_directory() {
+        let base_directory = env::temp_dir().join("monolith");
+
+        assert_eq!(
+            url::create_relative_url(&base_directory.join("assets"), &base_directory),
+            "assets"
+        );
+    }
+
+    #[test]
+    fn absolute_path_of_base_directory() {
+        let base_directory = env::temp_dir().join("monolith");
+
+        assert_eq!(
+            url::create_relative_url(&base_directory, &base_directory.join(".")),
+            ""
+        );
+    }
+
+    #[test]
+    fn absolute_path_from_current_directory() {
+        let current_directory = env::current_dir().unwrap();
+
+        assert_eq!(
+            url::create_relative_url(&current_directory.join("page_files"), Path::new("")),
+            "page_files"
+        );
+    }
+}
@@ -1,7 +1,9 @@
 mod clean_url;
 mod create_data_url;
+mod create_relative_url;
 mod domain_is_within_domain;
 mod get_referer_url;
 mod is_url_and_has_protocol;
 mod parse_data_url;
+mod parse_proxy_url;
 mod resolve_url;
@@ -0,0 +1,232 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use chrono::Utc;
+    use std::time::Duration;
+
+    use monolith::cache::{Cache, CacheHeaders};
+    use monolith::core::{
+        create_monolithic_document_from_data, MonolithOptions, MonolithOutputFormat,
+    };
+    use monolith::session::{HttpExchange, Session};
+    use monolith::url::Url;
+    use monolith::warc::create_warc;
+
+    fn record_types(warc: &str) -> Vec<&str> {
+        warc.lines()
+            .filter_map(|line| line.strip_prefix("WARC-Type: "))
+            .collect()
+    }
+
+    #[test]
+    fn document_only() {
+        let warc = create_warc(
+            &Url::parse("https://example.com/").unwrap(),
+            b"<html></html>\n",
+            &[],
+        );
+        let warc = String::from_utf8_lossy(&warc);
+
+        assert!(warc.starts_with("WARC/1.1\r\nWARC-Type: warcinfo\r\n"));
+        assert_eq!(record_types(&warc), vec!["warcinfo", "resource"]);
+        assert!(warc.contains("WARC-Target-URI: https://example.com/\r\n"));
+        assert!(warc.contains(
+            "Content-Type: text/html\r\nContent-Length: 14\r\n\r\n<html></html>\n\r\n\r\n"
+        ));
+    }
+
+    #[test]
+    fn exchanges() {
+        let exchanges = vec![
+            HttpExchange {
+                date: Utc::now(),
+                url: Url::parse("https://example.com/style.css?v=1").unwrap(),
+                request_headers: vec![
+                    ("host".to_string(), "example.com".to_string()),
+                    ("referer".to_string(), "https://example.com/".to_string()),
+                ],
+                remote_address: Some("93.184.216.34".to_string()),
+                http_version: "HTTP/1.1".to_string(),
+                status: 200,
+                response_headers: vec![
+                    ("content-type".to_string(), "text/css".to_string()),
+                    ("transfer-encoding".to_string(), "chunked".to_string()),
+                ],
+                data: b"body{}".to_vec(),
+                wait: Duration::ZERO,
+                receive: Duration::ZERO,
+                from_cache: false,
+                error: None,
+            },
+            HttpExchange {
+                date: Utc::now(),
+                url: Url::parse("https://example.com/old.png").unwrap(),
+                request_headers: vec![],
+                remote_address: None,
+                http_version: "HTTP/1.1".to_string(),
+                status: 301,
+                response_headers: vec![("location".to_string(), "/new.png".to_string())],
+                data: vec![],
+                wait: Duration::ZERO,
+                receive: Duration::ZERO,
+                from_cache: false,
+                error: None,
+            },
+        ];
+        let warc = create_warc(
+            &Url::parse("https://example.com/").unwrap(),
+            b"<html></html>\n",
+            &exchanges,
+        );
+        let warc = String::from_utf8_lossy(&warc);
+
+        assert_eq!(
+            record_types(&warc),
+            vec!["warcinfo", "request", "response", "request", "response", "resource"]
+        );
+        assert!(warc.contains(
+            "\r\n\r\nGET /style.css?v=1 HTTP/1.1\r\nhost: example.com\r\nreferer: https://example.com/\r\n\r\n"
+        ));
+        assert!(warc.contains("WARC-IP-Address: 93.184.216.34\r\n"));
+        assert!(warc.contains(
+            "\r\n\r\nHTTP/1.1 200 OK\r\ncontent-type: text/css\r\nContent-Length: 6\r\n\r\nbody{}\r\n\r\n"
+        ));
+        assert!(warc.contains("HTTP/1.1 301 Moved Permanently\r\nlocation: /new.png\r\n"));
+        assert!(!warc.contains("transfer-encoding"));
+    }
+
+    #[test]
+    fn exchanges_without_responses() {
+        let exchange = HttpExchange {
+            date: Utc::now(),
+            url: Url::parse("https://example.com/1.png").unwrap(),
+            request_headers: vec![],
+            remote_address: None,
+            http_version: "".to_string(),
+            status: 0,
+            response_headers: vec![],
+            data: vec![],
+            wait: Duration::ZERO,
+            receive: Duration::ZERO,
+            from_cache: false,
+            error: Some("connection refused".to_string()),
+        };
+        let warc = create_warc(
+            &Url::parse("https://example.com/").unwrap(),
+            b"<html></html>\n",
+            &[exchange],
+        );
+        let warc = String::from_utf8_lossy(&warc);
+
+        // Failed requests never made it onto the wire
+        assert_eq!(record_types(&warc), vec!["warcinfo", "resource"]);
+    }
+
+    #[test]
+    fn assets_from_warm_cache() {
+        let mut cache: Cache = Cache::new(usize::MAX, None);
+        cache.set_response(
+            "http://127.0.0.1:9/image.png",
+            &b"PNG".to_vec(),
+            "image/png".to_string(),
+            "".to_string(),
+            None,
+            CacheHeaders::default(),
+        );
+
+        let mut options = MonolithOptions::default();
+        options.no_metadata = true;
+        options.output_format = MonolithOutputFormat::WARC;
+        options.silent = true;
+
+        let mut session: Session = Session::new(Some(cache), None, options).unwrap();
+        let (warc, _title) = create_monolithic_document_from_data(
+            &mut session,
+            b"<html><body><img src=\"http://127.0.0.1:9/image.png\"></body></html>".to_vec(),
+            None,
+            Some("http://127.0.0.1:9/".to_string()),
+        )
+        .unwrap();
+        let warc = String::from_utf8_lossy(&warc);
+
+        // Archive can be replayed without retrieving the asset once again
+        assert!(warc.contains("WARC-Target-URI: http://127.0.0.1:9/image.png\r\n"));
+        assert!(warc.contains("content-type: image/png\r\nContent-Length: 3\r\n\r\nPNG"));
+    }
+
+    #[test]
+    fn reused_assets() {
+        let exchange = HttpExchange {
+            date: Utc::now(),
+            url: Url::parse("https://example.com/1.png").unwrap(),
+            request_headers: vec![],
+            remote_address: None,
+            http_version: "".to_string(),
+            status: 200,
+            response_headers: vec![("Content-Type".to_string(), "image/png".to_string())],
+            data: b"PNG".to_vec(),
+            wait: Duration::ZERO,
+            receive: Duration::ZERO,
+            from_cache: false,
+            error: None,
+        };
+        let reused_exchange = HttpExchange {
+            from_cache: true,
+            ..exchange.clone()
+        };
+        let cached_exchange = HttpExchange {
+            url: Url::parse("https://example.com/2.png").unwrap(),
+            data: b"PNG 2".to_vec(),
+            from_cache: true,
+            ..exchange.clone()
+        };
+        let revalidated_exchange = HttpExchange {
+            url: Url::parse("https://example.com/3.png").unwrap(),
+            status: 304,
+            response_headers: vec![],
+            data: vec![],
+            ..exchange.clone()
+        };
+        let revalidated_cached_exchange = HttpExchange {
+            url: Url::parse("https://example.com/3.png").unwrap(),
+            data: b"PNG 3".to_vec(),
+            from_cache: true,
+            ..exchange.clone()
+        };
+        let warc = create_warc(
+            &Url::parse("https://example.com/").unwrap(),
+            b"<html></html>\n",
+            &[
+                exchange,
+                reused_exchange,
+                cached_exchange,
+                revalidated_exchange,
+                revalidated_cached_exchange,
+            ],
+        );
+        let warc = String::from_utf8_lossy(&warc);
+
+        // Assets taken from cache get stored too, but only once
+        assert_eq!(
+            record_types(&warc),
+            vec![
+                "warcinfo", "request", "response", "request", "response", "request", "response",
+                "request", "response", "resource"
+            ]
+        );
+        assert_eq!(
+            warc.matches("WARC-Target-URI: https://example.com/1.png")
+                .count(),
+            2
+        );
+        assert!(warc.contains("Content-Length: 5\r\n\r\nPNG 2"));
+        assert!(warc.contains("HTTP/1.1 304 Not Modified"));
+        assert!(warc.contains("Content-Length: 5\r\n\r\nPNG 3"));
+    }
+}
@@ -0,0 +1,41 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::warc::create_warc_record;
+
+    #[test]
+    fn resource() {
+        let record = create_warc_record(
+            "resource",
+            vec![("WARC-Target-URI", "https://example.com/".to_string())],
+            b"<html></html>",
+        );
+
+        assert_eq!(
+            String::from_utf8_lossy(&record),
+            "WARC/1.1\r\n\
+WARC-Type: resource\r\n\
+WARC-Target-URI: https://example.com/\r\n\
+Content-Length: 13\r\n\
+\r\n\
+<html></html>\r\n\
+\r\n"
+        );
+    }
+
+    #[test]
+    fn empty_block() {
+        let record = create_warc_record("metadata", vec![], b"");
+
+        assert_eq!(
+            String::from_utf8_lossy(&record),
+            "WARC/1.1\r\nWARC-Type: metadata\r\nContent-Length: 0\r\n\r\n\r\n\r\n"
+        );
+    }
+}
@@ -0,0 +1,38 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use monolith::warc::create_warc_record_id;
+
+    #[test]
+    fn is_uuid_v5_urn() {
+        let record_id: String = create_warc_record_id("https://example.com/");
+
+        assert!(record_id.starts_with("<urn:uuid:"));
+        assert!(record_id.ends_with('>'));
+        assert_eq!(record_id.len(), 47);
+        // Version nibble
+        assert_eq!(&record_id[24..25], "5");
+    }
+
+    #[test]
+    fn same_name_same_id() {
+        assert_eq!(
+            create_warc_record_id("https://example.com/"),
+            create_warc_record_id("https://example.com/")
+        );
+    }
+
+    #[test]
+    fn different_names_different_ids() {
+        assert_ne!(
+            create_warc_record_id("https://example.com/a"),
+            create_warc_record_id("https://example.com/b")
+        );
+    }
+}
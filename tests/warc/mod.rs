@@ -0,0 +1,4 @@
+mod create_warc;
+mod create_warc_record;
+mod create_warc_record_id;
+mod parse_warc;
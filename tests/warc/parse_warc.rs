@@ -0,0 +1,246 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::io::Write;
+
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    use monolith::url::Url;
+    use monolith::warc::{create_warc_record, parse_warc};
+
+    fn response_record(url: &str, http_response: &[u8]) -> Vec<u8> {
+        create_warc_record(
+            "response",
+            vec![
+                ("WARC-Target-URI", url.to_string()),
+                (
+                    "Content-Type",
+                    "application/http; msgtype=response".to_string(),
+                ),
+            ],
+            http_response,
+        )
+    }
+
+    fn warcinfo_record() -> Vec<u8> {
+        create_warc_record(
+            "warcinfo",
+            vec![("Content-Type", "application/warc-fields".to_string())],
+            b"software: test\r\n",
+        )
+    }
+
+    #[test]
+    fn response() {
+        let mut warc = warcinfo_record();
+        warc.extend(response_record(
+            "https://example.com/style.css",
+            b"HTTP/1.1 200 OK\r\nContent-Type: text/css; charset=utf-8\r\n\r\nbody{}",
+        ));
+        let archive = parse_warc(&warc).unwrap();
+
+        let resource = archive
+            .get(&Url::parse("https://example.com/style.css#fragment").unwrap())
+            .unwrap();
+        assert_eq!(resource.url.as_str(), "https://example.com/style.css");
+        assert_eq!(resource.media_type, "text/css");
+        assert_eq!(resource.charset, "utf-8");
+        assert_eq!(resource.data, b"body{}");
+    }
+
+    #[test]
+    fn chunked_and_compressed_response() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"Hello, World!").unwrap();
+        let compressed: Vec<u8> = encoder.finish().unwrap();
+
+        let mut http_response: Vec<u8> = b"HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Encoding: gzip\r\nTransfer-Encoding: chunked\r\n\r\n"
+            .to_vec();
+        http_response.extend(format!("{:x};ext=1\r\n", 10).as_bytes());
+        http_response.extend(&compressed[..10]);
+        http_response.extend(format!("\r\n{:x}\r\n", compressed.len() - 10).as_bytes());
+        http_response.extend(&compressed[10..]);
+        http_response.extend(b"\r\n0\r\n\r\n");
+
+        let archive = parse_warc(&response_record("https://example.com/", &http_response)).unwrap();
+
+        assert_eq!(
+            archive
+                .get(&Url::parse("https://example.com/").unwrap())
+                .unwrap()
+                .data,
+            b"Hello, World!"
+        );
+    }
+
+    #[test]
+    fn redirects() {
+        let mut warc = response_record(
+            "https://example.com/a",
+            b"HTTP/1.1 301 Moved Permanently\r\nLocation: /b\r\n\r\n",
+        );
+        warc.extend(response_record(
+            "https://example.com/b",
+            b"HTTP/1.1 302 Found\r\nLocation: https://example.org/c\r\n\r\n",
+        ));
+        warc.extend(response_record(
+            "https://example.org/c",
+            b"HTTP/1.1 200 OK\r\nContent-Type: text/html\r\n\r\n<html></html>",
+        ));
+        let archive = parse_warc(&warc).unwrap();
+
+        for url in ["https://example.com/a", "https://example.com/b"] {
+            let resource = archive.get(&Url::parse(url).unwrap()).unwrap();
+            assert_eq!(resource.url.as_str(), "https://example.org/c");
+            assert_eq!(resource.data, b"<html></html>");
+        }
+    }
+
+    #[test]
+    fn resource_and_revisit_records() {
+        let mut warc = create_warc_record(
+            "resource",
+            vec![
+                ("WARC-Target-URI", "<https://example.com/1.png>".to_string()),
+                ("Content-Type", "image/png".to_string()),
+            ],
+            b"\x89PNG\r\n\x1a\n",
+        );
+        warc.extend(create_warc_record(
+            "revisit",
+            vec![
+                ("WARC-Target-URI", "https://example.com/2.png".to_string()),
+                (
+                    "WARC-Refers-To-Target-URI",
+                    "https://example.com/1.png".to_string(),
+                ),
+            ],
+            b"",
+        ));
+        let archive = parse_warc(&warc).unwrap();
+
+        let resource = archive
+            .get(&Url::parse("https://example.com/1.png").unwrap())
+            .unwrap();
+        assert_eq!(resource.media_type, "image/png");
+        assert_eq!(resource.data, b"\x89PNG\r\n\x1a\n");
+        assert_eq!(
+            archive
+                .get(&Url::parse("https://example.com/2.png").unwrap())
+                .unwrap()
+                .url,
+            resource.url
+        );
+    }
+
+    #[test]
+    fn responses_take_precedence_over_resources() {
+        let mut warc = response_record(
+            "https://example.com/",
+            b"HTTP/1.1 200 OK\r\nContent-Type: text/html\r\n\r\noriginal",
+        );
+        warc.extend(create_warc_record(
+            "resource",
+            vec![
+                ("WARC-Target-URI", "https://example.com/".to_string()),
+                ("Content-Type", "text/html".to_string()),
+            ],
+            b"derived",
+        ));
+        let archive = parse_warc(&warc).unwrap();
+
+        assert_eq!(archive.len(), 1);
+        assert_eq!(
+            archive
+                .get(&Url::parse("https://example.com/").unwrap())
+                .unwrap()
+                .data,
+            b"original"
+        );
+    }
+
+    #[test]
+    fn compressed_warc() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&warcinfo_record()).unwrap();
+        let mut warc: Vec<u8> = encoder.finish().unwrap();
+        // Every record is compressed individually
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(&response_record(
+                "https://example.com/",
+                b"HTTP/1.1 200 OK\r\n\r\nHello",
+            ))
+            .unwrap();
+        warc.extend(encoder.finish().unwrap());
+        let archive = parse_warc(&warc).unwrap();
+
+        assert_eq!(
+            archive
+                .get(&Url::parse("https://example.com/").unwrap())
+                .unwrap()
+                .data,
+            b"Hello"
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::url::Url;
+    use monolith::warc::{create_warc_record, parse_warc};
+
+    #[test]
+    fn not_warc() {
+        assert!(parse_warc(b"<html></html>").is_none());
+    }
+
+    #[test]
+    fn error_responses() {
+        let warc = create_warc_record(
+            "response",
+            vec![("WARC-Target-URI", "https://example.com/".to_string())],
+            b"HTTP/1.1 404 Not Found\r\nContent-Type: text/html\r\n\r\nNot found",
+        );
+        let archive = parse_warc(&warc).unwrap();
+
+        assert!(archive.is_empty());
+        assert!(archive
+            .get(&Url::parse("https://example.com/").unwrap())
+            .is_none());
+    }
+
+    #[test]
+    fn oversized_chunk() {
+        let http_response: &[u8] = b"HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nTransfer-Encoding: chunked\r\n\r\nffffffffffffffff\r\nHello\r\n0\r\n\r\n";
+        let warc = create_warc_record(
+            "response",
+            vec![("WARC-Target-URI", "https://example.com/".to_string())],
+            http_response,
+        );
+        let archive = parse_warc(&warc).unwrap();
+
+        // Body which can't be decoded is kept as it was
+        assert_eq!(
+            archive
+                .get(&Url::parse("https://example.com/").unwrap())
+                .unwrap()
+                .data,
+            b"ffffffffffffffff\r\nHello\r\n0\r\n\r\n"
+        );
+    }
+}
@@ -0,0 +1,103 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use plist::{Dictionary, Value};
+
+    use monolith::session::EmbeddedAsset;
+    use monolith::url::Url;
+    use monolith::webarchive::create_webarchive;
+
+    fn document() -> EmbeddedAsset {
+        EmbeddedAsset {
+            url: Url::parse("https://example.com/").unwrap(),
+            media_type: "text/html".to_string(),
+            charset: "UTF-8".to_string(),
+            data: b"<html><body><img src=\"https://example.com/1.png\"></body></html>\n".to_vec(),
+        }
+    }
+
+    fn read_webarchive(data: &[u8]) -> Dictionary {
+        // Has to be a binary property list
+        assert!(data.starts_with(b"bplist00"));
+
+        Value::from_reader(std::io::Cursor::new(data))
+            .unwrap()
+            .into_dictionary()
+            .unwrap()
+    }
+
+    #[test]
+    fn document_only() {
+        let webarchive = read_webarchive(&create_webarchive(&document(), &[]));
+        let main_resource = webarchive["WebMainResource"].as_dictionary().unwrap();
+
+        assert_eq!(
+            main_resource["WebResourceURL"].as_string(),
+            Some("https://example.com/")
+        );
+        assert_eq!(
+            main_resource["WebResourceMIMEType"].as_string(),
+            Some("text/html")
+        );
+        assert_eq!(
+            main_resource["WebResourceTextEncodingName"].as_string(),
+            Some("UTF-8")
+        );
+        assert_eq!(main_resource["WebResourceFrameName"].as_string(), Some(""));
+        assert_eq!(
+            main_resource["WebResourceData"].as_data(),
+            Some(document().data.as_slice())
+        );
+        assert!(!webarchive.contains_key("WebSubresources"));
+    }
+
+    #[test]
+    fn subresources() {
+        let assets = vec![
+            EmbeddedAsset {
+                url: Url::parse("https://example.com/1.png").unwrap(),
+                media_type: "image/png".to_string(),
+                charset: "US-ASCII".to_string(),
+                data: vec![0x89, b'P', b'N', b'G', 0, 255],
+            },
+            EmbeddedAsset {
+                url: Url::parse("https://example.com/style.css").unwrap(),
+                media_type: "text/css".to_string(),
+                charset: "windows-1252".to_string(),
+                data: b"body { content: \"\xe9\"; }".to_vec(),
+            },
+        ];
+        let webarchive = read_webarchive(&create_webarchive(&document(), &assets));
+        let subresources = webarchive["WebSubresources"].as_array().unwrap();
+
+        assert_eq!(subresources.len(), 2);
+
+        let image = subresources[0].as_dictionary().unwrap();
+        assert_eq!(
+            image["WebResourceURL"].as_string(),
+            Some("https://example.com/1.png")
+        );
+        assert_eq!(image["WebResourceMIMEType"].as_string(), Some("image/png"));
+        assert!(!image.contains_key("WebResourceTextEncodingName"));
+        assert_eq!(
+            image["WebResourceData"].as_data(),
+            Some(assets[0].data.as_slice())
+        );
+
+        let stylesheet = subresources[1].as_dictionary().unwrap();
+        assert_eq!(
+            stylesheet["WebResourceTextEncodingName"].as_string(),
+            Some("windows-1252")
+        );
+        assert_eq!(
+            stylesheet["WebResourceData"].as_data(),
+            Some(assets[1].data.as_slice())
+        );
+    }
+}
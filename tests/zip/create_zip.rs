@@ -0,0 +1,151 @@
+//  ██████╗  █████╗ ███████╗███████╗██╗███╗   ██╗ ██████╗
+//  ██╔══██╗██╔══██╗██╔════╝██╔════╝██║████╗  ██║██╔════╝
+//  ██████╔╝███████║███████╗███████╗██║██╔██╗ ██║██║  ███╗
+//  ██╔═══╝ ██╔══██║╚════██║╚════██║██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║███████║███████║██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚══════╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod passing {
+    use std::io::Read;
+
+    use flate2::read::DeflateDecoder;
+    use flate2::Crc;
+
+    use monolith::zip::{create_zip, ZipFile};
+
+    fn read_u16(zip: &[u8], position: usize) -> usize {
+        u16::from_le_bytes(zip[position..position + 2].try_into().unwrap()) as usize
+    }
+
+    fn read_u32(zip: &[u8], position: usize) -> u32 {
+        u32::from_le_bytes(zip[position..position + 4].try_into().unwrap())
+    }
+
+    // Returns path, compression method and decompressed contents of every file
+    fn read_files(zip: &[u8]) -> Vec<(String, usize, Vec<u8>)> {
+        let mut files = vec![];
+        let mut position = 0;
+
+        while zip[position..].starts_with(b"PK\x03\x04") {
+            let method = read_u16(zip, position + 8);
+            let crc = read_u32(zip, position + 14);
+            let compressed_size = read_u32(zip, position + 18) as usize;
+            let path_length = read_u16(zip, position + 26);
+            let path = String::from_utf8(zip[position + 30..position + 30 + path_length].to_vec())
+                .unwrap();
+            let contents = &zip[position + 30 + path_length..][..compressed_size];
+
+            let mut data = vec![];
+            if method == 8 {
+                DeflateDecoder::new(contents)
+                    .read_to_end(&mut data)
+                    .unwrap();
+            } else {
+                data = contents.to_vec();
+            }
+            let mut actual_crc = Crc::new();
+            actual_crc.update(&data);
+            assert_eq!(actual_crc.sum(), crc);
+
+            files.push((path, method, data));
+            position += 30 + path_length + compressed_size;
+        }
+
+        files
+    }
+
+    #[test]
+    fn compressed_and_stored_files() {
+        let zip = create_zip(&[
+            ZipFile {
+                path: "mimetype".to_string(),
+                data: b"application/epub+zip".to_vec(),
+                stored: true,
+            },
+            ZipFile {
+                path: "index.html".to_string(),
+                data: "<p>Hello</p>".repeat(100).into_bytes(),
+                stored: false,
+            },
+        ]);
+
+        assert_eq!(
+            read_files(&zip),
+            vec![
+                ("mimetype".to_string(), 0, b"application/epub+zip".to_vec()),
+                (
+                    "index.html".to_string(),
+                    8,
+                    "<p>Hello</p>".repeat(100).into_bytes()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn incompressible_file() {
+        let zip = create_zip(&[ZipFile {
+            path: "1.png".to_string(),
+            data: vec![1, 2, 3],
+            stored: false,
+        }]);
+
+        assert_eq!(
+            read_files(&zip),
+            vec![("1.png".to_string(), 0, vec![1, 2, 3])]
+        );
+    }
+
+    #[test]
+    fn central_directory() {
+        let zip = create_zip(&[
+            ZipFile {
+                path: "a.txt".to_string(),
+                data: b"a".to_vec(),
+                stored: false,
+            },
+            ZipFile {
+                path: "b.txt".to_string(),
+                data: b"b".to_vec(),
+                stored: false,
+            },
+        ]);
+        let end = zip.len() - 22;
+
+        assert!(zip[end..].starts_with(b"PK\x05\x06"));
+        assert_eq!(read_u16(&zip, end + 8), 2);
+        assert_eq!(read_u16(&zip, end + 10), 2);
+
+        let central_directory_offset = read_u32(&zip, end + 16) as usize;
+        assert_eq!(
+            central_directory_offset + read_u32(&zip, end + 12) as usize,
+            end
+        );
+        assert!(zip[central_directory_offset..].starts_with(b"PK\x01\x02"));
+        assert_eq!(
+            &zip[central_directory_offset + 46..central_directory_offset + 51],
+            b"a.txt"
+        );
+    }
+}
+
+//  ███████╗ █████╗ ██╗██╗     ██╗███╗   ██╗ ██████╗
+//  ██╔════╝██╔══██╗██║██║     ██║████╗  ██║██╔════╝
+//  █████╗  ███████║██║██║     ██║██╔██╗ ██║██║  ███╗
+//  ██╔══╝  ██╔══██║██║██║     ██║██║╚██╗██║██║   ██║
+//  ██║     ██║  ██║██║███████╗██║██║ ╚████║╚██████╔╝
+//  ╚═╝     ╚═╝  ╚═╝╚═╝╚══════╝╚═╝╚═╝  ╚═══╝ ╚═════╝
+
+#[cfg(test)]
+mod failing {
+    use monolith::zip::create_zip;
+
+    #[test]
+    fn no_files() {
+        let zip = create_zip(&[]);
+
+        assert_eq!(zip.len(), 22);
+        assert!(zip.starts_with(b"PK\x05\x06"));
+    }
+}
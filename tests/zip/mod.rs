@@ -0,0 +1 @@
+mod create_zip;